@@ -34,11 +34,25 @@ fn main() -> Result<()> {
 
     let old: PathBuf = find_lib(&out_dir)?;
     let new = old.with_file_name("dynlib");
-    fs::rename(old, new)?;
+    fs::rename(old, &new)?;
+
+    // Used to name the persistent cache file, so that different library builds never collide.
+    let hash: u64 = fnv1a(&fs::read(&new)?);
+    println!("cargo::rustc-env=UNDERANALYZER_DYNLIB_HASH={hash:016x}");
 
     Ok(())
 }
 
+/// 64-bit FNV-1a. Not cryptographic; only used to tell library builds apart.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
 fn find_lib(out_dir: &Path) -> Result<PathBuf> {
     let dir = std::fs::read_dir(out_dir)?;
     for entry in dir {
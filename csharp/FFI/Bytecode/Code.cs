@@ -4,18 +4,19 @@ using Underanalyzer;
 namespace FFI;
 
 [StructLayout(LayoutKind.Sequential)]
-public readonly struct GMCode : IGMCode
+public readonly unsafe struct GMCode : IGMCode
 {
     public readonly RustString Name;
     public readonly RawArray<GMInstruction> Instructions;
     public readonly RawArray<GMCode> Children;
+    public readonly GMCode* ParentPtr; // null for root code entries
     public readonly int Length;
     public readonly int StartOffset;
     public readonly short ArgumentCount;
     public readonly short LocalCount;
 
     public int InstructionCount => (int)Instructions.Len;
-    public IGMCode? Parent => null; // TODO
+    public IGMCode? Parent => ParentPtr == null ? null : (IGMCode)(*ParentPtr);
     public int ChildCount => (int)Children.Len;
     IGMString IGMCode.Name => Name;
     int IGMCode.Length => Length;
@@ -25,5 +26,10 @@ public readonly struct GMCode : IGMCode
 
     public IGMCode GetChild(int index) => Children.Get(index);
 
-    public IGMInstruction GetInstruction(int index) => Instructions.Get(index);
+    public IGMInstruction GetInstruction(int index)
+    {
+        if (ListingTrace.Current is ListingTrace trace)
+            return new TracedInstruction(Instructions.Get(index), index, trace);
+        return Instructions.Get(index);
+    }
 }
@@ -16,6 +16,8 @@ public readonly struct GMInstruction : IGMInstruction
     public readonly int BranchOffset;
     public readonly int ArgumentCount;
     public readonly int AssetReference;
+    public readonly int VariableIndex; // -1 if the instruction has no variable
+    public readonly int FunctionIndex; // -1 if the instruction has no function
     public readonly short ValueShort;
     public readonly short ExtKind;
     public readonly short InstType;
@@ -35,8 +37,11 @@ public readonly struct GMInstruction : IGMInstruction
     DataType IGMInstruction.Type1 => checked((DataType)Type1);
     DataType IGMInstruction.Type2 => checked((DataType)Type2);
     InstanceType IGMInstruction.InstType => checked((InstanceType)InstType);
-    IGMVariable? IGMInstruction.ResolvedVariable => ResolvedVariable;
-    IGMFunction? IGMInstruction.ResolvedFunction => ResolvedFunction;
+    // Unresolved references are annotated instead of making the decompilation fail
+    IGMVariable? IGMInstruction.ResolvedVariable =>
+        ((IGMInstruction)this).TryFindVariable(GameContext.Decompiling);
+    IGMFunction? IGMInstruction.ResolvedFunction =>
+        ((IGMInstruction)this).TryFindFunction(GameContext.Decompiling);
     VariableType IGMInstruction.ReferenceVarType => checked((VariableType)ReferenceVarType);
     double IGMInstruction.ValueDouble => ValueDouble;
     short IGMInstruction.ValueShort => ValueShort;
@@ -76,18 +81,63 @@ public readonly struct GMInstruction : IGMInstruction
         };
     }
 
+    // An index is broken if it is out of range of the context's tables or points at an entry
+    // with a different name (e.g. a context built before the data file was changed).
+    // The name converted from the data file is looked up instead then.
     IGMFunction? IGMInstruction.TryFindFunction(IGameContext? context)
     {
+        if (FunctionIndex < 0)
+            return null;
+        if (context is not GameContext ctx)
+            return ResolvedFunction.Exists
+                ? ResolvedFunction
+                : new UnresolvedFunction(FunctionIndex);
+
         if (ResolvedFunction.Exists)
+        {
+            string name = ResolvedFunction.Name.Content;
+            bool broken =
+                !ctx.TryFindFunction(FunctionIndex, out GMFunction atIndex)
+                || atIndex.Name.Content != name;
+            if (broken && ctx.TryFindFunction(name, out GMFunction byName))
+                return byName;
             return ResolvedFunction;
-        return null; //TODO
+        }
+        if (ctx.TryFindFunction(FunctionIndex, out GMFunction function))
+            return function;
+        return new UnresolvedFunction(FunctionIndex);
     }
 
     IGMVariable? IGMInstruction.TryFindVariable(IGameContext? context)
     {
+        if (VariableIndex < 0)
+            return null;
+        InstanceType instanceType = (InstanceType)InstType;
+        if (context is not GameContext ctx)
+            return ResolvedVariable.Exists
+                ? ResolvedVariable
+                : new UnresolvedVariable(VariableIndex, instanceType);
+
         if (ResolvedVariable.Exists)
+        {
+            // Locals may only be named by the code locals, so a nameless entry is not broken
+            string name = ResolvedVariable.Name.Content;
+            bool broken =
+                !ctx.HasVariableIndex(VariableIndex)
+                || (
+                    ctx.TryFindVariable(VariableIndex, out GMVariable atIndex)
+                    && atIndex.Name.Content != name
+                );
+            if (broken && ctx.TryFindVariable(name, instanceType, out GMVariable byName))
+                return byName;
             return ResolvedVariable;
-        return null; //TODO
+        }
+        if (
+            ctx.TryFindVariable(VariableIndex, out GMVariable variable)
+            || ctx.TryFindVariableById(VariableIndex, out variable)
+        )
+            return variable;
+        return new UnresolvedVariable(VariableIndex, instanceType);
     }
 
     private bool BoolFromByte(byte number)
@@ -0,0 +1,143 @@
+using System.Text;
+using Underanalyzer;
+using Underanalyzer.Decompiler;
+using Underanalyzer.Decompiler.AST;
+using static Underanalyzer.IGMInstruction;
+
+namespace FFI;
+
+/// <summary>
+/// Records which instructions the variables and functions printed on each line of
+/// decompiled code come from. While a trace is active on the current thread,
+/// <see cref="GMCode.GetInstruction"/> hands out instructions that know their index.
+/// </summary>
+sealed class ListingTrace
+{
+    [ThreadStatic]
+    internal static ListingTrace? Current;
+
+    readonly List<(int Line, int Index)> Entries = [];
+    ASTPrinter? Printer;
+    int PrintedLength;
+    int PrintedLines;
+
+    /// <summary>
+    /// Decompiles the code entry and returns the trace, followed by the decompiled code.
+    /// The trace is a line with the number of entries, then one <c>line index</c> line each.
+    /// </summary>
+    public static string Decompile(IGameContext context, IGMCode code, IDecompileSettings settings)
+    {
+        ListingTrace trace = new();
+        Current = trace;
+        try
+        {
+            DecompileContext decompileContext = new(context, code, settings);
+            IStatementNode ast = decompileContext.DecompileToAST();
+            trace.Printer = new ASTPrinter(decompileContext);
+            ast.Print(trace.Printer);
+
+            StringBuilder output = new();
+            output.Append(trace.Entries.Count).Append('\n');
+            foreach ((int line, int index) in trace.Entries)
+                output.Append(line).Append(' ').Append(index).Append('\n');
+            output.Append(trace.Printer.OutputString);
+            return output.ToString();
+        }
+        finally
+        {
+            Current = null;
+        }
+    }
+
+    /// <summary>
+    /// Records that a reference of the instruction with the given index is being printed.
+    /// References read while building the AST are not recorded.
+    /// </summary>
+    public void Record(int index)
+    {
+        if (Printer is null)
+            return;
+        string output = Printer.OutputString;
+        for (int i = PrintedLength; i < output.Length; i++)
+        {
+            if (output[i] == '\n')
+                PrintedLines++;
+        }
+        PrintedLength = output.Length;
+        Entries.Add((PrintedLines, index));
+    }
+}
+
+/// <summary>
+/// An instruction handed out while a <see cref="ListingTrace"/> is active.
+/// Its variable and function record the instruction's index when they are printed.
+/// </summary>
+sealed class TracedInstruction(GMInstruction inner, int index, ListingTrace trace)
+    : IGMInstruction
+{
+    IGMInstruction Inner => inner;
+
+    public Opcode Kind => Inner.Kind;
+    public ExtendedOpcode ExtKind => Inner.ExtKind;
+    public ComparisonType ComparisonKind => Inner.ComparisonKind;
+    public DataType Type1 => Inner.Type1;
+    public DataType Type2 => Inner.Type2;
+    public InstanceType InstType => Inner.InstType;
+    public IGMVariable? ResolvedVariable => Trace(Inner.ResolvedVariable);
+    public IGMFunction? ResolvedFunction => Trace(Inner.ResolvedFunction);
+    public VariableType ReferenceVarType => Inner.ReferenceVarType;
+    public double ValueDouble => Inner.ValueDouble;
+    public short ValueShort => Inner.ValueShort;
+    public int ValueInt => Inner.ValueInt;
+    public long ValueLong => Inner.ValueLong;
+    public IGMString? ValueString => Inner.ValueString;
+    public int BranchOffset => Inner.BranchOffset;
+    public bool PopWithContextExit => Inner.PopWithContextExit;
+    public byte DuplicationSize => Inner.DuplicationSize;
+    public byte DuplicationSize2 => Inner.DuplicationSize2;
+    public int ArgumentCount => Inner.ArgumentCount;
+    public int PopSwapSize => Inner.PopSwapSize;
+    public int AssetReferenceId => Inner.AssetReferenceId;
+
+    public AssetType GetAssetReferenceType(IGameContext context) =>
+        inner.GetAssetReferenceType(context);
+
+    public IGMVariable? TryFindVariable(IGameContext? context) =>
+        Trace(Inner.TryFindVariable(context));
+
+    public IGMFunction? TryFindFunction(IGameContext? context) =>
+        Trace(Inner.TryFindFunction(context));
+
+    IGMVariable? Trace(IGMVariable? variable) =>
+        variable is null ? null : new TracedVariable(variable, index, trace);
+
+    IGMFunction? Trace(IGMFunction? function) =>
+        function is null ? null : new TracedFunction(function, index, trace);
+}
+
+sealed class TracedVariable(IGMVariable inner, int index, ListingTrace trace) : IGMVariable
+{
+    public IGMString Name
+    {
+        get
+        {
+            trace.Record(index);
+            return inner.Name;
+        }
+    }
+
+    public InstanceType InstanceType => inner.InstanceType;
+    public int VariableID => inner.VariableID;
+}
+
+sealed class TracedFunction(IGMFunction inner, int index, ListingTrace trace) : IGMFunction
+{
+    public IGMString Name
+    {
+        get
+        {
+            trace.Record(index);
+            return inner.Name;
+        }
+    }
+}
@@ -0,0 +1,32 @@
+using Underanalyzer;
+using static Underanalyzer.IGMInstruction;
+
+namespace FFI;
+
+/// <summary>
+/// A string created on the C# side, as opposed to <see cref="RustString"/>.
+/// </summary>
+readonly struct ManagedString(string content) : IGMString
+{
+    public string Content => content;
+}
+
+/// <summary>
+/// Placeholder for a variable reference that could not be resolved.
+/// The name makes it stand out in the decompiled code while keeping it valid GML.
+/// </summary>
+class UnresolvedVariable(int index, InstanceType instanceType) : IGMVariable
+{
+    public IGMString Name => new ManagedString($"__unresolved_variable_{index}");
+    public InstanceType InstanceType => instanceType;
+    public int VariableID => -1;
+}
+
+/// <summary>
+/// Placeholder for a function reference that could not be resolved.
+/// The name makes it stand out in the decompiled code while keeping it valid GML.
+/// </summary>
+class UnresolvedFunction(int index) : IGMFunction
+{
+    public IGMString Name => new ManagedString($"__unresolved_function_{index}");
+}
@@ -0,0 +1,227 @@
+using Underanalyzer;
+using Underanalyzer.Compiler;
+using static Underanalyzer.IGMInstruction;
+
+namespace FFI;
+
+/// <summary>
+/// Creates the instructions emitted by Underanalyzer's compiler.
+/// Variables and functions are only referenced by name; nothing is added to the data file here.
+/// </summary>
+sealed class CodeBuilder(GameContext gameContext) : ICodeBuilder
+{
+    public IGMInstruction CreateInstruction(int address, Opcode opcode)
+    {
+        return new CompiledInstruction(address, opcode);
+    }
+
+    public IGMInstruction CreateInstruction(int address, Opcode opcode, DataType dataType)
+    {
+        return new CompiledInstruction(address, opcode) { Type1 = dataType };
+    }
+
+    public IGMInstruction CreateInstruction(
+        int address,
+        Opcode opcode,
+        DataType dataType1,
+        DataType dataType2
+    )
+    {
+        return new CompiledInstruction(address, opcode) { Type1 = dataType1, Type2 = dataType2 };
+    }
+
+    public IGMInstruction CreateInstruction(
+        int address,
+        Opcode opcode,
+        short value,
+        DataType dataType
+    )
+    {
+        return new CompiledInstruction(address, opcode) { ValueShort = value, Type1 = dataType };
+    }
+
+    public IGMInstruction CreateInstruction(
+        int address,
+        Opcode opcode,
+        int value,
+        DataType dataType
+    )
+    {
+        return new CompiledInstruction(address, opcode) { ValueInt = value, Type1 = dataType };
+    }
+
+    public IGMInstruction CreateInstruction(
+        int address,
+        Opcode opcode,
+        long value,
+        DataType dataType
+    )
+    {
+        return new CompiledInstruction(address, opcode) { ValueLong = value, Type1 = dataType };
+    }
+
+    public IGMInstruction CreateInstruction(
+        int address,
+        Opcode opcode,
+        double value,
+        DataType dataType
+    )
+    {
+        return new CompiledInstruction(address, opcode) { ValueDouble = value, Type1 = dataType };
+    }
+
+    public IGMInstruction CreateCompareInstruction(
+        int address,
+        ComparisonType comparisonType,
+        DataType dataType1,
+        DataType dataType2
+    )
+    {
+        return new CompiledInstruction(address, Opcode.Compare)
+        {
+            ComparisonKind = comparisonType,
+            Type1 = dataType1,
+            Type2 = dataType2,
+        };
+    }
+
+    public IGMInstruction CreateDuplicateInstruction(
+        int address,
+        DataType dataType,
+        byte duplicationSize
+    )
+    {
+        return new CompiledInstruction(address, Opcode.Duplicate)
+        {
+            Type1 = dataType,
+            DuplicationSize = duplicationSize,
+        };
+    }
+
+    public IGMInstruction CreateDupSwapInstruction(
+        int address,
+        DataType dataType,
+        byte duplicationSize,
+        byte duplicationSize2
+    )
+    {
+        return new CompiledInstruction(address, Opcode.Duplicate)
+        {
+            Type1 = dataType,
+            DuplicationSize = duplicationSize,
+            DuplicationSize2 = duplicationSize2,
+        };
+    }
+
+    public IGMInstruction CreatePopSwapInstruction(int address, byte swapSize)
+    {
+        return new CompiledInstruction(address, Opcode.Pop)
+        {
+            Type1 = DataType.Int16,
+            Type2 = DataType.Variable,
+            PopSwapSize = swapSize,
+        };
+    }
+
+    public IGMInstruction CreateWithExitInstruction(int address)
+    {
+        return new CompiledInstruction(address, Opcode.PopWithContext)
+        {
+            PopWithContextExit = true,
+        };
+    }
+
+    public IGMInstruction CreateCallInstruction(int address, int argumentCount)
+    {
+        return new CompiledInstruction(address, Opcode.Call)
+        {
+            Type1 = DataType.Int32,
+            ArgumentCount = argumentCount,
+        };
+    }
+
+    public IGMInstruction CreateCallVariableInstruction(int address, int argumentCount)
+    {
+        return new CompiledInstruction(address, Opcode.CallVariable)
+        {
+            Type1 = DataType.Variable,
+            ArgumentCount = argumentCount,
+        };
+    }
+
+    public IGMInstruction CreateExtendedInstruction(int address, ExtendedOpcode extendedOpcode)
+    {
+        return new CompiledInstruction(address, Opcode.Extended)
+        {
+            Type1 = DataType.Int16,
+            ExtKind = extendedOpcode,
+        };
+    }
+
+    public IGMInstruction CreateExtendedInstruction(
+        int address,
+        ExtendedOpcode extendedOpcode,
+        int value
+    )
+    {
+        return new CompiledInstruction(address, Opcode.Extended)
+        {
+            Type1 = DataType.Int32,
+            ExtKind = extendedOpcode,
+            ValueInt = value,
+        };
+    }
+
+    public void PatchInstruction(
+        IGMInstruction instruction,
+        string variableName,
+        InstanceType variableInstanceType,
+        InstanceType instructionInstanceType,
+        VariableType variableType,
+        bool isBuiltin,
+        bool isStructVariable
+    )
+    {
+        var compiled = (CompiledInstruction)instruction;
+        compiled.ResolvedVariable = new CompiledVariable(
+            variableName,
+            variableInstanceType,
+            isBuiltin
+        );
+        compiled.InstType = instructionInstanceType;
+        compiled.ReferenceVarType = variableType;
+    }
+
+    public void PatchInstruction(
+        IGMInstruction instruction,
+        FunctionScope scope,
+        string functionName,
+        IBuiltinFunction? builtinFunction
+    )
+    {
+        ((CompiledInstruction)instruction).ResolvedFunction = new CompiledFunction(functionName);
+    }
+
+    public void PatchInstruction(IGMInstruction instruction, FunctionEntry functionEntry)
+    {
+        string name = functionEntry.FunctionName ?? "";
+        ((CompiledInstruction)instruction).ResolvedFunction = new CompiledFunction(name);
+    }
+
+    public void PatchInstruction(IGMInstruction instruction, string stringContent)
+    {
+        ((CompiledInstruction)instruction).ValueString = new ManagedString(stringContent);
+    }
+
+    public void PatchInstruction(IGMInstruction instruction, int branchOffset)
+    {
+        ((CompiledInstruction)instruction).BranchOffset = branchOffset;
+    }
+
+    public bool IsGlobalFunctionName(string name)
+    {
+        return gameContext.GetScriptIdByFunctionName(name, out _);
+    }
+
+    public void OnParseNameIdentifier(string name) { }
+}
@@ -0,0 +1,117 @@
+using Underanalyzer;
+using Underanalyzer.Compiler;
+using Underanalyzer.Compiler.Errors;
+
+namespace FFI;
+
+/// <summary>
+/// Thrown if Underanalyzer's compiler reports errors for the given source code.
+/// </summary>
+class CompileErrorException(IReadOnlyList<CompileErrorInfo> errors)
+    : Exception(string.Join('\n', errors.Select(e => e.Message)))
+{
+    public IReadOnlyList<CompileErrorInfo> Errors { get; } = errors;
+
+    public IReadOnlyList<string> Messages => Errors.Select(e => e.Message).ToList();
+}
+
+/// <summary>
+/// A compiler error: its full message (which mentions the position), the message without
+/// the position, and the 1-based line and column of the token it was reported at
+/// (0 if the compiler reported no token).
+/// </summary>
+readonly record struct CompileErrorInfo(string Message, string BaseMessage, int Line, int Column)
+{
+    public static CompileErrorInfo From(ICompileError error, string source)
+    {
+        string message = error.GenerateMessage();
+        int position = error.NearbyToken?.TextPosition ?? -1;
+        if (position < 0 || position > source.Length)
+            return new(message, error.BaseMessage, 0, 0);
+
+        int line = 1;
+        int lineStart = 0;
+        for (int i = 0; i < position; i++)
+        {
+            if (source[i] == '\n')
+            {
+                line++;
+                lineStart = i + 1;
+            }
+        }
+        // Columns count characters, like on the Rust side
+        int column = 1;
+        for (int i = lineStart; i < position; i++)
+        {
+            if (!char.IsLowSurrogate(source[i]))
+                column++;
+        }
+        return new(message, error.BaseMessage, line, column);
+    }
+}
+
+static class Compilation
+{
+    /// <summary>
+    /// Global scripts for GMLv2 games, where functions may be declared, and plain scripts before.
+    /// </summary>
+    public static CompileScriptKind DefaultKind(in GameContext gameContext)
+    {
+        return gameContext.UsingGMLv2 ? CompileScriptKind.GlobalScript : CompileScriptKind.Script;
+    }
+
+    /// <summary>
+    /// Decodes the script kind passed from Rust; 0 selects <see cref="DefaultKind"/>.
+    /// </summary>
+    public static CompileScriptKind KindFromByte(in GameContext gameContext, byte kind)
+    {
+        return kind switch
+        {
+            0 => DefaultKind(gameContext),
+            1 => CompileScriptKind.Script,
+            2 => CompileScriptKind.GlobalScript,
+            3 => CompileScriptKind.ObjectEvent,
+            _ => throw new ArgumentOutOfRangeException(nameof(kind), $"Unknown script kind {kind}"),
+        };
+    }
+
+    /// <summary>
+    /// Compiles GML into a code entry which references variables and functions by name.
+    /// GMLv2 function declarations become child entries.
+    /// </summary>
+    public static CompiledCode Compile(
+        GameContext gameContext,
+        string source,
+        CompileScriptKind kind,
+        string name
+    )
+    {
+        string? globalScriptName = kind == CompileScriptKind.GlobalScript ? name : null;
+        CompileContext context = new(source, kind, globalScriptName, gameContext);
+        context.Compile();
+        if (context.HasErrors)
+        {
+            throw new CompileErrorException(
+                context.Errors.Select(e => CompileErrorInfo.From(e, source)).ToList()
+            );
+        }
+
+        CompiledCode root = new(name, context.OutputInstructions!, context.OutputLength);
+        foreach (FunctionEntry function in context.OutputFunctionEntries!)
+        {
+            CompiledCode child = new(
+                $"gml_Script_{function.FunctionName}_{name}",
+                context.OutputInstructions!,
+                context.OutputLength
+            )
+            {
+                Parent = root,
+                StartOffset = function.BytecodeOffset,
+                ArgumentCount = function.ArgumentCount,
+                LocalCount = function.LocalCount,
+            };
+            root.Children.Add(child);
+        }
+        return root;
+    }
+}
@@ -0,0 +1,81 @@
+using Underanalyzer;
+using static Underanalyzer.IGMInstruction;
+
+namespace FFI;
+
+/// <summary>
+/// An instruction created by Underanalyzer's compiler through <see cref="CodeBuilder"/>.
+/// Unlike <see cref="GMInstruction"/>, this lives entirely on the C# side.
+/// </summary>
+sealed class CompiledInstruction(int address, Opcode kind) : IGMInstruction
+{
+    public int Address { get; } = address;
+    public Opcode Kind { get; set; } = kind;
+    public ExtendedOpcode ExtKind { get; set; }
+    public ComparisonType ComparisonKind { get; set; }
+    public DataType Type1 { get; set; }
+    public DataType Type2 { get; set; }
+    public InstanceType InstType { get; set; }
+    public IGMVariable? ResolvedVariable { get; set; }
+    public IGMFunction? ResolvedFunction { get; set; }
+    public VariableType ReferenceVarType { get; set; }
+    public double ValueDouble { get; set; }
+    public short ValueShort { get; set; }
+    public int ValueInt { get; set; }
+    public long ValueLong { get; set; }
+    public IGMString? ValueString { get; set; }
+    public int BranchOffset { get; set; }
+    public bool PopWithContextExit { get; set; }
+    public byte DuplicationSize { get; set; }
+    public byte DuplicationSize2 { get; set; }
+    public int ArgumentCount { get; set; }
+    public int PopSwapSize { get; set; }
+    public int AssetReferenceId { get; set; }
+    public AssetType AssetReferenceType { get; set; }
+
+    public AssetType GetAssetReferenceType(IGameContext context) => AssetReferenceType;
+
+    public IGMFunction? TryFindFunction(IGameContext? context) => ResolvedFunction;
+
+    public IGMVariable? TryFindVariable(IGameContext? context) => ResolvedVariable;
+}
+
+/// <summary>
+/// A variable referenced by compiled code. It only exists by name until the code is imported.
+/// </summary>
+sealed class CompiledVariable(string name, InstanceType instanceType, bool isBuiltin)
+    : IGMVariable
+{
+    public bool IsBuiltin => isBuiltin;
+    public IGMString Name => new ManagedString(name);
+    public InstanceType InstanceType => instanceType;
+    public int VariableID => -1;
+}
+
+/// <summary>
+/// A function referenced by compiled code. It only exists by name until the code is imported.
+/// </summary>
+sealed class CompiledFunction(string name) : IGMFunction
+{
+    public IGMString Name => new ManagedString(name);
+}
+
+/// <summary>
+/// A code entry produced by the compiler, which can be decompiled again.
+/// </summary>
+sealed class CompiledCode(string name, List<IGMInstruction> instructions, int length) : IGMCode
+{
+    public List<CompiledCode> Children { get; } = [];
+    public IGMCode? Parent { get; init; }
+    public int StartOffset { get; init; }
+    public int ArgumentCount { get; init; }
+    public int LocalCount { get; init; }
+    public IGMString Name => new ManagedString(name);
+    public int Length => length;
+    public int InstructionCount => instructions.Count;
+    public int ChildCount => Children.Count;
+
+    public IGMInstruction GetInstruction(int index) => instructions[index];
+
+    public IGMCode GetChild(int index) => Children[index];
+}
@@ -0,0 +1,154 @@
+using System.Text;
+using System.Text.Json;
+using Underanalyzer;
+
+namespace FFI;
+
+/// <summary>
+/// Serializes compiled code to JSON for the Rust side, which links it into the data file.
+/// Written by hand since reflection-based serialization is not available with NativeAOT.
+/// </summary>
+static class CompiledCodeWriter
+{
+    /// <summary>
+    /// Writes <c>{"code": {length, instructions, functions}}</c>.
+    /// </summary>
+    public static string Write(CompiledCode code)
+    {
+        using MemoryStream stream = new();
+        using (Utf8JsonWriter writer = new(stream))
+        {
+            writer.WriteStartObject();
+            writer.WriteStartObject("code");
+            writer.WriteNumber("length", code.Length);
+            writer.WriteStartArray("instructions");
+            for (int i = 0; i < code.InstructionCount; i++)
+            {
+                WriteInstruction(writer, (CompiledInstruction)code.GetInstruction(i));
+            }
+            writer.WriteEndArray();
+            writer.WriteStartArray("functions");
+            foreach (CompiledCode child in code.Children)
+            {
+                writer.WriteStartObject();
+                writer.WriteString("name", child.Name.Content);
+                writer.WriteNumber("offset", child.StartOffset);
+                writer.WriteNumber("arguments", child.ArgumentCount);
+                writer.WriteNumber("locals", child.LocalCount);
+                writer.WriteEndObject();
+            }
+            writer.WriteEndArray();
+            writer.WriteEndObject();
+            writer.WriteEndObject();
+        }
+        return Encoding.UTF8.GetString(stream.ToArray());
+    }
+
+    /// <summary>
+    /// Writes <c>{"errors": [message, ...]}</c>.
+    /// </summary>
+    public static string WriteErrors(IReadOnlyList<string> messages)
+    {
+        using MemoryStream stream = new();
+        using (Utf8JsonWriter writer = new(stream))
+        {
+            writer.WriteStartObject();
+            writer.WriteStartArray("errors");
+            foreach (string message in messages)
+            {
+                writer.WriteStringValue(message);
+            }
+            writer.WriteEndArray();
+            writer.WriteEndObject();
+        }
+        return Encoding.UTF8.GetString(stream.ToArray());
+    }
+
+    /// <summary>
+    /// Writes <c>{"errors": [{message, base_message, line, column}, ...]}</c>.
+    /// </summary>
+    public static string WriteErrorPositions(IReadOnlyList<CompileErrorInfo> errors)
+    {
+        using MemoryStream stream = new();
+        using (Utf8JsonWriter writer = new(stream))
+        {
+            writer.WriteStartObject();
+            writer.WriteStartArray("errors");
+            foreach (CompileErrorInfo error in errors)
+            {
+                writer.WriteStartObject();
+                writer.WriteString("message", error.Message);
+                writer.WriteString("base_message", error.BaseMessage);
+                writer.WriteNumber("line", error.Line);
+                writer.WriteNumber("column", error.Column);
+                writer.WriteEndObject();
+            }
+            writer.WriteEndArray();
+            writer.WriteEndObject();
+        }
+        return Encoding.UTF8.GetString(stream.ToArray());
+    }
+
+    /// <summary>
+    /// Writes the fields of an instruction; fields which are zero (or null) are left out.
+    /// </summary>
+    static void WriteInstruction(Utf8JsonWriter writer, CompiledInstruction instr)
+    {
+        writer.WriteStartObject();
+        writer.WriteNumber("address", instr.Address);
+        writer.WriteNumber("opcode", (byte)instr.Kind);
+        WriteNonZero(writer, "type1", (byte)instr.Type1);
+        WriteNonZero(writer, "type2", (byte)instr.Type2);
+        WriteNonZero(writer, "extended", (short)instr.ExtKind);
+        WriteNonZero(writer, "comparison", (byte)instr.ComparisonKind);
+        WriteNonZero(writer, "instance_type", (short)instr.InstType);
+        if (instr.ResolvedVariable is IGMVariable variable)
+        {
+            writer.WriteStartObject("variable");
+            writer.WriteString("name", variable.Name.Content);
+            writer.WriteNumber("instance_type", (short)variable.InstanceType);
+            writer.WriteNumber("variable_type", (byte)instr.ReferenceVarType);
+            if (variable is CompiledVariable { IsBuiltin: true })
+            {
+                writer.WriteBoolean("builtin", true);
+            }
+            writer.WriteEndObject();
+        }
+        if (instr.ResolvedFunction is IGMFunction function)
+        {
+            writer.WriteString("function", function.Name.Content);
+        }
+        if (instr.ValueString is IGMString str)
+        {
+            writer.WriteString("string", str.Content);
+        }
+        if (instr.ValueDouble != 0)
+        {
+            writer.WriteNumber("double", instr.ValueDouble);
+        }
+        WriteNonZero(writer, "short", instr.ValueShort);
+        WriteNonZero(writer, "int", instr.ValueInt);
+        if (instr.ValueLong != 0)
+        {
+            writer.WriteNumber("long", instr.ValueLong);
+        }
+        WriteNonZero(writer, "branch_offset", instr.BranchOffset);
+        WriteNonZero(writer, "duplication_size", instr.DuplicationSize);
+        WriteNonZero(writer, "duplication_size2", instr.DuplicationSize2);
+        WriteNonZero(writer, "argument_count", instr.ArgumentCount);
+        WriteNonZero(writer, "pop_swap_size", instr.PopSwapSize);
+        if (instr.PopWithContextExit)
+        {
+            writer.WriteBoolean("pop_with_context_exit", true);
+        }
+        writer.WriteEndObject();
+    }
+
+    static void WriteNonZero(Utf8JsonWriter writer, string name, int value)
+    {
+        if (value != 0)
+        {
+            writer.WriteNumber(name, value);
+        }
+    }
+}
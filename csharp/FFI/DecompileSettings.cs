@@ -7,31 +7,52 @@ namespace FFI;
 [StructLayout(LayoutKind.Sequential)]
 public struct DecompileSettings : IDecompileSettings
 {
-    // TODO: allow settings to be controlled from rust later
+    // Field order has to match `DecompileSettings` on the Rust side
+    public byte IndentSize; // 0 for tabs
+    public byte UseSemicolon;
+    public byte UseCssColors;
+    public byte PrintWarnings;
+    public byte MacroDeclarationsAtTop;
+    public byte EmptyLineAfterBlockLocals;
+    public byte EmptyLineAroundEnums;
+    public byte EmptyLineAroundBranchStatements;
+    public byte EmptyLineBeforeSwitchCases;
+    public byte EmptyLineAfterSwitchCases;
+    public byte EmptyLineAroundFunctionDeclarations;
+    public byte EmptyLineAroundStaticInitialization;
+    public byte OpenBlockBraceOnSameLine;
+    public byte RemoveSingleLineBlockBraces;
+    public byte CleanupTry;
+    public byte CleanupElseToContinue;
+    public byte CleanupDefaultArgumentValues;
+    public byte CleanupBuiltinArrayVariables;
+    public byte CleanupLocalVarDeclarations;
+    public byte CreateEnumDeclarations;
 
-    // empty constructor to avoid CS8983
-    public DecompileSettings() { }
-
-    string IDecompileSettings.IndentString => "    ";
-    bool IDecompileSettings.UseSemicolon => true;
-    bool IDecompileSettings.UseCSSColors => false;
-    bool IDecompileSettings.PrintWarnings => true;
-    bool IDecompileSettings.MacroDeclarationsAtTop => true;
-    bool IDecompileSettings.EmptyLineAfterBlockLocals => true;
-    bool IDecompileSettings.EmptyLineAroundEnums => true;
-    bool IDecompileSettings.EmptyLineAroundBranchStatements => true;
-    bool IDecompileSettings.EmptyLineBeforeSwitchCases => false;
-    bool IDecompileSettings.EmptyLineAfterSwitchCases => false;
-    bool IDecompileSettings.EmptyLineAroundFunctionDeclarations => true;
-    bool IDecompileSettings.EmptyLineAroundStaticInitialization => true;
-    bool IDecompileSettings.OpenBlockBraceOnSameLine => true;
-    bool IDecompileSettings.RemoveSingleLineBlockBraces => false;
-    bool IDecompileSettings.CleanupTry => false;
-    bool IDecompileSettings.CleanupElseToContinue => true;
-    bool IDecompileSettings.CleanupDefaultArgumentValues => true;
-    bool IDecompileSettings.CleanupBuiltinArrayVariables => true;
-    bool IDecompileSettings.CleanupLocalVarDeclarations => true;
-    bool IDecompileSettings.CreateEnumDeclarations => true;
+    string IDecompileSettings.IndentString =>
+        IndentSize == 0 ? "\t" : new string(' ', IndentSize);
+    bool IDecompileSettings.UseSemicolon => UseSemicolon != 0;
+    bool IDecompileSettings.UseCSSColors => UseCssColors != 0;
+    bool IDecompileSettings.PrintWarnings => PrintWarnings != 0;
+    bool IDecompileSettings.MacroDeclarationsAtTop => MacroDeclarationsAtTop != 0;
+    bool IDecompileSettings.EmptyLineAfterBlockLocals => EmptyLineAfterBlockLocals != 0;
+    bool IDecompileSettings.EmptyLineAroundEnums => EmptyLineAroundEnums != 0;
+    bool IDecompileSettings.EmptyLineAroundBranchStatements =>
+        EmptyLineAroundBranchStatements != 0;
+    bool IDecompileSettings.EmptyLineBeforeSwitchCases => EmptyLineBeforeSwitchCases != 0;
+    bool IDecompileSettings.EmptyLineAfterSwitchCases => EmptyLineAfterSwitchCases != 0;
+    bool IDecompileSettings.EmptyLineAroundFunctionDeclarations =>
+        EmptyLineAroundFunctionDeclarations != 0;
+    bool IDecompileSettings.EmptyLineAroundStaticInitialization =>
+        EmptyLineAroundStaticInitialization != 0;
+    bool IDecompileSettings.OpenBlockBraceOnSameLine => OpenBlockBraceOnSameLine != 0;
+    bool IDecompileSettings.RemoveSingleLineBlockBraces => RemoveSingleLineBlockBraces != 0;
+    bool IDecompileSettings.CleanupTry => CleanupTry != 0;
+    bool IDecompileSettings.CleanupElseToContinue => CleanupElseToContinue != 0;
+    bool IDecompileSettings.CleanupDefaultArgumentValues => CleanupDefaultArgumentValues != 0;
+    bool IDecompileSettings.CleanupBuiltinArrayVariables => CleanupBuiltinArrayVariables != 0;
+    bool IDecompileSettings.CleanupLocalVarDeclarations => CleanupLocalVarDeclarations != 0;
+    bool IDecompileSettings.CreateEnumDeclarations => CreateEnumDeclarations != 0;
     string IDecompileSettings.UnknownEnumName => "UnknownEnum";
     string IDecompileSettings.UnknownEnumValuePattern => "Variant{0}";
     string IDecompileSettings.UnknownArgumentNamePattern => "arg{0}";
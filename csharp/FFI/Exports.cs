@@ -1,4 +1,6 @@
 using System.Runtime.InteropServices;
+using Underanalyzer;
+using Underanalyzer.Compiler;
 using Underanalyzer.Decompiler;
 
 namespace FFI;
@@ -10,15 +12,44 @@ struct ReturnValue
     public byte error;
 }
 
+[StructLayout(LayoutKind.Sequential)]
+struct BuiltinFunctionInfo
+{
+    public byte exists;
+    public int minArguments;
+    public int maxArguments;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+struct BuiltinVariableInfo
+{
+    public byte exists;
+    public byte canSet;
+    public byte isGlobal;
+    public byte isAutomaticArray;
+}
+
+[StructLayout(LayoutKind.Sequential)]
+struct ConstantInfo
+{
+    public byte exists;
+    public double value;
+}
+
 static class Exports
 {
     [UnmanagedCallersOnly(EntryPoint = "decompile_to_string")]
-    static unsafe ReturnValue DecompileToString(GameContext* gameContext, GMCode* code)
+    static unsafe ReturnValue DecompileToString(
+        GameContext* gameContext,
+        GMCode* code,
+        DecompileSettings* settings
+    )
     {
         try
         {
-            DecompileSettings settings = new();
-            DecompileContext decompileContext = new(*gameContext, *code, settings);
+            IGameContext context = *gameContext;
+            GameContext.Decompiling = context;
+            DecompileContext decompileContext = new(context, *code, *settings);
             string output = decompileContext.DecompileToString();
             CsString outputRaw = CsString.FromManagedString(output);
             return new ReturnValue { str = outputRaw, error = 0 };
@@ -28,6 +59,192 @@ static class Exports
             CsString message = CsString.FromManagedString(e.ToString());
             return new ReturnValue { str = message, error = 1 };
         }
+        finally
+        {
+            GameContext.Decompiling = null;
+        }
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "decompile_listing")]
+    static unsafe ReturnValue DecompileListing(
+        GameContext* gameContext,
+        GMCode* code,
+        DecompileSettings* settings
+    )
+    {
+        try
+        {
+            IGameContext context = *gameContext;
+            GameContext.Decompiling = context;
+            string output = ListingTrace.Decompile(context, *code, *settings);
+            return new ReturnValue { str = CsString.FromManagedString(output), error = 0 };
+        }
+        catch (Exception e)
+        {
+            CsString message = CsString.FromManagedString(e.ToString());
+            return new ReturnValue { str = message, error = 1 };
+        }
+        finally
+        {
+            GameContext.Decompiling = null;
+        }
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "format_source")]
+    static unsafe ReturnValue FormatSource(
+        GameContext* gameContext,
+        RustString source,
+        DecompileSettings* settings
+    )
+    {
+        try
+        {
+            // Compile and decompile again, so the output is printed exactly like decompiled code
+            CompiledCode code = Compilation.Compile(
+                *gameContext,
+                source.Content,
+                Compilation.DefaultKind(*gameContext),
+                "gml_GlobalScript_format"
+            );
+            DecompileContext decompileContext = new(*gameContext, code, *settings);
+            string output = decompileContext.DecompileToString();
+            return new ReturnValue { str = CsString.FromManagedString(output), error = 0 };
+        }
+        catch (Exception e)
+        {
+            CsString message = CsString.FromManagedString(e.ToString());
+            return new ReturnValue { str = message, error = 1 };
+        }
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "compile_errors")]
+    static unsafe ReturnValue CompileErrors(GameContext* gameContext, RustString source)
+    {
+        try
+        {
+            Compilation.Compile(
+                *gameContext,
+                source.Content,
+                Compilation.DefaultKind(*gameContext),
+                "gml_GlobalScript_check"
+            );
+            string json = CompiledCodeWriter.WriteErrorPositions([]);
+            return new ReturnValue { str = CsString.FromManagedString(json), error = 0 };
+        }
+        catch (CompileErrorException e)
+        {
+            // These are not a failure of this function
+            string json = CompiledCodeWriter.WriteErrorPositions(e.Errors);
+            return new ReturnValue { str = CsString.FromManagedString(json), error = 0 };
+        }
+        catch (Exception e)
+        {
+            CsString message = CsString.FromManagedString(e.ToString());
+            return new ReturnValue { str = message, error = 1 };
+        }
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "compile_code")]
+    static unsafe ReturnValue CompileCode(
+        GameContext* gameContext,
+        RustString source,
+        RustString name,
+        byte kind
+    )
+    {
+        try
+        {
+            CompiledCode code = Compilation.Compile(
+                *gameContext,
+                source.Content,
+                Compilation.KindFromByte(*gameContext, kind),
+                name.Content
+            );
+            string json = CompiledCodeWriter.Write(code);
+            return new ReturnValue { str = CsString.FromManagedString(json), error = 0 };
+        }
+        catch (CompileErrorException e)
+        {
+            // Errors in the source are part of the result, not a failure of this function
+            string json = CompiledCodeWriter.WriteErrors(e.Messages);
+            return new ReturnValue { str = CsString.FromManagedString(json), error = 0 };
+        }
+        catch (Exception e)
+        {
+            CsString message = CsString.FromManagedString(e.ToString());
+            return new ReturnValue { str = message, error = 1 };
+        }
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "query_context_flags")]
+    static unsafe ContextFlags QueryContextFlags(GameContext* gameContext)
+    {
+        return gameContext->Flags;
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "load_builtins")]
+    static byte LoadBuiltins(RustString json, byte replace)
+    {
+        try
+        {
+            BuiltinList.LoadDatabase(json.Content, replace != 0);
+            return 0;
+        }
+        catch (Exception e)
+        {
+            Console.Error.WriteLine($"[ERROR] Could not load builtin database: {e}");
+            return 1;
+        }
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "release_context")]
+    static void ReleaseContext(ulong contextId)
+    {
+        ContextBuiltins.Release(contextId);
+        ContextTables.Release(contextId);
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "lookup_builtin_function")]
+    static unsafe BuiltinFunctionInfo LookupBuiltinFunction(
+        GameContext* gameContext,
+        RustString name
+    )
+    {
+        IBuiltinFunction? function = gameContext->Builtins.LookupBuiltinFunction(name.Content);
+        if (function is null)
+            return new BuiltinFunctionInfo { exists = 0 };
+        return new BuiltinFunctionInfo
+        {
+            exists = 1,
+            minArguments = function.MinArguments,
+            maxArguments = function.MaxArguments,
+        };
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "lookup_builtin_variable")]
+    static unsafe BuiltinVariableInfo LookupBuiltinVariable(
+        GameContext* gameContext,
+        RustString name
+    )
+    {
+        IBuiltinVariable? variable = gameContext->Builtins.LookupBuiltinVariable(name.Content);
+        if (variable is null)
+            return new BuiltinVariableInfo { exists = 0 };
+        return new BuiltinVariableInfo
+        {
+            exists = 1,
+            canSet = ContextFlags.Byte(variable.CanSet),
+            isGlobal = ContextFlags.Byte(variable.IsGlobal),
+            isAutomaticArray = ContextFlags.Byte(variable.IsAutomaticArray),
+        };
+    }
+
+    [UnmanagedCallersOnly(EntryPoint = "lookup_constant")]
+    static unsafe ConstantInfo LookupConstant(GameContext* gameContext, RustString name)
+    {
+        if (gameContext->Builtins.LookupConstantDouble(name.Content, out double value))
+            return new ConstantInfo { exists = 1, value = value };
+        return new ConstantInfo { exists = 0 };
     }
 
     [UnmanagedCallersOnly(EntryPoint = "free_cs_string")]
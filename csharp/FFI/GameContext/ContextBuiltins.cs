@@ -0,0 +1,88 @@
+using System.Collections.Concurrent;
+using System.Runtime.InteropServices;
+using Underanalyzer.Compiler;
+
+namespace FFI;
+
+/// <summary>
+/// A function provided by an extension in the data file's <c>EXTN</c> chunk.
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public readonly struct ExtensionFunction
+{
+    public readonly RustString Name;
+    public readonly int ArgumentCount; // negative for variable argument count
+}
+
+/// <summary>
+/// The builtin list of a game's version, extended by the game's extension functions.
+/// One instance is cached per game context (see <see cref="For"/>) until it is released from Rust.
+/// </summary>
+class ContextBuiltins : IBuiltins
+{
+    static readonly ConcurrentDictionary<ulong, ContextBuiltins> Cache = new();
+
+    readonly GMVersion Ver;
+    readonly Dictionary<string, FunctionInfo> ExtensionFunctions;
+
+    ContextBuiltins(GMVersion ver, in RawArray<ExtensionFunction> extensionFunctions)
+    {
+        Ver = ver;
+        ExtensionFunctions = new((int)extensionFunctions.Len);
+        for (int i = 0; i < (int)extensionFunctions.Len; i++)
+        {
+            ref readonly ExtensionFunction function = ref extensionFunctions.Get(i);
+            string name = function.Name.Content;
+            // The first extension function with a name wins, like in the game
+            if (ExtensionFunctions.ContainsKey(name))
+                continue;
+            ExtensionFunctions[name] =
+                function.ArgumentCount < 0
+                    ? new FunctionInfo(name, 0, int.MaxValue)
+                    : new FunctionInfo(name, function.ArgumentCount);
+        }
+    }
+
+    /// <summary>
+    /// Returns the (cached) builtins of the game context with the given id.
+    /// </summary>
+    public static ContextBuiltins For(
+        ulong contextId,
+        GMVersion ver,
+        in RawArray<ExtensionFunction> extensionFunctions
+    )
+    {
+        if (Cache.TryGetValue(contextId, out ContextBuiltins? builtins))
+            return builtins;
+        builtins = new ContextBuiltins(ver, in extensionFunctions);
+        return Cache.GetOrAdd(contextId, builtins);
+    }
+
+    /// <summary>
+    /// Forgets the cached builtins of a game context that is being dropped.
+    /// </summary>
+    public static void Release(ulong contextId)
+    {
+        Cache.TryRemove(contextId, out _);
+    }
+
+    // Looked up on each access, so databases loaded later on are picked up
+    BuiltinList Builtins => BuiltinList.ForVersion(Ver);
+
+    public IBuiltinFunction? LookupBuiltinFunction(string name)
+    {
+        if (ExtensionFunctions.TryGetValue(name, out FunctionInfo? function))
+            return function;
+        return Builtins.LookupBuiltinFunction(name);
+    }
+
+    public IBuiltinVariable? LookupBuiltinVariable(string name)
+    {
+        return Builtins.LookupBuiltinVariable(name);
+    }
+
+    public bool LookupConstantDouble(string name, out double value)
+    {
+        return Builtins.LookupConstantDouble(name, out value);
+    }
+}
@@ -0,0 +1,39 @@
+using System.Runtime.InteropServices;
+
+namespace FFI;
+
+/// <summary>
+/// All feature flags of a <see cref="GameContext"/>, either derived from its version info
+/// or overridden from Rust. Every field is a boolean byte (0 or 1).
+/// </summary>
+[StructLayout(LayoutKind.Sequential)]
+public struct ContextFlags
+{
+    public byte UsingGMS2OrLater;
+    public byte UsingGMLv2;
+    public byte UsingStringRealOptimizations;
+    public byte UsingTypedBooleans;
+    public byte UsingNullishOperator;
+    public byte UsingAssetReferences;
+    public byte UsingRoomInstanceReferences;
+    public byte UsingFunctionScriptReferences;
+    public byte UsingNewFunctionResolution;
+    public byte Bytecode14OrLower;
+    public byte UsingLogicalShortCircuit;
+    public byte UsingLongCompoundBitwise;
+    public byte UsingExtraRepeatInstruction;
+    public byte UsingFinallyBeforeThrow;
+    public byte UsingConstructorSetStatic;
+    public byte UsingArrayCopyOnWrite;
+    public byte UsingNewArrayOwners;
+    public byte UsingReentrantStatic;
+    public byte UsingNewFunctionVariables;
+    public byte UsingSelfToBuiltin;
+    public byte UsingGlobalConstantFunction;
+    public byte UsingObjectFunctionForesight;
+    public byte UsingBetterTryBreakContinue;
+    public byte UsingBuiltinDefaultArguments;
+    public byte UsingOptimizedFunctionDeclarations;
+
+    internal static byte Byte(bool value) => value ? (byte)1 : (byte)0;
+}
@@ -0,0 +1,95 @@
+using System.Collections.Concurrent;
+using Underanalyzer;
+using static Underanalyzer.IGMInstruction;
+
+namespace FFI;
+
+/// <summary>
+/// Name and ID lookups for a game context's variable and function tables.
+/// One instance is cached per game context (see <see cref="For"/>) until it is released from Rust.
+/// </summary>
+class ContextTables
+{
+    static readonly ConcurrentDictionary<ulong, ContextTables> Cache = new();
+
+    readonly Dictionary<string, List<int>> VariablesByName = [];
+    readonly Dictionary<(int, short), int> VariablesById = [];
+    readonly Dictionary<string, int> FunctionsByName = [];
+
+    ContextTables(in RawArray<GMVariable> variables, in RawArray<GMFunction> functions)
+    {
+        for (int i = 0; i < (int)variables.Len; i++)
+        {
+            ref readonly GMVariable variable = ref variables.Get(i);
+            if (!variable.Exists)
+                continue;
+            string name = variable.Name.Content;
+            if (!VariablesByName.TryGetValue(name, out List<int>? indices))
+            {
+                indices = [];
+                VariablesByName[name] = indices;
+            }
+            indices.Add(i);
+            VariablesById.TryAdd((variable.VariableID, variable.InstType), i);
+        }
+        for (int i = 0; i < (int)functions.Len; i++)
+        {
+            ref readonly GMFunction function = ref functions.Get(i);
+            if (function.Exists)
+                FunctionsByName.TryAdd(function.Name.Content, i);
+        }
+    }
+
+    /// <summary>
+    /// Returns the (cached) tables of the game context with the given id.
+    /// </summary>
+    public static ContextTables For(
+        ulong contextId,
+        in RawArray<GMVariable> variables,
+        in RawArray<GMFunction> functions
+    )
+    {
+        if (Cache.TryGetValue(contextId, out ContextTables? tables))
+            return tables;
+        tables = new ContextTables(in variables, in functions);
+        return Cache.GetOrAdd(contextId, tables);
+    }
+
+    /// <summary>
+    /// Forgets the cached tables of a game context that is being dropped.
+    /// </summary>
+    public static void Release(ulong contextId)
+    {
+        Cache.TryRemove(contextId, out _);
+    }
+
+    /// <summary>
+    /// Finds the index of a variable by name, preferring one with the given instance type.
+    /// </summary>
+    public int VariableIndex(
+        string name,
+        InstanceType instanceType,
+        in RawArray<GMVariable> variables
+    )
+    {
+        if (!VariablesByName.TryGetValue(name, out List<int>? indices))
+            return -1;
+        foreach (int index in indices)
+        {
+            if (variables.Get(index).InstType == (short)instanceType)
+                return index;
+        }
+        return indices[0];
+    }
+
+    /// <summary>
+    /// Finds the index of a named variable with the given variable ID and instance type.
+    /// </summary>
+    public int VariableIndex(int variableId, short instanceType) =>
+        VariablesById.GetValueOrDefault((variableId, instanceType), -1);
+
+    /// <summary>
+    /// Finds the index of a function by name.
+    /// </summary>
+    public int FunctionIndex(string name) => FunctionsByName.GetValueOrDefault(name, -1);
+}
@@ -3,6 +3,7 @@ using Underanalyzer;
 using Underanalyzer.Compiler;
 using Underanalyzer.Decompiler;
 using Underanalyzer.Decompiler.GameSpecific;
+using static Underanalyzer.IGMInstruction;
 
 namespace FFI;
 
@@ -45,6 +46,9 @@ public readonly struct GameContext : IGameContext
     readonly byte UsesShortCurcuit;
     readonly byte UsesArrayCow;
 
+    readonly byte HasFlagOverrides;
+    readonly ContextFlags FlagOverrides;
+
     readonly RawArray<RustString> AssetObjectNames;
     readonly RawArray<RustString> AssetSpriteNames;
     readonly RawArray<RustString> AssetSoundNames;
@@ -59,6 +63,16 @@ public readonly struct GameContext : IGameContext
     readonly RawArray<RustString> AssetAnimCurveNames;
     readonly RawArray<RustString> AssetParticleSystemNames;
 
+    readonly RawArray<ExtensionFunction> ExtensionFunctions;
+
+    readonly RawArray<GMVariable> Variables;
+    readonly RawArray<GMFunction> Functions;
+
+    /// <summary>
+    /// Unique id of this game context, used to cache data derived from it.
+    /// </summary>
+    readonly ulong Id;
+
     bool IsVer(uint major, uint minor = 0, uint release = 0, uint build = 0)
     {
         return Ver.AtLeast(major, minor, release, build);
@@ -80,42 +94,103 @@ public readonly struct GameContext : IGameContext
         }
     }
 
-    public bool UsingGMS2OrLater => IsVer(2);
-    public bool UsingGMLv2 => IsVer(2, 3);
-    public bool UsingStringRealOptimizations => IsVer(2) || Ver.Build == 1539 || Ver.Build >= 1763;
-    public bool UsingTypedBooleans => IsVer(2, 3, 7);
-    public bool UsingNullishOperator => IsVer(2, 3, 7);
-    public bool UsingAssetReferences => IsVer(2023, 8);
-    public bool UsingRoomInstanceReferences => IsVer(2024, 2);
-    public bool UsingFunctionScriptReferences => IsVer(2024, 2);
-    public bool UsingNewFunctionResolution => IsVer(2023, 13);
-    public bool Bytecode14OrLower => WadVersion <= 14;
-    public bool UsingLogicalShortCircuit => UsesShortCurcuit != 0;
-    public bool UsingLongCompoundBitwise => IsVer(2, 3, 2);
-    public bool UsingExtraRepeatInstruction => !IsVerNonLTS(2022, 11);
-    public bool UsingFinallyBeforeThrow => !IsVer(2024, 6);
-    public bool UsingConstructorSetStatic => IsVer(2024, 11);
-    public bool UsingArrayCopyOnWrite => UsesArrayCow != 0;
-    public bool UsingNewArrayOwners => IsVer(2, 3, 2);
-    public bool UsingReentrantStatic => !IsVer(2024, 11);
-    public bool UsingNewFunctionVariables => IsVer(2024, 2);
-    public bool UsingSelfToBuiltin => IsVer(2024, 2);
-    public bool UsingGlobalConstantFunction => IsVer(2023, 11);
-    public bool UsingObjectFunctionForesight => IsVer(2024, 11);
-    public bool UsingBetterTryBreakContinue => IsVer(2024, 11);
-    public bool UsingBuiltinDefaultArguments => IsVer(2024, 11);
-    public bool UsingOptimizedFunctionDeclarations => IsVer(2024, 14);
+    /// <summary>
+    /// Feature flags derived from the version info, unless all of them were overridden from Rust.
+    /// </summary>
+    internal ContextFlags Flags => HasFlagOverrides != 0 ? FlagOverrides : DeriveFlags();
+
+    ContextFlags DeriveFlags()
+    {
+        return new ContextFlags
+        {
+            UsingGMS2OrLater = ContextFlags.Byte(IsVer(2)),
+            UsingGMLv2 = ContextFlags.Byte(IsVer(2, 3)),
+            UsingStringRealOptimizations = ContextFlags.Byte(
+                IsVer(2) || Ver.Build == 1539 || Ver.Build >= 1763
+            ),
+            UsingTypedBooleans = ContextFlags.Byte(IsVer(2, 3, 7)),
+            UsingNullishOperator = ContextFlags.Byte(IsVer(2, 3, 7)),
+            UsingAssetReferences = ContextFlags.Byte(IsVer(2023, 8)),
+            UsingRoomInstanceReferences = ContextFlags.Byte(IsVer(2024, 2)),
+            UsingFunctionScriptReferences = ContextFlags.Byte(IsVer(2024, 2)),
+            UsingNewFunctionResolution = ContextFlags.Byte(IsVer(2023, 13)),
+            Bytecode14OrLower = ContextFlags.Byte(WadVersion <= 14),
+            UsingLogicalShortCircuit = ContextFlags.Byte(UsesShortCurcuit != 0),
+            UsingLongCompoundBitwise = ContextFlags.Byte(IsVer(2, 3, 2)),
+            UsingExtraRepeatInstruction = ContextFlags.Byte(!IsVerNonLTS(2022, 11)),
+            UsingFinallyBeforeThrow = ContextFlags.Byte(!IsVer(2024, 6)),
+            UsingConstructorSetStatic = ContextFlags.Byte(IsVer(2024, 11)),
+            UsingArrayCopyOnWrite = ContextFlags.Byte(UsesArrayCow != 0),
+            UsingNewArrayOwners = ContextFlags.Byte(IsVer(2, 3, 2)),
+            UsingReentrantStatic = ContextFlags.Byte(!IsVer(2024, 11)),
+            UsingNewFunctionVariables = ContextFlags.Byte(IsVer(2024, 2)),
+            UsingSelfToBuiltin = ContextFlags.Byte(IsVer(2024, 2)),
+            UsingGlobalConstantFunction = ContextFlags.Byte(IsVer(2023, 11)),
+            UsingObjectFunctionForesight = ContextFlags.Byte(IsVer(2024, 11)),
+            UsingBetterTryBreakContinue = ContextFlags.Byte(IsVer(2024, 11)),
+            UsingBuiltinDefaultArguments = ContextFlags.Byte(IsVer(2024, 11)),
+            UsingOptimizedFunctionDeclarations = ContextFlags.Byte(IsVer(2024, 14)),
+        };
+    }
+
+    public bool UsingGMS2OrLater => Flags.UsingGMS2OrLater != 0;
+    public bool UsingGMLv2 => Flags.UsingGMLv2 != 0;
+    public bool UsingStringRealOptimizations => Flags.UsingStringRealOptimizations != 0;
+    public bool UsingTypedBooleans => Flags.UsingTypedBooleans != 0;
+    public bool UsingNullishOperator => Flags.UsingNullishOperator != 0;
+    public bool UsingAssetReferences => Flags.UsingAssetReferences != 0;
+    public bool UsingRoomInstanceReferences => Flags.UsingRoomInstanceReferences != 0;
+    public bool UsingFunctionScriptReferences => Flags.UsingFunctionScriptReferences != 0;
+    public bool UsingNewFunctionResolution => Flags.UsingNewFunctionResolution != 0;
+    public bool Bytecode14OrLower => Flags.Bytecode14OrLower != 0;
+    public bool UsingLogicalShortCircuit => Flags.UsingLogicalShortCircuit != 0;
+    public bool UsingLongCompoundBitwise => Flags.UsingLongCompoundBitwise != 0;
+    public bool UsingExtraRepeatInstruction => Flags.UsingExtraRepeatInstruction != 0;
+    public bool UsingFinallyBeforeThrow => Flags.UsingFinallyBeforeThrow != 0;
+    public bool UsingConstructorSetStatic => Flags.UsingConstructorSetStatic != 0;
+    public bool UsingArrayCopyOnWrite => Flags.UsingArrayCopyOnWrite != 0;
+    public bool UsingNewArrayOwners => Flags.UsingNewArrayOwners != 0;
+    public bool UsingReentrantStatic => Flags.UsingReentrantStatic != 0;
+    public bool UsingNewFunctionVariables => Flags.UsingNewFunctionVariables != 0;
+    public bool UsingSelfToBuiltin => Flags.UsingSelfToBuiltin != 0;
+    public bool UsingGlobalConstantFunction => Flags.UsingGlobalConstantFunction != 0;
+    public bool UsingObjectFunctionForesight => Flags.UsingObjectFunctionForesight != 0;
+    public bool UsingBetterTryBreakContinue => Flags.UsingBetterTryBreakContinue != 0;
+    public bool UsingBuiltinDefaultArguments => Flags.UsingBuiltinDefaultArguments != 0;
+    public bool UsingOptimizedFunctionDeclarations => Flags.UsingOptimizedFunctionDeclarations != 0;
 
     public IGlobalFunctions GlobalFunctions => new GlobalFunctions(); // empty cache for now
 
     public GameSpecificRegistry GameSpecificRegistry => new();
 
-    public IBuiltins Builtins => new BuiltinList(Ver);
-    public ICodeBuilder CodeBuilder => throw new NotImplementedException("CodeBuilder");
+    public IBuiltins Builtins => ContextBuiltins.For(Id, Ver, in ExtensionFunctions);
+    public ICodeBuilder CodeBuilder => new CodeBuilder(this);
 
     public bool GetAssetId(string assetName, out int assetId)
     {
-        throw new NotImplementedException("GetAssetId");
+        ReadOnlySpan<RawArray<RustString>> assetLists =
+        [
+            AssetObjectNames,
+            AssetSpriteNames,
+            AssetSoundNames,
+            AssetRoomNames,
+            AssetBackgroundNames,
+            AssetPathNames,
+            AssetScriptNames,
+            AssetFontNames,
+            AssetTimelineNames,
+            AssetShaderNames,
+            AssetSequenceNames,
+            AssetAnimCurveNames,
+            AssetParticleSystemNames,
+        ];
+        foreach (RawArray<RustString> names in assetLists)
+        {
+            if (_FindAssetIndex(in names, assetName, out assetId))
+                return true;
+        }
+        assetId = -1;
+        return false;
     }
 
     public string? GetAssetName(AssetType assetType, int assetIndex)
@@ -162,17 +237,116 @@ public readonly struct GameContext : IGameContext
 
     public bool GetRoomInstanceId(string roomInstanceName, out int assetId)
     {
-        throw new NotImplementedException("GetRoomInstanceId");
+        // The inverse of GetAssetName for AssetType.RoomInstance
+        if (
+            roomInstanceName.StartsWith("inst_")
+            && int.TryParse(roomInstanceName.AsSpan(5), out assetId)
+            && assetId >= 100_000
+        )
+            return true;
+        assetId = -1;
+        return false;
     }
 
     public bool GetScriptId(string scriptName, out int assetId)
     {
-        throw new NotImplementedException("GetScriptId");
+        return _FindAssetIndex(in AssetScriptNames, scriptName, out assetId);
     }
 
     public bool GetScriptIdByFunctionName(string functionName, out int assetId)
     {
-        throw new NotImplementedException("GetScriptIdByFunctionName");
+        // GMLv2 global functions have a script asset with the same name
+        return _FindAssetIndex(in AssetScriptNames, functionName, out assetId);
+    }
+
+    /// <summary>
+    /// The game context of the decompilation running on this thread, if any.
+    /// Instructions use it to resolve their references (see <see cref="GMInstruction"/>).
+    /// </summary>
+    [ThreadStatic]
+    internal static IGameContext? Decompiling;
+
+    ContextTables Tables => ContextTables.For(Id, in Variables, in Functions);
+
+    /// <summary>
+    /// Whether the variable table has an entry at this index, with or without a name.
+    /// </summary>
+    public bool HasVariableIndex(int index) => index >= 0 && (nuint)index < Variables.Len;
+
+    /// <summary>
+    /// Looks up a variable by its index in the data file.
+    /// Fails if the index is out of range or the variable has no name.
+    /// </summary>
+    public bool TryFindVariable(int index, out GMVariable variable)
+    {
+        if (HasVariableIndex(index) && Variables.Get(index).Exists)
+        {
+            variable = Variables.Get(index);
+            return true;
+        }
+        variable = default;
+        return false;
+    }
+
+    /// <summary>
+    /// Looks up a variable by name, preferring one with the given instance type.
+    /// </summary>
+    public bool TryFindVariable(string name, InstanceType instanceType, out GMVariable variable)
+    {
+        int index = Tables.VariableIndex(name, instanceType, in Variables);
+        return TryFindVariable(index, out variable);
+    }
+
+    /// <summary>
+    /// Looks up a variable without a name (e.g. in a stripped data file)
+    /// by its variable ID and instance type, which another entry with a name may share.
+    /// </summary>
+    public bool TryFindVariableById(int index, out GMVariable variable)
+    {
+        if (HasVariableIndex(index))
+        {
+            ref readonly GMVariable nameless = ref Variables.Get(index);
+            return TryFindVariable(
+                Tables.VariableIndex(nameless.VariableID, nameless.InstType),
+                out variable
+            );
+        }
+        variable = default;
+        return false;
+    }
+
+    /// <summary>
+    /// Looks up a function by its index in the data file.
+    /// Fails if the index is out of range or the function has no name.
+    /// </summary>
+    public bool TryFindFunction(int index, out GMFunction function)
+    {
+        if (index >= 0 && (nuint)index < Functions.Len && Functions.Get(index).Exists)
+        {
+            function = Functions.Get(index);
+            return true;
+        }
+        function = default;
+        return false;
+    }
+
+    /// <summary>
+    /// Looks up a function by name.
+    /// </summary>
+    public bool TryFindFunction(string name, out GMFunction function)
+    {
+        return TryFindFunction(Tables.FunctionIndex(name), out function);
+    }
+
+    private static bool _FindAssetIndex(in RawArray<RustString> array, string name, out int index)
+    {
+        for (index = 0; (nuint)index < array.Len; index++)
+        {
+            if (array.Get(index).Content == name)
+                return true;
+        }
+        index = -1;
+        return false;
     }
 
     private string? _GetAssetNameFor(in RawArray<RustString> array, int index)
@@ -0,0 +1,582 @@
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    ops::Range,
+};
+
+use libgm::{
+    gml::{GMCode, instruction::Instruction as LibGMInstruction},
+    prelude::*,
+};
+use serde_json::{Value, json};
+
+use crate::{
+    gamemaker::{AssetKind, Code, Instruction, child_function_name},
+    highlight::escape_html,
+    owner::EventType,
+    xref::asset_name,
+};
+
+/// How the callee was determined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CallKind {
+    /// A `call` instruction of a script or GMLv2 function.
+    Call,
+    /// A `callv` instruction, whose function was pushed right before it.
+    CallVariable,
+    /// `script_execute` with a constant script.
+    ScriptExecute,
+    /// `event_perform` with a constant event type and number, in an object event.
+    EventPerform,
+}
+
+impl CallKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Call => "call",
+            Self::CallVariable => "call_variable",
+            Self::ScriptExecute => "script_execute",
+            Self::EventPerform => "event_perform",
+        }
+    }
+}
+
+/// A single call site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Call {
+    /// The code entry (or GMLv2 function) containing the call.
+    pub caller: GMRef<GMCode>,
+    /// The called code entry (or GMLv2 function).
+    pub callee: GMRef<GMCode>,
+    /// Byte offset of the call instruction from the start of the root entry.
+    pub address: u32,
+    pub kind: CallKind,
+}
+
+/// Calls between code entries. Every code entry is a node;
+/// child entries (GMLv2 functions) are separate nodes from their root entry.
+/// Build it using [`build_call_graph`].
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    /// Whether each code entry is a GMLv2 function (child entry), indexed like `data.codes`.
+    functions: Vec<bool>,
+    calls: Vec<Call>,
+}
+
+/// Builds the call graph by scanning the instructions of every root code entry.
+///
+/// Calls of builtin functions are not included, since they have no code entry.
+/// Calls through variables, `script_execute` and `event_perform` are only found
+/// if their target is pushed right before the call (as a function, asset or constant);
+/// anything computed at runtime is missed.
+/// Code entries that cannot be converted (e.g. because of broken references) are skipped.
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> libgm::Result<()> {
+/// let data = libgm::parse_file("./data.win")?;
+/// let graph = underanalyzer::build_call_graph(&data);
+/// let target = data.codes.ref_by_name("gml_Script_scr_damage")?;
+/// for caller in graph.transitive_callers(target) {
+///     println!("{}", data.codes.by_ref(caller)?.name);
+/// }
+/// std::fs::write("calls.dot", graph.to_dot(&data))?;
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn build_call_graph(data: &GMData) -> CallGraph {
+    let resolver = Resolver::new(data);
+    let codes: &[GMCode] = data.codes.elements();
+    let mut graph = CallGraph {
+        functions: codes.iter().map(|code| !code.is_root()).collect(),
+        calls: Vec::new(),
+    };
+
+    for (i, gm_code) in codes.iter().enumerate() {
+        if !gm_code.is_root() {
+            continue;
+        }
+        let root_ref = GMRef::from(i);
+        let Ok(code) = Code::try_from_libgm(root_ref, data) else {
+            continue;
+        };
+        let instructions: Vec<(u32, &Instruction)> = code.addressed_instructions().collect();
+        let functions = function_ranges(root_ref, data, &instructions, gm_code.length());
+
+        for (pos, &(address, _)) in instructions.iter().enumerate() {
+            let site = Site {
+                root: gm_code,
+                gm_instructions: &gm_code.instructions,
+                instructions: &instructions,
+                pos,
+            };
+            let Some((callee, kind)) = resolver.callee(&site, i) else {
+                continue;
+            };
+            // The innermost function containing the call
+            let caller = functions
+                .iter()
+                .filter(|(_, range)| range.contains(&address))
+                .min_by_key(|(_, range)| range.end - range.start)
+                .map_or(root_ref, |(function, _)| *function);
+            graph.calls.push(Call {
+                caller,
+                callee,
+                address,
+                kind,
+            });
+        }
+    }
+    graph
+}
+
+/// The address range of each GMLv2 function declared in a root entry.
+///
+/// Function bodies are compiled inline, behind an unconditional branch which skips them.
+pub(crate) fn function_ranges(
+    root_ref: GMRef<GMCode>,
+    data: &GMData,
+    instructions: &[(u32, &Instruction)],
+    length: u32,
+) -> Vec<(GMRef<GMCode>, Range<u32>)> {
+    GMCode::find_children(root_ref, data)
+        .into_iter()
+        .filter_map(|child_ref| {
+            let start: u32 = data
+                .codes
+                .by_ref(child_ref)
+                .ok()?
+                .modern_data
+                .as_ref()?
+                .offset;
+            let pos = instructions
+                .iter()
+                .position(|&(address, _)| address == start)?;
+            let end: u32 = pos
+                .checked_sub(1)
+                .map(|before| instructions[before])
+                .filter(|(_, instr)| instr.is_unconditional_branch())
+                .map_or(length, |(address, instr)| instr.branch_target(address));
+            Some((child_ref, start..end))
+        })
+        .collect()
+}
+
+/// A call instruction and its surroundings.
+struct Site<'i, 'a> {
+    root: &'a GMCode,
+    gm_instructions: &'a [LibGMInstruction],
+    instructions: &'i [(u32, &'i Instruction<'a>)],
+    /// Position of the call in `instructions`.
+    pos: usize,
+}
+
+impl Site<'_, '_> {
+    /// Positions of the instructions pushing the first, second, ... argument,
+    /// assuming every argument is pushed by a single instruction.
+    /// Arguments are pushed in reverse order, followed by conversions to variables.
+    fn argument_positions(&self) -> impl Iterator<Item = usize> {
+        (0..self.pos)
+            .rev()
+            .filter(|&pos| !self.instructions[pos].1.is_conversion())
+    }
+
+    fn argument(&self, n: usize) -> Option<(usize, &Instruction<'_>)> {
+        let pos = self.argument_positions().nth(n)?;
+        Some((pos, self.instructions[pos].1))
+    }
+}
+
+struct Resolver<'a> {
+    data: &'a GMData,
+    /// Index of every code entry, by name.
+    codes: HashMap<&'a str, usize>,
+    /// GMLv2 functions by function name, as (root index, child index).
+    methods: HashMap<&'a str, Vec<(usize, usize)>>,
+}
+
+impl<'a> Resolver<'a> {
+    fn new(data: &'a GMData) -> Self {
+        let gm_codes: &[GMCode] = data.codes.elements();
+        let codes = gm_codes
+            .iter()
+            .enumerate()
+            .map(|(i, code)| (code.name.as_str(), i))
+            .collect();
+
+        let mut methods: HashMap<&str, Vec<(usize, usize)>> = HashMap::new();
+        for (i, root) in gm_codes.iter().enumerate() {
+            if !root.is_root() {
+                continue;
+            }
+            for child_ref in GMCode::find_children(GMRef::from(i), data) {
+                let child_index = u32::from(child_ref) as usize;
+                let Some(child) = gm_codes.get(child_index) else {
+                    continue;
+                };
+                let name = child_function_name(&child.name, &root.name);
+                methods.entry(name).or_default().push((i, child_index));
+            }
+        }
+        Self {
+            data,
+            codes,
+            methods,
+        }
+    }
+
+    fn callee(&self, site: &Site, root_index: usize) -> Option<(GMRef<GMCode>, CallKind)> {
+        let instr: &Instruction = site.instructions[site.pos].1;
+        if instr.is_call_variable() {
+            let (_, function) = site.argument(0)?;
+            let callee = match function.function_name() {
+                Some(name) => self.function(name),
+                None => self.method(function.variable_name()?, root_index),
+            };
+            return Some((callee?, CallKind::CallVariable));
+        }
+        if !instr.is_call() {
+            return None;
+        }
+
+        match instr.function_name()? {
+            "script_execute" => {
+                let (pos, script) = site.argument(0)?;
+                let callee = self.script_argument(&site.gm_instructions[pos], script)?;
+                Some((callee, CallKind::ScriptExecute))
+            }
+            "event_perform" => {
+                let event_type = site.argument(0)?.1.integer_value()?;
+                let event_number = site.argument(1)?.1.integer_value()?;
+                let name = event_code_name(&site.root.name, event_type, event_number)?;
+                let callee = GMRef::from(*self.codes.get(name.as_str())?);
+                Some((callee, CallKind::EventPerform))
+            }
+            name => Some((self.function(name)?, CallKind::Call)),
+        }
+    }
+
+    /// The code entry of a called function or script.
+    /// GMLv2 functions are named like their child entry; GMS1 scripts are not.
+    fn function(&self, name: &str) -> Option<GMRef<GMCode>> {
+        let index = self
+            .codes
+            .get(name)
+            .or_else(|| self.codes.get(format!("gml_Script_{name}").as_str()))?;
+        Some(GMRef::from(*index))
+    }
+
+    /// The code entry of a script asset: its function (GMLv2) or the script itself (GMS1).
+    fn script(&self, name: &str) -> Option<GMRef<GMCode>> {
+        let index = self
+            .codes
+            .get(format!("gml_Script_{name}").as_str())
+            .or_else(|| self.codes.get(format!("gml_GlobalScript_{name}").as_str()))?;
+        Some(GMRef::from(*index))
+    }
+
+    /// A GMLv2 function stored in a variable, preferring functions declared in the same root entry.
+    fn method(&self, name: &str, root_index: usize) -> Option<GMRef<GMCode>> {
+        let candidates: &[(usize, usize)] = self.methods.get(name)?;
+        let mut local = candidates.iter().filter(|(root, _)| *root == root_index);
+        let (_, child) = match (local.next(), local.next()) {
+            (Some(candidate), None) => candidate,
+            _ if candidates.len() == 1 => &candidates[0],
+            _ => return None,
+        };
+        Some(GMRef::from(*child))
+    }
+
+    /// The script passed to `script_execute`: an asset reference (GameMaker 2023.8+),
+    /// a function reference (GMLv2) or a script index (GMS1).
+    fn script_argument(
+        &self,
+        gm_instr: &LibGMInstruction,
+        instr: &Instruction,
+    ) -> Option<GMRef<GMCode>> {
+        if let LibGMInstruction::PushReference { asset_reference } = gm_instr {
+            let (kind, name) = asset_name(self.data, asset_reference.build() as i32)?;
+            if kind != AssetKind::Script {
+                return None;
+            }
+            return self.script(&name);
+        }
+        if let Some(name) = instr.function_name() {
+            return self.function(name);
+        }
+        let index = usize::try_from(instr.integer_value()?).ok()?;
+        let script = self.data.scripts.elements().get(index)?;
+        self.script(script.name())
+    }
+}
+
+fn code_ref(index: u32) -> GMRef<GMCode> {
+    GMRef::from(index as usize)
+}
+
+/// Escapes text for a quoted DOT ID, in which only `"` and `\` are special.
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Name of the code entry of another event of the object owning the given event code entry.
+fn event_code_name(caller: &str, event_type: i64, event_number: i64) -> Option<String> {
+    let rest: &str = caller.strip_prefix("gml_Object_")?;
+    // The object name may contain underscores, so find the last event name instead
+    let object_end: usize = EventType::ALL
+        .iter()
+        .filter_map(|event| rest.rfind(&format!("_{}_", event.code_name())))
+        .max()?;
+    let object: &str = &rest[..object_end];
+    let event: &str = EventType::from_index(usize::try_from(event_type).ok()?)?.code_name();
+    Some(format!("gml_Object_{object}_{event}_{event_number}"))
+}
+
+impl Call {
+    /// `(caller, callee)`, or `(callee, caller)` when walking the graph backwards.
+    const fn direction(&self, forward: bool) -> (GMRef<GMCode>, GMRef<GMCode>) {
+        if forward {
+            (self.caller, self.callee)
+        } else {
+            (self.callee, self.caller)
+        }
+    }
+}
+
+impl CallGraph {
+    /// All call sites, in the order of their root entries and addresses.
+    #[must_use]
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// Code entries called directly by the given code entry.
+    #[must_use]
+    pub fn callees(&self, code: GMRef<GMCode>) -> Vec<GMRef<GMCode>> {
+        self.neighbours(code, true)
+    }
+
+    /// Code entries calling the given code entry directly.
+    #[must_use]
+    pub fn callers(&self, code: GMRef<GMCode>) -> Vec<GMRef<GMCode>> {
+        self.neighbours(code, false)
+    }
+
+    /// All code entries reachable from the given code entry through calls.
+    /// Contains the code entry itself only if it is (indirectly) recursive.
+    #[must_use]
+    pub fn transitive_callees(&self, code: GMRef<GMCode>) -> Vec<GMRef<GMCode>> {
+        self.reachable(code, true)
+    }
+
+    /// All code entries which (indirectly) call the given code entry,
+    /// i.e. everything that may be affected by changing it.
+    /// Contains the code entry itself only if it is (indirectly) recursive.
+    #[must_use]
+    pub fn transitive_callers(&self, code: GMRef<GMCode>) -> Vec<GMRef<GMCode>> {
+        self.reachable(code, false)
+    }
+
+    fn neighbours(&self, code: GMRef<GMCode>, forward: bool) -> Vec<GMRef<GMCode>> {
+        let indices: BTreeSet<u32> = self
+            .calls
+            .iter()
+            .filter_map(|call| {
+                let (from, to) = call.direction(forward);
+                (from == code).then_some(u32::from(to))
+            })
+            .collect();
+        indices.into_iter().map(code_ref).collect()
+    }
+
+    fn reachable(&self, start: GMRef<GMCode>, forward: bool) -> Vec<GMRef<GMCode>> {
+        let mut edges: HashMap<u32, Vec<u32>> = HashMap::new();
+        for call in &self.calls {
+            let (from, to) = call.direction(forward);
+            edges
+                .entry(u32::from(from))
+                .or_default()
+                .push(u32::from(to));
+        }
+
+        let mut visited: BTreeSet<u32> = BTreeSet::new();
+        let mut queue: VecDeque<u32> = VecDeque::from([u32::from(start)]);
+        while let Some(current) = queue.pop_front() {
+            for &next in edges.get(&current).into_iter().flatten() {
+                if visited.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        visited.into_iter().map(code_ref).collect()
+    }
+
+    fn name<'d>(data: &'d GMData, code: GMRef<GMCode>) -> &'d str {
+        data.codes.by_ref(code).map_or("", |code| &code.name)
+    }
+
+    /// Exports the graph in Graphviz DOT format.
+    /// Functions are drawn as ellipses; multiple calls between the same entries are merged.
+    #[must_use]
+    pub fn to_dot(&self, data: &GMData) -> String {
+        let mut output = String::from("digraph calls {\n    rankdir=LR;\n    node [shape=box];\n");
+        for (i, &function) in self.functions.iter().enumerate() {
+            let name = Self::name(data, GMRef::from(i));
+            let attributes = if function { " [shape=ellipse]" } else { "" };
+            output += &format!("    \"{}\"{attributes};\n", dot_escape(name));
+        }
+
+        let edges: BTreeSet<(u32, u32, CallKind)> = self
+            .calls
+            .iter()
+            .map(|call| (u32::from(call.caller), u32::from(call.callee), call.kind))
+            .collect();
+        for (caller, callee, kind) in edges {
+            let caller = Self::name(data, code_ref(caller));
+            let callee = Self::name(data, code_ref(callee));
+            let attributes = match kind {
+                CallKind::Call => String::new(),
+                _ => format!(" [label=\"{}\"]", dot_escape(kind.as_str())),
+            };
+            output += &format!(
+                "    \"{}\" -> \"{}\"{attributes};\n",
+                dot_escape(caller),
+                dot_escape(callee)
+            );
+        }
+        output += "}\n";
+        output
+    }
+
+    /// Exports the graph in GraphML format, with one edge per call site.
+    #[must_use]
+    pub fn to_graphml(&self, data: &GMData) -> String {
+        let mut output = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n",
+            "  <key id=\"function\" for=\"node\" attr.name=\"function\" attr.type=\"boolean\"/>\n",
+            "  <key id=\"kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <key id=\"address\" for=\"edge\" attr.name=\"address\" attr.type=\"long\"/>\n",
+            "  <graph id=\"calls\" edgedefault=\"directed\">\n",
+        ));
+        for (i, function) in self.functions.iter().enumerate() {
+            output += &format!("    <node id=\"n{i}\"><data key=\"name\">");
+            escape_html(&mut output, Self::name(data, GMRef::from(i)));
+            output += &format!("</data><data key=\"function\">{function}</data></node>\n");
+        }
+        for call in &self.calls {
+            output += &format!(
+                concat!(
+                    "    <edge source=\"n{}\" target=\"n{}\">",
+                    "<data key=\"kind\">{}</data><data key=\"address\">{}</data></edge>\n",
+                ),
+                u32::from(call.caller),
+                u32::from(call.callee),
+                call.kind.as_str(),
+                call.address,
+            );
+        }
+        output += "  </graph>\n</graphml>\n";
+        output
+    }
+
+    /// Exports the graph as JSON, with one edge per call site:
+    ///
+    /// ```json
+    /// {
+    ///   "nodes": [{ "id": 0, "name": "gml_Object_obj_player_Step_0", "function": false }],
+    ///   "calls": [{ "caller": 0, "callee": 3, "address": 24, "kind": "call" }]
+    /// }
+    /// ```
+    ///
+    /// Node ids are indices into the data file's code entries.
+    #[must_use]
+    pub fn to_json(&self, data: &GMData) -> Value {
+        let nodes: Vec<Value> = self
+            .functions
+            .iter()
+            .enumerate()
+            .map(|(i, function)| {
+                let name = Self::name(data, GMRef::from(i));
+                json!({ "id": i, "name": name, "function": function })
+            })
+            .collect();
+        let calls: Vec<Value> = self
+            .calls
+            .iter()
+            .map(|call| {
+                json!({
+                    "caller": u32::from(call.caller),
+                    "callee": u32::from(call.callee),
+                    "address": call.address,
+                    "kind": call.kind.as_str(),
+                })
+            })
+            .collect();
+        json!({ "nodes": nodes, "calls": calls })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libgm::prelude::*;
+
+    use super::{Call, CallGraph, CallKind, dot_escape, event_code_name};
+
+    fn call(caller: usize, callee: usize) -> Call {
+        Call {
+            caller: GMRef::from(caller),
+            callee: GMRef::from(callee),
+            address: 0,
+            kind: CallKind::Call,
+        }
+    }
+
+    fn indices(refs: Vec<GMRef<libgm::gml::GMCode>>) -> Vec<u32> {
+        refs.into_iter().map(u32::from).collect()
+    }
+
+    #[test]
+    fn transitive_queries() {
+        // 0 -> 1 -> 2 -> 1, 3 -> 2
+        let graph = CallGraph {
+            functions: vec![false; 4],
+            calls: vec![call(0, 1), call(1, 2), call(2, 1), call(3, 2)],
+        };
+        assert_eq!(indices(graph.callers(GMRef::from(2))), [1, 3]);
+        assert_eq!(indices(graph.callees(GMRef::from(0))), [1]);
+        assert_eq!(
+            indices(graph.transitive_callers(GMRef::from(2))),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(
+            indices(graph.transitive_callers(GMRef::from(0))),
+            [] as [u32; 0]
+        );
+        assert_eq!(indices(graph.transitive_callees(GMRef::from(3))), [1, 2]);
+    }
+
+    #[test]
+    fn event_names() {
+        assert_eq!(
+            event_code_name("gml_Object_obj_player_Step_0", 0, 0).as_deref(),
+            Some("gml_Object_obj_player_Create_0")
+        );
+        assert_eq!(
+            event_code_name("gml_Object_obj_Draw_thing_Other_10", 7, 11).as_deref(),
+            Some("gml_Object_obj_Draw_thing_Other_11")
+        );
+        assert_eq!(event_code_name("gml_Script_scr_test", 0, 0), None);
+        assert_eq!(event_code_name("gml_Object_obj_player_Step_0", 99, 0), None);
+    }
+
+    #[test]
+    fn dot_ids() {
+        assert_eq!(dot_escape("gml_Script_scr_test"), "gml_Script_scr_test");
+        assert_eq!(dot_escape(r#"a"b\c"#), r#"a\"b\\c"#);
+        // Unlike Rust's debug formatting, unicode and control characters stay as they are
+        assert_eq!(dot_escape("\u{e9}\t"), "\u{e9}\t");
+    }
+}
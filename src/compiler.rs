@@ -0,0 +1,353 @@
+use serde_json::Value;
+
+use crate::{
+    GameContext,
+    dynlib::{compile_code, compile_errors},
+    primitives::RustStr,
+};
+
+/// How Underanalyzer's compiler treats the source, passed to the library as a byte.
+/// (0 lets the library choose, like [`GameContext::compile_errors`].)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScriptKind {
+    /// A GMS1 script (or a script of an older game).
+    Script = 1,
+    /// A GMLv2 script, which declares global functions.
+    GlobalScript = 2,
+    /// Object events, room creation code and everything else.
+    ObjectEvent = 3,
+}
+
+impl ScriptKind {
+    /// The kind to compile a code entry with, derived from its name.
+    pub(crate) fn for_code_name(name: &str, using_gmlv2: bool) -> Self {
+        if name.starts_with("gml_GlobalScript_") {
+            Self::GlobalScript
+        } else if name.starts_with("gml_Script_") {
+            if using_gmlv2 {
+                Self::GlobalScript
+            } else {
+                Self::Script
+            }
+        } else {
+            Self::ObjectEvent
+        }
+    }
+}
+
+/// An error reported by Underanalyzer's compiler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CompileError {
+    /// The full message, which mentions the position.
+    pub message: String,
+    /// The message without the position.
+    pub base_message: String,
+    /// Line and column (starting at 1) of the token the error was reported at,
+    /// or 0 if the compiler reported no token.
+    pub line: u32,
+    pub column: u32,
+}
+
+/// A root code entry produced by Underanalyzer's compiler.
+/// Variables, functions and strings are referenced by name and not yet part of any data file.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CompiledCode {
+    /// Length of the bytecode in bytes.
+    pub length: u32,
+    pub instructions: Vec<CompiledInstruction>,
+    /// GMLv2 functions declared in the code, which become child entries.
+    pub functions: Vec<CompiledFunction>,
+}
+
+/// An instruction with the fields of Underanalyzer's `IGMInstruction`.
+/// Fields the instruction does not use are zero.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct CompiledInstruction {
+    pub address: u32,
+    pub opcode: u8,
+    pub type1: u8,
+    pub type2: u8,
+    pub extended_kind: i16,
+    pub comparison_kind: u8,
+    pub instance_type: i16,
+    pub variable: Option<CompiledVariable>,
+    pub function: Option<String>,
+    pub value_string: Option<String>,
+    pub value_double: f64,
+    pub value_short: i16,
+    pub value_int: i32,
+    pub value_long: i64,
+    /// In bytes, relative to this instruction.
+    pub branch_offset: i32,
+    pub duplication_size: u8,
+    pub duplication_size2: u8,
+    pub argument_count: u16,
+    pub pop_swap_size: u8,
+    pub pop_with_context_exit: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CompiledVariable {
+    pub name: String,
+    pub instance_type: i16,
+    pub variable_type: u8,
+    pub builtin: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CompiledFunction {
+    /// The name the compiler gave the child entry (`gml_Script_<function>_<root>`).
+    pub name: String,
+    /// Byte offset of the function's first instruction.
+    pub offset: u32,
+    pub arguments: u16,
+    pub locals: u16,
+}
+
+impl GameContext<'_> {
+    /// Compiles GML source with Underanalyzer's compiler and returns its error messages,
+    /// without modifying anything. An empty list means the source compiles.
+    ///
+    /// The source is compiled as a global script for GMLv2 games (so functions can be declared)
+    /// and as a plain script for older ones.
+    ///
+    /// # Errors
+    /// This function fails if the compiler crashes, not if the source has errors.
+    pub fn compile_errors(&self, source: &str) -> libgm::Result<Vec<String>> {
+        let errors = self.compile_error_positions(source)?;
+        Ok(errors.into_iter().map(|error| error.message).collect())
+    }
+
+    /// Compiles GML source like [`GameContext::compile_errors`],
+    /// returning the errors with the position the compiler reported them at.
+    ///
+    /// # Errors
+    /// This function fails if the compiler crashes or its output cannot be read.
+    pub(crate) fn compile_error_positions(&self, source: &str) -> libgm::Result<Vec<CompileError>> {
+        let ret = unsafe { compile_errors(self, RustStr::from_str(source)) };
+        let json = ret.into_result("CompileErrors", "compiling GML using Underanalyzer")?;
+        parse_errors(&json).map_err(|e| {
+            libgm::Error::new(format!("reading compiler errors: {e}"))
+                .push_context("compiling GML using Underanalyzer")
+        })
+    }
+
+    /// Compiles GML source into a root code entry with the given name.
+    /// Returns the compiler's error messages (which include line and column) if the source
+    /// has errors.
+    ///
+    /// # Errors
+    /// This function fails if the compiler crashes or its output cannot be read.
+    pub(crate) fn compile(
+        &self,
+        source: &str,
+        name: &str,
+        kind: ScriptKind,
+    ) -> libgm::Result<Result<CompiledCode, Vec<String>>> {
+        let ret = unsafe {
+            compile_code(
+                self,
+                RustStr::from_str(source),
+                RustStr::from_str(name),
+                kind as u8,
+            )
+        };
+        let json = ret.into_result("CompileCode", "compiling GML using Underanalyzer")?;
+        parse_output(&json).map_err(|e| {
+            libgm::Error::new(format!("reading compiler output: {e}"))
+                .push_context("compiling GML using Underanalyzer")
+        })
+    }
+}
+
+/// Reads `{"code": {...}}` or `{"errors": [...]}`, as written by `CompiledCodeWriter`.
+fn parse_output(json: &str) -> Result<Result<CompiledCode, Vec<String>>, String> {
+    let output: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    if let Some(errors) = output.get("errors").and_then(Value::as_array) {
+        let messages = errors
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_owned)
+            .collect();
+        return Ok(Err(messages));
+    }
+    let code: &Value = output.get("code").ok_or("missing \"code\"")?;
+    let instructions = array(code, "instructions")
+        .iter()
+        .map(parse_instruction)
+        .collect::<Result<_, String>>()?;
+    let functions = array(code, "functions")
+        .iter()
+        .map(|function| {
+            Ok(CompiledFunction {
+                name: string(function, "name").ok_or("function without name")?,
+                offset: number(function, "offset")?,
+                arguments: number(function, "arguments")?,
+                locals: number(function, "locals")?,
+            })
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(Ok(CompiledCode {
+        length: number(code, "length")?,
+        instructions,
+        functions,
+    }))
+}
+
+/// Reads `{"errors": [{message, base_message, line, column}, ...]}`,
+/// as written by `CompiledCodeWriter.WriteErrorPositions`.
+fn parse_errors(json: &str) -> Result<Vec<CompileError>, String> {
+    let output: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    array(&output, "errors")
+        .iter()
+        .map(|error| {
+            Ok(CompileError {
+                message: string(error, "message").ok_or("error without message")?,
+                base_message: string(error, "base_message").unwrap_or_default(),
+                line: number(error, "line")?,
+                column: number(error, "column")?,
+            })
+        })
+        .collect()
+}
+
+fn parse_instruction(instr: &Value) -> Result<CompiledInstruction, String> {
+    let variable = match instr.get("variable") {
+        Some(variable) => Some(CompiledVariable {
+            name: string(variable, "name").ok_or("variable without name")?,
+            instance_type: number(variable, "instance_type")?,
+            variable_type: number(variable, "variable_type")?,
+            builtin: variable.get("builtin").and_then(Value::as_bool) == Some(true),
+        }),
+        None => None,
+    };
+    Ok(CompiledInstruction {
+        address: number(instr, "address")?,
+        opcode: number(instr, "opcode")?,
+        type1: number(instr, "type1")?,
+        type2: number(instr, "type2")?,
+        extended_kind: number(instr, "extended")?,
+        comparison_kind: number(instr, "comparison")?,
+        instance_type: number(instr, "instance_type")?,
+        variable,
+        function: string(instr, "function"),
+        value_string: string(instr, "string"),
+        value_double: instr.get("double").and_then(Value::as_f64).unwrap_or(0.0),
+        value_short: number(instr, "short")?,
+        value_int: number(instr, "int")?,
+        value_long: number(instr, "long")?,
+        branch_offset: number(instr, "branch_offset")?,
+        duplication_size: number(instr, "duplication_size")?,
+        duplication_size2: number(instr, "duplication_size2")?,
+        argument_count: number(instr, "argument_count")?,
+        pop_swap_size: number(instr, "pop_swap_size")?,
+        pop_with_context_exit: instr
+            .get("pop_with_context_exit")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    })
+}
+
+fn array<'v>(value: &'v Value, key: &str) -> &'v [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+fn string(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_owned)
+}
+
+/// An integer field; missing fields are zero.
+fn number<T: TryFrom<i64>>(value: &Value, key: &str) -> Result<T, String> {
+    let Some(field) = value.get(key) else {
+        return T::try_from(0).map_err(|_| format!("{key:?} cannot be zero"));
+    };
+    field
+        .as_i64()
+        .and_then(|number| T::try_from(number).ok())
+        .ok_or_else(|| format!("{key:?} is out of range: {field}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CompileError, CompiledInstruction, CompiledVariable, ScriptKind, parse_errors, parse_output,
+    };
+
+    #[test]
+    fn parses_code() {
+        let json = r#"{"code": {"length": 12, "instructions": [
+            {"address": 0, "opcode": 132, "type1": 15, "short": 5},
+            {"address": 4, "opcode": 69, "type1": 5, "type2": 15, "instance_type": -1,
+             "variable": {"name": "hp", "instance_type": -1, "variable_type": 160}}
+        ], "functions": [{"name": "gml_Script_f_gml_Object_o_Create_0", "offset": 8, "arguments": 1, "locals": 0}]}}"#;
+        let code = parse_output(json).unwrap().unwrap();
+        assert_eq!(code.length, 12);
+        assert_eq!(code.instructions.len(), 2);
+        assert_eq!(code.instructions[0].value_short, 5);
+        assert_eq!(
+            code.instructions[1],
+            CompiledInstruction {
+                address: 4,
+                opcode: 69,
+                type1: 5,
+                type2: 15,
+                instance_type: -1,
+                variable: Some(CompiledVariable {
+                    name: "hp".to_owned(),
+                    instance_type: -1,
+                    variable_type: 160,
+                    builtin: false,
+                }),
+                ..CompiledInstruction::default()
+            }
+        );
+        assert_eq!(code.functions[0].offset, 8);
+        assert_eq!(code.functions[0].arguments, 1);
+    }
+
+    #[test]
+    fn parses_errors() {
+        let json = r#"{"errors": ["line 1: unexpected ')'", "line 3: unknown macro"]}"#;
+        let errors = parse_output(json).unwrap().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(parse_output(r#"{"code": {"length": -1}}"#).is_err());
+    }
+
+    #[test]
+    fn parses_error_positions() {
+        let json = r#"{"errors": [{"message": "Expected ')' around line 2, column 7",
+            "base_message": "Expected ')'", "line": 2, "column": 7}]}"#;
+        assert_eq!(
+            parse_errors(json).unwrap(),
+            [CompileError {
+                message: "Expected ')' around line 2, column 7".to_owned(),
+                base_message: "Expected ')'".to_owned(),
+                line: 2,
+                column: 7,
+            }]
+        );
+        assert_eq!(parse_errors(r#"{"errors": []}"#).unwrap(), []);
+        assert!(parse_errors(r#"{"errors": [{"line": 1}]}"#).is_err());
+    }
+
+    #[test]
+    fn kinds_by_name() {
+        let kind = ScriptKind::for_code_name;
+        assert_eq!(
+            kind("gml_GlobalScript_scr_a", true),
+            ScriptKind::GlobalScript
+        );
+        assert_eq!(kind("gml_Script_scr_a", false), ScriptKind::Script);
+        assert_eq!(
+            kind("gml_Object_obj_a_Step_0", true),
+            ScriptKind::ObjectEvent
+        );
+        assert_eq!(
+            kind("gml_RoomCC_rm_a_0_Create", false),
+            ScriptKind::ObjectEvent
+        );
+    }
+}
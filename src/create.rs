@@ -0,0 +1,185 @@
+use libgm::{gml::GMCode, prelude::*};
+
+use crate::{
+    GameContext,
+    import::{ImportReport, compile_and_link},
+    owner::{Event, EventType},
+};
+
+/// Creates a script from GML source, compiled with Underanalyzer.
+///
+/// For GameMaker 2.3+ games, this is a global script (`gml_GlobalScript_<name>`):
+/// every function it declares gets a child code entry, a function entry and a script asset
+/// of its own. For older games, it is a plain script (`gml_Script_<name>`).
+///
+/// Returns the new root code entry.
+///
+/// # Errors
+/// This function fails if a script or code entry with that name exists,
+/// if the source has compile errors (returned as the error message), or if linking fails.
+pub fn create_script(data: &mut GMData, name: &str, source: &str) -> Result<GMRef<GMCode>> {
+    if data
+        .scripts
+        .elements()
+        .iter()
+        .any(|script| script.name == name)
+    {
+        return Err(libgm::Error::new(format!("Script {name:?} already exists")));
+    }
+    let prefix = if using_gmlv2(data)? {
+        "gml_GlobalScript_"
+    } else {
+        "gml_Script_"
+    };
+    compile_new(data, format!("{prefix}{name}"), source)
+}
+
+/// Adds an event to an existing object, with code compiled from GML source.
+///
+/// The event gets a single "execute code" action, modelled after an existing object event.
+/// Returns the new code entry.
+///
+/// # Errors
+/// This function fails if the object already has this event (use [`crate::hook_code`]
+/// or [`crate::import_directory`] to change it), if no object event exists to model
+/// the new one after, if the source has compile errors, or if linking fails.
+pub fn add_object_event(
+    data: &mut GMData,
+    object_ref: GMRef<GMGameObject>,
+    event: Event,
+    source: &str,
+) -> Result<GMRef<GMCode>> {
+    let object: &GMGameObject = data.game_objects.by_ref(object_ref)?;
+    let kind_index = event.kind as usize;
+    let exists = object
+        .events
+        .get(kind_index)
+        .is_some_and(|events| events.iter().any(|e| e.subtype == event.subtype));
+    if exists {
+        return Err(libgm::Error::new(format!(
+            "{} already has a {} event",
+            object.name(),
+            event.describe(data)
+        )));
+    }
+
+    // Event actions have many fields besides the code, which are the same for all code actions.
+    let mut new_event = data
+        .game_objects
+        .elements()
+        .iter()
+        .flat_map(|object| object.events.iter().flatten())
+        .find(|event| event.actions.iter().any(|action| action.code.is_some()))
+        .cloned()
+        .ok_or("The data file has no object event to model a new one after")?;
+    new_event.subtype = event.subtype;
+    new_event.actions.retain(|action| action.code.is_some());
+    new_event.actions.truncate(1);
+
+    let collision_object: Option<String> = match event.kind {
+        EventType::Collision if using_gmlv2(data)? => {
+            let other = data
+                .game_objects
+                .by_ref(GMRef::from(event.subtype as usize))?;
+            Some(other.name().to_owned())
+        }
+        _ => None,
+    };
+    let name = event_code_name(object.name(), event, collision_object.as_deref());
+    let code_ref = compile_new(data, name, source)?;
+
+    new_event.actions[0].code = Some(code_ref);
+    let object: &mut GMGameObject = data.game_objects.by_ref_mut(object_ref)?;
+    if object.events.len() <= kind_index {
+        object.events.resize_with(kind_index + 1, Vec::new);
+    }
+    object.events[kind_index].push(new_event);
+    Ok(code_ref)
+}
+
+/// Adds creation code, compiled from GML source, to a room without creation code.
+/// Returns the new code entry.
+///
+/// # Errors
+/// This function fails if the room already has creation code,
+/// if the source has compile errors, or if linking fails.
+pub fn add_room_creation_code(
+    data: &mut GMData,
+    room_ref: GMRef<GMRoom>,
+    source: &str,
+) -> Result<GMRef<GMCode>> {
+    let room: &GMRoom = data.rooms.by_ref(room_ref)?;
+    if room.creation_code.is_some() {
+        return Err(libgm::Error::new(format!(
+            "{} already has creation code",
+            room.name()
+        )));
+    }
+    let name = format!("gml_RoomCC_{}_Create", room.name());
+    let code_ref = compile_new(data, name, source)?;
+    data.rooms.by_ref_mut(room_ref)?.creation_code = Some(code_ref);
+    Ok(code_ref)
+}
+
+/// Name of an object event's code entry, e.g. `gml_Object_obj_player_Alarm_3`.
+/// Collision events are named after the other object in GMLv2 games
+/// and after its index in older ones.
+fn event_code_name(object: &str, event: Event, collision_object: Option<&str>) -> String {
+    let kind = event.kind.code_name();
+    match collision_object {
+        Some(other) => format!("gml_Object_{object}_{kind}_{other}"),
+        None => format!("gml_Object_{object}_{kind}_{}", event.subtype),
+    }
+}
+
+fn using_gmlv2(data: &GMData) -> Result<bool> {
+    Ok(GameContext::new(data)?.capabilities().using_gmlv2)
+}
+
+/// Compiles source into a new root code entry (see [`crate::import_directory`]).
+fn compile_new(data: &mut GMData, name: String, source: &str) -> Result<GMRef<GMCode>> {
+    if data.codes.ref_by_name(&name).is_ok() {
+        return Err(libgm::Error::new(format!(
+            "Code entry {name:?} already exists"
+        )));
+    }
+    let sources = vec![(name.clone(), source.to_owned())];
+    let report = compile_and_link(data, sources, ImportReport::default())?;
+    if let Some((_, messages)) = report.errors.first() {
+        return Err(libgm::Error::new(format!(
+            "{name} does not compile:\n{}",
+            messages.join("\n")
+        )));
+    }
+    data.codes.ref_by_name(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::event_code_name;
+    use crate::owner::{Event, EventType};
+
+    #[test]
+    fn names_event_code() {
+        let alarm = Event {
+            kind: EventType::Alarm,
+            subtype: 3,
+        };
+        assert_eq!(
+            event_code_name("obj_a", alarm, None),
+            "gml_Object_obj_a_Alarm_3"
+        );
+        let collision = Event {
+            kind: EventType::Collision,
+            subtype: 7,
+        };
+        assert_eq!(
+            event_code_name("obj_a", collision, Some("obj_b")),
+            "gml_Object_obj_a_Collision_obj_b"
+        );
+        assert_eq!(
+            event_code_name("obj_a", collision, None),
+            "gml_Object_obj_a_Collision_7"
+        );
+    }
+}
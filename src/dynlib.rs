@@ -1,8 +1,19 @@
-use std::{io::Write, sync::OnceLock};
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 
 use tempfile::NamedTempFile;
 
-use crate::{GameContext, gamemaker::Code, primitives::CsString};
+use crate::{
+    GameContext,
+    gamemaker::{
+        Capabilities, Code, DecompileSettings, RawBuiltinFunction, RawBuiltinVariable, RawConstant,
+    },
+    primitives::{CsString, RustStr},
+};
 
 // FFI definitions ------>
 #[repr(C)]
@@ -11,56 +22,237 @@ pub struct ReturnValue {
     pub error: u8,
 }
 
-type DecompileFn = extern "C" fn(*const GameContext, *const Code) -> ReturnValue;
+impl ReturnValue {
+    /// Converts the returned string into the output or error message, depending on `error`.
+    pub fn into_result(self, export: &str, action: &str) -> libgm::Result<String> {
+        use libgm::error::Context;
+
+        let string: &str = unsafe { self.string.to_str() }.with_context(|| {
+            format!("constructing string from return value of Underanalyzer {export}")
+        })?;
+
+        if self.error == 0 {
+            return Ok(string.to_owned());
+        }
+
+        // Error occurred
+        let errno: u8 = self.error;
+        let message = if errno == 1 {
+            string.to_owned()
+        } else {
+            // print extra stuff for unknown error codes
+            format!("{export} failed with error code {errno}: {string}")
+        };
+
+        Err(libgm::Error::new(message).push_context(action))
+    }
+}
+
+type DecompileFn =
+    extern "C" fn(*const GameContext, *const Code, *const DecompileSettings) -> ReturnValue;
+type FormatSourceFn =
+    extern "C" fn(*const GameContext, RustStr, *const DecompileSettings) -> ReturnValue;
+type CompileErrorsFn = extern "C" fn(*const GameContext, RustStr) -> ReturnValue;
+type CompileCodeFn = extern "C" fn(*const GameContext, RustStr, RustStr, u8) -> ReturnValue;
+type QueryContextFlagsFn = extern "C" fn(*const GameContext) -> Capabilities;
+type LoadBuiltinsFn = extern "C" fn(RustStr, u8) -> u8;
+type ReleaseContextFn = extern "C" fn(u64);
+type LookupBuiltinFunctionFn = extern "C" fn(*const GameContext, RustStr) -> RawBuiltinFunction;
+type LookupBuiltinVariableFn = extern "C" fn(*const GameContext, RustStr) -> RawBuiltinVariable;
+type LookupConstantFn = extern "C" fn(*const GameContext, RustStr) -> RawConstant;
 type FreeCsStringFn = extern "C" fn(*const u8);
 // <------- FFI definitions
 
+/// Builtin functions, variables and constants, handed to the library once after loading it.
+const BUILTIN_DATABASE: &str = include_str!("../data/builtins.json");
+
 const DYN_LIB_DATA: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/dynlib"));
 
 struct ExternFns {
     decompile: DecompileFn,
+    decompile_listing: DecompileFn,
+    format_source: FormatSourceFn,
+    compile_errors: CompileErrorsFn,
+    compile_code: CompileCodeFn,
+    query_context_flags: QueryContextFlagsFn,
+    load_builtins: LoadBuiltinsFn,
+    release_context: ReleaseContextFn,
+    lookup_builtin_function: LookupBuiltinFunctionFn,
+    lookup_builtin_variable: LookupBuiltinVariableFn,
+    lookup_constant: LookupConstantFn,
     free_cs_string: FreeCsStringFn,
     _lib: libloading::Library,
 }
 
-fn load_externs() -> Result<ExternFns, String> {
-    let mut file = NamedTempFile::new()
-        .map_err(|e| format!("Could not create temporary file for dynamic library: {e}"))?;
-    file.write_all(DYN_LIB_DATA)
-        .map_err(|e| format!("Could not write data to dynamic library tempfile: {e}"))?;
+/// Where the Underanalyzer dynamic library is loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DynlibSource {
+    /// Extract the embedded library into a persistent cache directory and load it from there.
+    ///
+    /// The file name contains a hash of the library contents, so the file is written once
+    /// and then reused by every following process using the same library build.
+    ///
+    /// If no directory is given, `UNDERANALYZER_CACHE_DIR` is used if set.
+    /// Otherwise, the platform's user cache directory is used
+    /// (`%LOCALAPPDATA%`, `~/Library/Caches` or `$XDG_CACHE_HOME` / `~/.cache`).
+    Cache(Option<PathBuf>),
+
+    /// Load the library from an explicit file path, without extracting anything.
+    Path(PathBuf),
+
+    /// Load a library installed on the system, found through the platform's
+    /// library search path (`LD_LIBRARY_PATH`, `PATH`, ...).
+    System,
+}
+
+impl Default for DynlibSource {
+    fn default() -> Self {
+        Self::Cache(None)
+    }
+}
+
+/// File name of the library as produced by `dotnet publish`.
+const SYSTEM_LIB_NAME: &str = concat!("UnderanalyzerRS", env::consts::DLL_SUFFIX);
+
+const DYN_LIB_HASH: &str = env!("UNDERANALYZER_DYNLIB_HASH");
+
+fn load_externs(source: &DynlibSource) -> Result<ExternFns, String> {
+    let path: PathBuf = match source {
+        DynlibSource::Cache(dir) => extract_to_cache(dir.as_deref())?,
+        DynlibSource::Path(path) => path.clone(),
+        DynlibSource::System => PathBuf::from(SYSTEM_LIB_NAME),
+    };
 
     let lib = unsafe {
-        libloading::Library::new(file.path())
-            .map_err(|e| format!("Failed to load Underanalyzer dynamic library: {e}"))?
+        libloading::Library::new(&path).map_err(|e| {
+            format!(
+                "Failed to load Underanalyzer dynamic library from {}: {e}",
+                path.display(),
+            )
+        })?
+    };
+
+    let load_builtins: LoadBuiltinsFn = unsafe { symbol(&lib, "load_builtins")? };
+    if load_builtins(RustStr::from_str(BUILTIN_DATABASE), 1) != 0 {
+        return Err("Underanalyzer could not load the builtin database".to_owned());
+    }
+
+    unsafe {
+        Ok(ExternFns {
+            decompile: symbol(&lib, "decompile_to_string")?,
+            decompile_listing: symbol(&lib, "decompile_listing")?,
+            format_source: symbol(&lib, "format_source")?,
+            compile_errors: symbol(&lib, "compile_errors")?,
+            compile_code: symbol(&lib, "compile_code")?,
+            query_context_flags: symbol(&lib, "query_context_flags")?,
+            load_builtins,
+            release_context: symbol(&lib, "release_context")?,
+            lookup_builtin_function: symbol(&lib, "lookup_builtin_function")?,
+            lookup_builtin_variable: symbol(&lib, "lookup_builtin_variable")?,
+            lookup_constant: symbol(&lib, "lookup_constant")?,
+            free_cs_string: symbol(&lib, "free_cs_string")?,
+            _lib: lib,
+        })
+    }
+}
+
+/// Loads a function pointer from the library.
+///
+/// # Safety
+/// `T` has to be the correct function pointer type for this symbol.
+unsafe fn symbol<T: Copy>(lib: &libloading::Library, name: &str) -> Result<T, String> {
+    let symbol: libloading::Symbol<T> = unsafe {
+        lib.get(name.as_bytes())
+            .map_err(|e| format!("Failed to load {name}: {e}"))?
     };
+    Ok(*symbol)
+}
 
-    let decompile: libloading::Symbol<DecompileFn> = unsafe {
-        lib.get(b"decompile_to_string")
-            .map_err(|e| format!("Failed to load decompile_to_string: {e}"))?
+/// Writes the embedded library into the cache directory (unless it already exists there)
+/// and returns the path of the cached file.
+fn extract_to_cache(dir: Option<&Path>) -> Result<PathBuf, String> {
+    let dir: PathBuf = match dir {
+        Some(dir) => dir.to_owned(),
+        None => default_cache_dir()?,
     };
 
-    let free_cs_string: libloading::Symbol<FreeCsStringFn> = unsafe {
-        lib.get(b"free_cs_string")
-            .map_err(|e| format!("Failed to load free_cs_string: {e}"))?
+    let file_name = format!(
+        "{}underanalyzer-{DYN_LIB_HASH}{}",
+        env::consts::DLL_PREFIX,
+        env::consts::DLL_SUFFIX,
+    );
+    let path: PathBuf = dir.join(file_name);
+
+    // The hash is part of the file name, so matching length means it was fully written.
+    if fs::metadata(&path).is_ok_and(|m| m.len() == DYN_LIB_DATA.len() as u64) {
+        return Ok(path);
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| {
+        format!(
+            "Could not create dynamic library cache directory {}: {e}",
+            dir.display(),
+        )
+    })?;
+
+    // Write into a temporary file in the same directory first and rename it afterwards.
+    // This way, concurrently starting processes never load a partially written library.
+    let mut file = NamedTempFile::new_in(&dir).map_err(|e| {
+        format!(
+            "Could not create temporary file in cache directory {}: {e}",
+            dir.display(),
+        )
+    })?;
+    file.write_all(DYN_LIB_DATA)
+        .map_err(|e| format!("Could not write data to dynamic library cache file: {e}"))?;
+
+    if let Err(e) = file.persist(&path) {
+        // Another process may have won the race (renaming over a loaded library fails on Windows).
+        if !path.exists() {
+            return Err(format!(
+                "Could not move dynamic library into cache at {}: {}",
+                path.display(),
+                e.error,
+            ));
+        }
+    }
+
+    Ok(path)
+}
+
+fn default_cache_dir() -> Result<PathBuf, String> {
+    if let Some(dir) = env::var_os("UNDERANALYZER_CACHE_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+
+    let non_empty = |var: &str| env::var_os(var).filter(|v| !v.is_empty());
+    let base: Option<PathBuf> = if cfg!(windows) {
+        non_empty("LOCALAPPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        non_empty("HOME").map(|home| Path::new(&home).join("Library/Caches"))
+    } else {
+        non_empty("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| non_empty("HOME").map(|home| Path::new(&home).join(".cache")))
     };
 
-    Ok(ExternFns {
-        decompile: *decompile,
-        free_cs_string: *free_cs_string,
-        _lib: lib,
-    })
+    let base = base.ok_or(
+        "Could not determine a cache directory for the dynamic library; \
+         set UNDERANALYZER_CACHE_DIR or load the library from an explicit path",
+    )?;
+    Ok(base.join("underanalyzer"))
 }
 
 fn force_load_externs() -> ExternFns {
-    load_externs().expect("Could not load dynamic library")
+    load_externs(&DynlibSource::default()).expect("Could not load dynamic library")
 }
 
-pub fn init_externs() -> libgm::Result<()> {
+pub fn init_externs(source: &DynlibSource) -> libgm::Result<()> {
     use libgm::error::Context;
     if EXTERNS.get().is_some() {
         return Ok(());
     }
-    let ext = load_externs().context("loading dynamic library")?;
+    let ext = load_externs(source).context("loading dynamic library")?;
     let _ = EXTERNS.set(ext);
     Ok(())
 }
@@ -70,9 +262,88 @@ static EXTERNS: OnceLock<ExternFns> = OnceLock::new();
 pub unsafe fn decompile_to_string(
     game_context: *const GameContext,
     code: *const Code,
+    settings: *const DecompileSettings,
+) -> ReturnValue {
+    let ext = EXTERNS.get_or_init(force_load_externs);
+    (ext.decompile)(game_context, code, settings)
+}
+
+/// Like [`decompile_to_string`], but the output starts with the number of printed references,
+/// followed by a `<line> <instruction index>` line for each of them.
+pub unsafe fn decompile_listing(
+    game_context: *const GameContext,
+    code: *const Code,
+    settings: *const DecompileSettings,
+) -> ReturnValue {
+    let ext = EXTERNS.get_or_init(force_load_externs);
+    (ext.decompile_listing)(game_context, code, settings)
+}
+
+pub unsafe fn format_source(
+    game_context: *const GameContext,
+    source: RustStr,
+    settings: *const DecompileSettings,
 ) -> ReturnValue {
     let ext = EXTERNS.get_or_init(force_load_externs);
-    (ext.decompile)(game_context, code)
+    (ext.format_source)(game_context, source, settings)
+}
+
+pub unsafe fn compile_errors(game_context: *const GameContext, source: RustStr) -> ReturnValue {
+    let ext = EXTERNS.get_or_init(force_load_externs);
+    (ext.compile_errors)(game_context, source)
+}
+
+pub unsafe fn compile_code(
+    game_context: *const GameContext,
+    source: RustStr,
+    name: RustStr,
+    kind: u8,
+) -> ReturnValue {
+    let ext = EXTERNS.get_or_init(force_load_externs);
+    (ext.compile_code)(game_context, source, name, kind)
+}
+
+pub unsafe fn query_context_flags(game_context: *const GameContext) -> Capabilities {
+    let ext = EXTERNS.get_or_init(force_load_externs);
+    (ext.query_context_flags)(game_context)
+}
+
+/// Loads a builtin database, either replacing all loaded ones or extending them.
+pub fn load_builtins(json: &str, replace: bool) -> libgm::Result<()> {
+    let ext = EXTERNS.get_or_init(force_load_externs);
+    if (ext.load_builtins)(RustStr::from_str(json), u8::from(replace)) != 0 {
+        return Err("Underanalyzer could not load the builtin database".into());
+    }
+    Ok(())
+}
+
+/// Releases data cached for a game context. Does nothing if the library is not loaded,
+/// since nothing can have been cached then.
+pub fn release_context(context_id: u64) {
+    if let Some(ext) = EXTERNS.get() {
+        (ext.release_context)(context_id);
+    }
+}
+
+pub unsafe fn lookup_builtin_function(
+    game_context: *const GameContext,
+    name: RustStr,
+) -> RawBuiltinFunction {
+    let ext = EXTERNS.get_or_init(force_load_externs);
+    (ext.lookup_builtin_function)(game_context, name)
+}
+
+pub unsafe fn lookup_builtin_variable(
+    game_context: *const GameContext,
+    name: RustStr,
+) -> RawBuiltinVariable {
+    let ext = EXTERNS.get_or_init(force_load_externs);
+    (ext.lookup_builtin_variable)(game_context, name)
+}
+
+pub unsafe fn lookup_constant(game_context: *const GameContext, name: RustStr) -> RawConstant {
+    let ext = EXTERNS.get_or_init(force_load_externs);
+    (ext.lookup_constant)(game_context, name)
 }
 
 pub unsafe fn free_cs_string(ptr: *const u8) {
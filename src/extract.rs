@@ -0,0 +1,199 @@
+use std::ops::Range;
+
+/// Finds the declaration of the given function in decompiled GML.
+///
+/// Returns the range of lines, from the line containing the declaration
+/// up to (and including) the line closing its body.
+#[must_use]
+pub fn find_function(source: &str, name: &str) -> Option<Range<usize>> {
+    find_nth_function(source, name, 0)
+}
+
+/// Like [`find_function`], but finds the `n`th (zero-based) declaration of a function
+/// with this name, e.g. the second of two constructors declaring a method with the same name.
+#[must_use]
+pub fn find_nth_function(source: &str, name: &str, n: usize) -> Option<Range<usize>> {
+    let lines: Vec<&str> = source.lines().collect();
+    let declarations = [format!("function {name}("), format!("{name} = function(")];
+
+    let start: usize = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim_start();
+            let line = line.strip_prefix("static ").unwrap_or(line);
+            declarations
+                .iter()
+                .any(|decl| line.starts_with(decl.as_str()))
+        })
+        .nth(n)?
+        .0;
+
+    let mut depth: u32 = 0;
+    let mut opened = false;
+    let mut scanner = Scanner::default();
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        for brace in scanner.braces(line) {
+            if brace == '{' {
+                depth += 1;
+                opened = true;
+            } else {
+                depth = depth.saturating_sub(1);
+            }
+        }
+        if opened && depth == 0 {
+            return Some(start..i + 1);
+        }
+    }
+    None
+}
+
+/// Returns the given lines of the source, with the indentation of the first line removed.
+#[must_use]
+pub fn extract_lines(source: &str, lines: Range<usize>) -> String {
+    let lines: Vec<&str> = source.lines().skip(lines.start).take(lines.len()).collect();
+    let indent: &str = lines.first().map_or("", |line| {
+        let trimmed = line.trim_start();
+        &line[..line.len() - trimmed.len()]
+    });
+
+    let mut output = String::new();
+    for line in lines {
+        output += line.strip_prefix(indent).unwrap_or(line);
+        output.push('\n');
+    }
+    output
+}
+
+/// Surrounds the given lines of the source with marker comments.
+#[must_use]
+pub fn mark_lines(source: &str, lines: Range<usize>, label: &str) -> String {
+    let mut output = String::new();
+    for (i, line) in source.lines().enumerate() {
+        if i == lines.start {
+            output += &format!("// >>> {label}\n");
+        }
+        output += line;
+        output.push('\n');
+        if i + 1 == lines.end {
+            output += &format!("// <<< {label}\n");
+        }
+    }
+    output
+}
+
+/// Finds braces in GML source line by line, skipping strings and comments.
+#[derive(Default)]
+struct Scanner {
+    in_block_comment: bool,
+    /// Quote character of an unterminated verbatim (`@"..."`) string.
+    in_verbatim_string: Option<char>,
+}
+
+impl Scanner {
+    fn braces(&mut self, line: &str) -> Vec<char> {
+        let mut braces = Vec::new();
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if self.in_block_comment {
+                if c == '*' && chars.peek() == Some(&'/') {
+                    chars.next();
+                    self.in_block_comment = false;
+                }
+                continue;
+            }
+            if let Some(quote) = self.in_verbatim_string {
+                if c == quote {
+                    self.in_verbatim_string = None;
+                }
+                continue;
+            }
+            match c {
+                '/' if chars.peek() == Some(&'/') => break,
+                '/' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    self.in_block_comment = true;
+                }
+                '@' if matches!(chars.peek(), Some('"' | '\'')) => {
+                    self.in_verbatim_string = chars.next();
+                }
+                '"' => {
+                    while let Some(c) = chars.next() {
+                        match c {
+                            '\\' => {
+                                chars.next();
+                            }
+                            '"' => break,
+                            _ => {}
+                        }
+                    }
+                }
+                '{' | '}' => braces.push(c),
+                _ => {}
+            }
+        }
+        braces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_lines, find_function, find_nth_function, mark_lines};
+
+    const SOURCE: &str = r#"function Player() constructor
+{
+    static take_damage = function(amount)
+    {
+        hp -= amount; // "}"
+        log("{");
+    };
+}
+
+function other() {}
+"#;
+
+    #[test]
+    fn finds_nested_method() {
+        let lines = find_function(SOURCE, "take_damage").unwrap();
+        assert_eq!(lines, 2..7);
+        assert_eq!(
+            extract_lines(SOURCE, lines),
+            "static take_damage = function(amount)\n{\n    hp -= amount; // \"}\"\n    log(\"{\");\n};\n",
+        );
+    }
+
+    #[test]
+    fn finds_single_line_function() {
+        assert_eq!(find_function(SOURCE, "other"), Some(9..10));
+        assert_eq!(find_function(SOURCE, "missing"), None);
+    }
+
+    #[test]
+    fn finds_later_declarations_with_the_same_name() {
+        let source = r"function A() constructor
+{
+    f = function()
+    {
+        return 1;
+    };
+}
+
+function B() constructor
+{
+    f = function()
+    {
+        return 2;
+    };
+}
+";
+        assert_eq!(find_nth_function(source, "f", 0), Some(2..6));
+        assert_eq!(find_nth_function(source, "f", 1), Some(10..14));
+        assert_eq!(find_nth_function(source, "f", 2), None);
+    }
+
+    #[test]
+    fn marks_lines() {
+        let marked = mark_lines("a\nb\nc\n", 1..2, "b");
+        assert_eq!(marked, "a\n// >>> b\nb\n// <<< b\nc\n");
+    }
+}
@@ -0,0 +1,272 @@
+use std::ops::Range;
+
+use libgm::{gml::GMCode, prelude::*};
+
+use crate::{
+    GameContext,
+    callgraph::function_ranges,
+    extract::extract_lines,
+    function_lookup::find_declaration,
+    gamemaker::{Code, DecompileSettings, find_parent},
+};
+
+/// Result of [`GameContext::decompile_or_disassemble`].
+#[derive(Debug)]
+pub enum Decompilation {
+    /// Decompilation succeeded.
+    Decompiled(String),
+    /// Decompilation failed.
+    /// The output contains the error message and the disassembly of the entry as GML comments.
+    Disassembled { output: String, error: libgm::Error },
+    /// Decompilation failed in one GMLv2 function, but the rest of the root entry
+    /// could be decompiled. The output is GML in which the body of that function consists of
+    /// the error message and its disassembly as comments.
+    Partial { output: String, error: libgm::Error },
+}
+
+impl Decompilation {
+    /// The decompiled code, possibly containing disassembly comments.
+    #[must_use]
+    pub fn output(&self) -> &str {
+        match self {
+            Self::Decompiled(output)
+            | Self::Disassembled { output, .. }
+            | Self::Partial { output, .. } => output,
+        }
+    }
+
+    #[must_use]
+    pub fn into_output(self) -> String {
+        match self {
+            Self::Decompiled(output)
+            | Self::Disassembled { output, .. }
+            | Self::Partial { output, .. } => output,
+        }
+    }
+}
+
+impl GameContext<'_> {
+    /// Decompiles the given code entry, falling back to a disassembly if that fails.
+    ///
+    /// Underanalyzer decompiles a root entry as a whole. If it fails on a GMLv2 function,
+    /// the root entry is decompiled again with that function's body left out, and only the body
+    /// is replaced by the disassembly ([`Decompilation::Partial`]); the smallest function for
+    /// which this works is chosen. Otherwise, the disassembly covers the entry, with the entry
+    /// points of child entries labelled by `> <name>` lines. For child entries, this is their body
+    /// in the root entry, or their own instructions if the root entry cannot be found.
+    /// This way, dumps of heavily modified games never contain empty entries.
+    #[must_use]
+    pub fn decompile_or_disassemble(
+        &self,
+        code_ref: GMRef<GMCode>,
+        gm_data: &GMData,
+    ) -> Decompilation {
+        let error = match self.decompile(code_ref, gm_data) {
+            Ok(source) => return Decompilation::Decompiled(source),
+            Err(error) => error,
+        };
+        if let Ok(Some(output)) = self.decompile_partially(code_ref, gm_data, &error) {
+            return Decompilation::Partial { output, error };
+        }
+
+        let mut output = failure_comment(&error, "");
+        match disassemble_entry(code_ref, gm_data) {
+            Ok(disassembly) => {
+                for line in disassembly.lines() {
+                    output += &format!("// {line}\n");
+                }
+            }
+            Err(e) => output += &format!("// Could not disassemble: {}\n", e.chain_pretty()),
+        }
+        Decompilation::Disassembled { output, error }
+    }
+
+    /// Decompiles the root entry with one function body at a time left out, smallest first,
+    /// and puts the disassembly of that body into the first output that could be decompiled.
+    ///
+    /// Returns [`None`] if no such function exists, or if the given child entry is declared
+    /// inside it.
+    fn decompile_partially(
+        &self,
+        code_ref: GMRef<GMCode>,
+        gm_data: &GMData,
+        error: &libgm::Error,
+    ) -> Result<Option<String>> {
+        // Without its root, there is nothing to decompile partially.
+        let Ok(parent_ref) = find_parent(code_ref, gm_data) else {
+            return Ok(None);
+        };
+        let root_ref = parent_ref.unwrap_or(code_ref);
+        let root = Code::try_from_libgm(root_ref, gm_data)?;
+        let instructions: Vec<_> = root.addressed_instructions().collect();
+        let mut ranges = function_ranges(root_ref, gm_data, &instructions, root.length());
+        ranges.sort_by_key(|(_, body)| body.end - body.start);
+
+        let settings = DecompileSettings::default();
+        for (function_ref, body) in ranges {
+            let Ok(stubbed) = Code::try_from_libgm_stubbed(root_ref, gm_data, body.clone()) else {
+                continue;
+            };
+            let Ok(source) = self.decompile_converted(&stubbed, &settings) else {
+                continue;
+            };
+            let Some(lines) = find_declaration(&source, function_ref, root_ref, gm_data)? else {
+                continue;
+            };
+            let Some(source) =
+                replace_body(&source, lines, error, &root.disassemble_range(body.clone()))
+            else {
+                continue;
+            };
+            if code_ref == root_ref {
+                return Ok(Some(source));
+            }
+            let offset = gm_data
+                .codes
+                .by_ref(code_ref)?
+                .modern_data
+                .as_ref()
+                .map(|m| m.offset);
+            if code_ref != function_ref && offset.is_some_and(|offset| body.contains(&offset)) {
+                return Ok(None);
+            }
+            let lines = find_declaration(&source, code_ref, root_ref, gm_data)?;
+            return Ok(lines.map(|lines| extract_lines(&source, lines)));
+        }
+        Ok(None)
+    }
+}
+
+/// The error message as a block of GML comments.
+fn failure_comment(error: &libgm::Error, indent: &str) -> String {
+    let mut output = format!("{indent}// Decompilation failed:\n");
+    for line in error.chain_pretty().lines() {
+        output += &format!("{indent}//   {line}\n");
+    }
+    output += &format!("{indent}//\n");
+    output
+}
+
+/// Replaces the body of the function declared on the given lines by the error message
+/// and the disassembly of the body as comments.
+///
+/// Returns [`None`] if the declaration has no lines between its braces.
+fn replace_body(
+    source: &str,
+    lines: Range<usize>,
+    error: &libgm::Error,
+    disassembly: &str,
+) -> Option<String> {
+    let all: Vec<&str> = source.lines().collect();
+    let mut body_start = lines.start + 1;
+    if all.get(body_start).is_some_and(|line| line.trim() == "{") {
+        body_start += 1;
+    }
+    let closing = lines.end.checked_sub(1)?;
+    if body_start > closing {
+        return None;
+    }
+
+    let closing_line = all[closing];
+    let indent = format!(
+        "{}    ",
+        &closing_line[..closing_line.len() - closing_line.trim_start().len()],
+    );
+    let mut output = String::new();
+    for line in &all[..body_start] {
+        output += &format!("{line}\n");
+    }
+    output += &failure_comment(error, &indent);
+    for line in disassembly.lines() {
+        output += &format!("{indent}// {line}\n");
+    }
+    for line in &all[closing..] {
+        output += &format!("{line}\n");
+    }
+    Some(output)
+}
+
+/// Disassembles a code entry (see [`GameContext::decompile_or_disassemble`]).
+fn disassemble_entry(code_ref: GMRef<GMCode>, gm_data: &GMData) -> Result<String> {
+    let name = &gm_data.codes.by_ref(code_ref)?.name;
+    let Ok(Some(root_ref)) = find_parent(code_ref, gm_data) else {
+        let code = Code::try_from_libgm(code_ref, gm_data)?;
+        return Ok(format!("Disassembly of {name}:\n{}", code.disassemble()));
+    };
+
+    let root = Code::try_from_libgm(root_ref, gm_data)?;
+    let root_name = &gm_data.codes.by_ref(root_ref)?.name;
+    let instructions: Vec<_> = root.addressed_instructions().collect();
+    let body = function_ranges(root_ref, gm_data, &instructions, root.length())
+        .into_iter()
+        .find(|(child_ref, _)| *child_ref == code_ref)
+        .map(|(_, body)| body);
+    Ok(match body {
+        Some(body) => format!(
+            "Disassembly of {name} (in {root_name}):\n{}",
+            root.disassemble_range(body)
+        ),
+        None => format!("Disassembly of {root_name}:\n{}", root.disassemble()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_only_the_function_body() {
+        let source = "a = 1;\nfunction f()\n{\n    exit;\n}\n\nb = 2;\n";
+        let error = libgm::Error::new("unexpected branch".to_owned());
+        let output = replace_body(source, 1..5, &error, "00000: exit.i").unwrap();
+        assert!(output.starts_with("a = 1;\nfunction f()\n{\n    // Decompilation failed:\n"));
+        assert!(output.contains("    //   unexpected branch\n"));
+        assert!(output.ends_with("    // 00000: exit.i\n}\n\nb = 2;\n"));
+        assert!(!output.contains("exit;"));
+    }
+
+    #[test]
+    fn one_line_functions_have_no_body_to_replace() {
+        let error = libgm::Error::new("unexpected branch".to_owned());
+        assert_eq!(replace_body("function f() {}\n", 0..1, &error, ""), None);
+    }
+
+    fn code(name: &str, parent: Option<usize>, offset: u32) -> GMCode {
+        let mut code = GMCode {
+            name: name.to_owned(),
+            ..GMCode::default()
+        };
+        let mut modern = code.modern_data.take().unwrap_or_default();
+        modern.parent = parent.map(GMRef::from);
+        modern.offset = offset;
+        code.modern_data = Some(modern);
+        code
+    }
+
+    #[test]
+    fn disassembles_only_the_child_body() {
+        use libgm::gml::instruction::Instruction as LibGMInstruction;
+
+        // `0: b 8`, f: `4: exit`, root: `8: exit`
+        let mut data = GMData::default();
+        data.general_info.wad_version = 17;
+        let mut root = code("gml_GlobalScript_scr_a", None, 0);
+        root.instructions = vec![
+            LibGMInstruction::Branch { jump_offset: 2 },
+            LibGMInstruction::Exit,
+            LibGMInstruction::Exit,
+        ];
+        data.codes.push(root);
+        data.codes.push(code("gml_Script_f", Some(0), 4));
+        data.codes.push(code("gml_Script_g", Some(5), 0));
+
+        let output = disassemble_entry(GMRef::from(1), &data).unwrap();
+        assert!(output.starts_with("Disassembly of gml_Script_f (in gml_GlobalScript_scr_a):\n"));
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.contains("00004: "));
+
+        // The parent link is broken, so the child's own (empty) listing is used.
+        let output = disassemble_entry(GMRef::from(2), &data).unwrap();
+        assert_eq!(output, "Disassembly of gml_Script_g:\n");
+    }
+}
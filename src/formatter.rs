@@ -0,0 +1,37 @@
+use libgm::gamemaker::version::{GMVersion, LTSBranch};
+
+use crate::{
+    GameContext, dynlib::format_source, gamemaker::DecompileSettings, primitives::RustStr,
+};
+
+/// Normalizes GML source code to look exactly like decompiled code.
+///
+/// **This is a lossy normalizing mode, not a formatter that preserves the source.**
+/// The source is compiled with Underanalyzer's compiler to bytecode and decompiled again,
+/// using the given printer settings. Everything the bytecode does not keep is lost:
+/// comments are dropped, macros are expanded, constant expressions may be folded,
+/// and loops and conditions come out the way the decompiler restructures them.
+///
+/// This assumes the latest GameMaker version and knows no assets;
+/// use [`GameContext::format`] to normalize code for a specific game.
+///
+/// # Errors
+/// This function fails if the source does not compile or cannot be decompiled again.
+pub fn format(source: &str, settings: &DecompileSettings) -> libgm::Result<String> {
+    let version = GMVersion::new(2024, 14, 0, 0, LTSBranch::PostLTS);
+    GameContext::from_version(&version, 17, true, false).format(source, settings)
+}
+
+impl GameContext<'_> {
+    /// Normalizes GML source code to look exactly like decompiled code of this game.
+    ///
+    /// **This is lossy:** comments are dropped and macros are expanded.
+    /// See [`format`](crate::format) for details.
+    ///
+    /// # Errors
+    /// This function fails if the source does not compile or cannot be decompiled again.
+    pub fn format(&self, source: &str, settings: &DecompileSettings) -> libgm::Result<String> {
+        let ret = unsafe { format_source(self, RustStr::from_str(source), settings) };
+        ret.into_result("FormatSource", "formatting GML using Underanalyzer")
+    }
+}
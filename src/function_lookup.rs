@@ -0,0 +1,254 @@
+use std::{
+    collections::{HashMap, HashSet, hash_map::Entry},
+    ops::Range,
+};
+
+use libgm::{gml::GMCode, prelude::*};
+
+use crate::{
+    DecompileSettings, GameContext,
+    callgraph::function_ranges,
+    extract::{extract_lines, find_function, find_nth_function},
+    gamemaker::{Code, Instruction, child_function_name, find_parent},
+};
+
+/// A GMLv2 function declaration matching a name passed to [`GameContext::decompile_function`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionCandidate {
+    /// The child code entry containing the function body.
+    pub code: GMRef<GMCode>,
+    /// The root code entry declaring the function.
+    pub parent: GMRef<GMCode>,
+    /// Name of the root code entry, e.g. `gml_GlobalScript_scr_player`.
+    pub parent_name: String,
+}
+
+/// Result of [`GameContext::decompile_function`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FunctionLookup {
+    /// Exactly one function matched; contains its decompiled declaration.
+    Found {
+        candidate: FunctionCandidate,
+        source: String,
+    },
+    /// Multiple functions matched. Nothing was decompiled.
+    /// Qualify the name with its owner (`Owner.function`) to pick one.
+    Ambiguous(Vec<FunctionCandidate>),
+}
+
+impl GameContext<'_> {
+    /// Decompiles a single GMLv2 function (including its default arguments and static block)
+    /// instead of the entire root entry declaring it.
+    ///
+    /// The name may be qualified with its owner, like `Player.take_damage`.
+    /// The owner matches either (a part of) the name of the root code entry
+    /// or another function whose body contains the function, such as a struct constructor.
+    ///
+    /// # Errors
+    /// This function fails if no function with this name exists,
+    /// or if decompiling the root entry fails.
+    pub fn decompile_function(&self, gm_data: &GMData, name: &str) -> Result<FunctionLookup> {
+        let mut candidates: Vec<FunctionCandidate> = find_candidates(gm_data, name)?;
+        match candidates.len() {
+            0 => Err(libgm::Error::new(format!(
+                "Could not find GMLv2 function {name:?}"
+            ))),
+            1 => {
+                let candidate = candidates.remove(0);
+                let settings = DecompileSettings::default();
+                let (parent_source, lines) =
+                    self.locate_child(candidate.code, candidate.parent, gm_data, &settings)?;
+                let lines = lines.ok_or_else(|| {
+                    libgm::Error::new(format!(
+                        "Could not locate declaration of {name:?} in {:?}",
+                        candidate.parent_name,
+                    ))
+                })?;
+                let source = extract_lines(&parent_source, lines);
+                Ok(FunctionLookup::Found { candidate, source })
+            }
+            _ => Ok(FunctionLookup::Ambiguous(candidates)),
+        }
+    }
+}
+
+pub(crate) fn find_candidates(data: &GMData, name: &str) -> Result<Vec<FunctionCandidate>> {
+    let (owner, name) = match name.rsplit_once('.') {
+        Some((owner, name)) => (Some(owner), name),
+        None => (None, name),
+    };
+    let prefix = format!("gml_Script_{name}");
+
+    // Every declared function has an entry in the function table, named like its child entry
+    let function_names: HashSet<&str> = data
+        .functions
+        .elements()
+        .iter()
+        .map(|function| function.name.as_str())
+        .filter(|function| function.starts_with(&prefix))
+        .collect();
+
+    // By index of the root entry
+    let mut declarations: HashMap<u32, Vec<Declaration>> = HashMap::new();
+    let mut candidates = Vec::new();
+    for i in 0..data.codes.len() {
+        let code_ref = GMRef::from(i);
+        let code: &GMCode = data.codes.by_ref(code_ref)?;
+        if code.is_root() || !function_names.contains(code.name.as_str()) {
+            continue;
+        }
+
+        // A child with a broken parent link cannot be decompiled, but should not hide the others.
+        let Ok(Some(parent_ref)) = find_parent(code_ref, data) else {
+            continue;
+        };
+        let parent: &GMCode = data.codes.by_ref(parent_ref)?;
+        if child_function_name(&code.name, &parent.name) != name {
+            continue;
+        }
+        if let Some(owner) = owner
+            && !parent.name.contains(owner)
+        {
+            let declarations = match declarations.entry(u32::from(parent_ref)) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(declarations_in(parent_ref, data)?),
+            };
+            if !is_owned_by(declarations, code_ref, owner) {
+                continue;
+            }
+        }
+
+        candidates.push(FunctionCandidate {
+            code: code_ref,
+            parent: parent_ref,
+            parent_name: parent.name.clone(),
+        });
+    }
+    Ok(candidates)
+}
+
+/// Finds the lines declaring a child entry in the decompiled source of its root entry.
+///
+/// Functions with the same name (e.g. methods of different constructors) are told apart
+/// by their position: declarations appear in the source in the order of their bodies.
+pub(crate) fn find_declaration(
+    source: &str,
+    code_ref: GMRef<GMCode>,
+    parent_ref: GMRef<GMCode>,
+    data: &GMData,
+) -> Result<Option<Range<usize>>> {
+    let declarations = declarations_in(parent_ref, data)?;
+    if let Some((name, n)) = nth_declaration(&declarations, code_ref) {
+        return Ok(find_nth_function(source, name, n));
+    }
+    let child = data.codes.by_ref(code_ref)?;
+    let parent = data.codes.by_ref(parent_ref)?;
+    let name = child_function_name(&child.name, &parent.name);
+    Ok(find_function(source, name))
+}
+
+/// A GMLv2 function declared in a root entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Declaration {
+    code: GMRef<GMCode>,
+    /// Name of the function, as it appears in the decompiled root entry.
+    name: String,
+    /// Address range of the function body in the root entry.
+    range: Range<u32>,
+}
+
+fn declarations_in(root_ref: GMRef<GMCode>, data: &GMData) -> Result<Vec<Declaration>> {
+    let root: &GMCode = data.codes.by_ref(root_ref)?;
+    let code = Code::try_from_libgm(root_ref, data)?;
+    let instructions: Vec<(u32, &Instruction)> = code.addressed_instructions().collect();
+    let ranges = function_ranges(root_ref, data, &instructions, root.length());
+
+    let mut declarations = Vec::with_capacity(ranges.len());
+    for (code_ref, range) in ranges {
+        let child: &GMCode = data.codes.by_ref(code_ref)?;
+        declarations.push(Declaration {
+            code: code_ref,
+            name: child_function_name(&child.name, &root.name).to_owned(),
+            range,
+        });
+    }
+    Ok(declarations)
+}
+
+/// The name of a function and which of the declarations with that name it is.
+fn nth_declaration(declarations: &[Declaration], code_ref: GMRef<GMCode>) -> Option<(&str, usize)> {
+    let function = declarations.iter().find(|decl| decl.code == code_ref)?;
+    let n = declarations
+        .iter()
+        .filter(|decl| decl.name == function.name && decl.range.start < function.range.start)
+        .count();
+    Some((&function.name, n))
+}
+
+/// Whether the function's body lies inside the body of a function named `owner`.
+fn is_owned_by(declarations: &[Declaration], code_ref: GMRef<GMCode>, owner: &str) -> bool {
+    let Some(function) = declarations.iter().find(|decl| decl.code == code_ref) else {
+        return false;
+    };
+    declarations.iter().any(|decl| {
+        decl.code != code_ref
+            && decl.name == owner
+            && decl.range.start <= function.range.start
+            && function.range.end <= decl.range.end
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use libgm::prelude::*;
+
+    use super::{Declaration, is_owned_by, nth_declaration};
+
+    fn declaration(index: usize, name: &str, start: u32, end: u32) -> Declaration {
+        Declaration {
+            code: GMRef::from(index),
+            name: name.to_owned(),
+            range: start..end,
+        }
+    }
+
+    /// function A() constructor { f = function() {} }
+    /// function B() constructor { f = function() {} }
+    fn constructors() -> [Declaration; 4] {
+        [
+            declaration(1, "A", 4, 40),
+            declaration(2, "f", 12, 28),
+            declaration(3, "B", 44, 80),
+            declaration(4, "f", 52, 68),
+        ]
+    }
+
+    #[test]
+    fn owners_contain_their_functions() {
+        let declarations = constructors();
+        assert!(is_owned_by(&declarations, GMRef::from(2), "A"));
+        assert!(!is_owned_by(&declarations, GMRef::from(2), "B"));
+        assert!(is_owned_by(&declarations, GMRef::from(4), "B"));
+        assert!(!is_owned_by(&declarations, GMRef::from(4), "A"));
+        assert!(!is_owned_by(&declarations, GMRef::from(1), "A"));
+        assert!(!is_owned_by(&declarations, GMRef::from(9), "A"));
+    }
+
+    #[test]
+    fn same_named_functions_are_numbered_in_order() {
+        let declarations = constructors();
+        assert_eq!(
+            nth_declaration(&declarations, GMRef::from(2)),
+            Some(("f", 0))
+        );
+        assert_eq!(
+            nth_declaration(&declarations, GMRef::from(4)),
+            Some(("f", 1))
+        );
+        assert_eq!(
+            nth_declaration(&declarations, GMRef::from(3)),
+            Some(("B", 0))
+        );
+        assert_eq!(nth_declaration(&declarations, GMRef::from(9)), None);
+    }
+}
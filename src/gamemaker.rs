@@ -1,9 +1,32 @@
+mod asset_kind;
+mod builtins;
+mod capabilities;
 mod code;
+mod context_builder;
+mod decompile_settings;
 mod function;
 mod game_context;
 mod instruction;
 mod variable;
 
-pub use code::Code;
+pub use asset_kind::AssetKind;
+pub use builtins::{
+    BuiltinFunction, BuiltinVariable, ExtensionFunction, RawBuiltinFunction, RawBuiltinVariable,
+    RawConstant,
+};
+pub use capabilities::Capabilities;
+pub use code::{Code, child_function_name, find_parent};
+pub use context_builder::GameContextBuilder;
+pub use decompile_settings::DecompileSettings;
 pub use game_context::GameContext;
 pub use instruction::Instruction;
+pub(crate) use instruction::{
+    OPCODE_ADD, OPCODE_AND, OPCODE_BRANCH, OPCODE_BRANCH_FALSE, OPCODE_BRANCH_TRUE, OPCODE_CALL,
+    OPCODE_CALL_VARIABLE, OPCODE_CMP, OPCODE_CONV, OPCODE_DIV, OPCODE_DUP, OPCODE_EXIT,
+    OPCODE_EXTENDED, OPCODE_MOD, OPCODE_MUL, OPCODE_NEG, OPCODE_NOT, OPCODE_OR, OPCODE_POP,
+    OPCODE_POP_ENV, OPCODE_POPZ, OPCODE_PUSH, OPCODE_PUSH_BUILTIN, OPCODE_PUSH_ENV,
+    OPCODE_PUSH_GLOBAL, OPCODE_PUSH_IMMEDIATE, OPCODE_PUSH_LOCAL, OPCODE_REM, OPCODE_RETURN,
+    OPCODE_SHL, OPCODE_SHR, OPCODE_SUB, OPCODE_XOR, TYPE_BOOL, TYPE_DOUBLE, TYPE_INT16, TYPE_INT32,
+    TYPE_INT64, TYPE_STRING, TYPE_VARIABLE,
+};
+pub(crate) use variable::INSTANCE_TYPE_LOCAL;
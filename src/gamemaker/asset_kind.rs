@@ -0,0 +1,63 @@
+/// The kind of asset referenced by a `pushref` instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AssetKind {
+    Object,
+    Sprite,
+    Sound,
+    Room,
+    Background,
+    Path,
+    Script,
+    Font,
+    Timeline,
+    Shader,
+    Sequence,
+    AnimationCurve,
+    ParticleSystem,
+    RoomInstance,
+}
+
+impl AssetKind {
+    /// Decodes the asset type stored in the upper 8 bits of an asset reference.
+    /// Matches `GetAssetReferenceType` of the C# library.
+    #[must_use]
+    pub const fn from_reference_type(asset_type: u8) -> Option<Self> {
+        Some(match asset_type {
+            0 => Self::Object,
+            1 => Self::Sprite,
+            2 => Self::Sound,
+            3 => Self::Room,
+            4 => Self::Path,
+            5 => Self::Script,
+            6 => Self::Font,
+            7 => Self::Timeline,
+            8 => Self::Shader,
+            9 => Self::Sequence,
+            10 => Self::AnimationCurve,
+            11 => Self::ParticleSystem,
+            13 => Self::Background,
+            14 => Self::RoomInstance,
+            _ => return None,
+        })
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Object => "object",
+            Self::Sprite => "sprite",
+            Self::Sound => "sound",
+            Self::Room => "room",
+            Self::Background => "background",
+            Self::Path => "path",
+            Self::Script => "script",
+            Self::Font => "font",
+            Self::Timeline => "timeline",
+            Self::Shader => "shader",
+            Self::Sequence => "sequence",
+            Self::AnimationCurve => "animation_curve",
+            Self::ParticleSystem => "particle_system",
+            Self::RoomInstance => "room_instance",
+        }
+    }
+}
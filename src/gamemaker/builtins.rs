@@ -0,0 +1,184 @@
+use libgm::gamemaker::elements::extension::GMExtensionFunction;
+
+use crate::{
+    GameContext,
+    dynlib::{lookup_builtin_function, lookup_builtin_variable, lookup_constant},
+    primitives::RustStr,
+};
+
+/// A builtin GML function (or extension function) known to the compiler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinFunction {
+    pub min_args: u32,
+    /// [`None`] if the function accepts any number of arguments.
+    pub max_args: Option<u32>,
+}
+
+/// A builtin GML variable known to the compiler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinVariable {
+    pub can_set: bool,
+    pub is_global: bool,
+    /// Whether an array index is added automatically when compiling an access without one.
+    pub is_automatic_array: bool,
+}
+
+// FFI definitions ------>
+#[repr(C)]
+pub struct RawBuiltinFunction {
+    exists: bool,
+    min_args: i32,
+    max_args: i32,
+}
+
+#[repr(C)]
+pub struct RawBuiltinVariable {
+    exists: bool,
+    can_set: bool,
+    is_global: bool,
+    is_automatic_array: bool,
+}
+
+#[repr(C)]
+pub struct RawConstant {
+    exists: bool,
+    value: f64,
+}
+
+#[repr(C)]
+pub struct ExtensionFunction<'a> {
+    name: RustStr<'a>,
+    argument_count: i32,
+}
+// <------- FFI definitions
+
+impl<'a> ExtensionFunction<'a> {
+    #[must_use]
+    pub fn from_libgm(function: &'a GMExtensionFunction) -> Self {
+        Self {
+            name: RustStr::from_str(&function.name),
+            argument_count: i32::try_from(function.arguments.len()).unwrap_or(-1),
+        }
+    }
+}
+
+impl GameContext<'_> {
+    /// Looks up a builtin function (or a function provided by one of the game's extensions)
+    /// in the builtin database for this game's version.
+    ///
+    /// # Panics
+    /// This function panics if the dynamic library was not initialized yet and fails to load.
+    #[must_use]
+    pub fn builtin_function(&self, name: &str) -> Option<BuiltinFunction> {
+        let raw = unsafe { lookup_builtin_function(self, RustStr::from_str(name)) };
+        if !raw.exists {
+            return None;
+        }
+        Some(BuiltinFunction {
+            min_args: raw.min_args.try_into().unwrap_or(0),
+            max_args: (raw.max_args != i32::MAX).then(|| raw.max_args.try_into().unwrap_or(0)),
+        })
+    }
+
+    /// Looks up a builtin variable in the builtin database for this game's version.
+    ///
+    /// # Panics
+    /// This function panics if the dynamic library was not initialized yet and fails to load.
+    #[must_use]
+    pub fn builtin_variable(&self, name: &str) -> Option<BuiltinVariable> {
+        let raw = unsafe { lookup_builtin_variable(self, RustStr::from_str(name)) };
+        raw.exists.then_some(BuiltinVariable {
+            can_set: raw.can_set,
+            is_global: raw.is_global,
+            is_automatic_array: raw.is_automatic_array,
+        })
+    }
+
+    /// Looks up the value of a builtin constant (like `c_white` or `vk_enter`)
+    /// in the builtin database for this game's version.
+    ///
+    /// # Panics
+    /// This function panics if the dynamic library was not initialized yet and fails to load.
+    #[must_use]
+    pub fn constant(&self, name: &str) -> Option<f64> {
+        let raw = unsafe { lookup_constant(self, RustStr::from_str(name)) };
+        raw.exists.then_some(raw.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libgm::gamemaker::version::{GMVersion, LTSBranch};
+
+    use super::BuiltinFunction;
+    use crate::GameContext;
+
+    fn context(major: u32, minor: u32, release: u32) -> GameContext<'static> {
+        let ver = GMVersion::new(major, minor, release, 0, LTSBranch::PreLTS);
+        GameContext::from_version(&ver, 17, true, true)
+    }
+
+    #[test]
+    fn functions_are_version_gated() {
+        let gms1 = context(1, 4, 0);
+        let gms2 = context(2, 2, 0);
+        assert_eq!(gms1.builtin_function("d3d_start"), None);
+        assert!(gms2.builtin_function("d3d_start").is_some());
+
+        let expected = |args| {
+            Some(BuiltinFunction {
+                min_args: args,
+                max_args: Some(args),
+            })
+        };
+        assert_eq!(gms2.builtin_function("buffer_get_surface"), expected(5));
+        let gms2_3_1 = context(2, 3, 1);
+        assert_eq!(gms2_3_1.builtin_function("buffer_get_surface"), expected(3));
+    }
+
+    #[test]
+    fn variadic_functions_have_no_maximum() {
+        let ctx = context(2, 3, 0);
+        let function = ctx.builtin_function("matrix_stack_push").unwrap();
+        assert_eq!(function.max_args, None);
+    }
+
+    #[test]
+    fn variables_and_constants() {
+        let ctx = context(2, 3, 0);
+        let x = ctx.builtin_variable("x").unwrap();
+        assert!(x.can_set);
+        assert!(!x.is_global);
+        assert!(!ctx.builtin_variable("fps").unwrap().can_set);
+        assert_eq!(ctx.constant("c_white"), Some(16_777_215.0));
+        assert_eq!(ctx.constant("not_a_constant"), None);
+    }
+    #[test]
+    fn build_gates_ignore_the_minor_version() {
+        let gms1 = |minor, build| {
+            let ver = GMVersion::new(1, minor, 0, build, LTSBranch::PreLTS);
+            GameContext::from_version(&ver, 15, false, false)
+        };
+        assert!(gms1(4, 1763).builtin_function("immersion_stop").is_some());
+        assert!(gms1(0, 1700).builtin_function("immersion_stop").is_some());
+        assert_eq!(gms1(4, 1764).builtin_function("immersion_stop"), None);
+        assert_eq!(context(2, 0, 0).builtin_function("immersion_stop"), None);
+    }
+
+    #[test]
+    fn loaded_databases_extend_the_embedded_one() {
+        crate::load_builtins(
+            r#"{ "functions": [{ "name": "underanalyzer_test_function", "args": 2 }] }"#,
+        )
+        .unwrap();
+        let ctx = context(2, 3, 0);
+        assert_eq!(
+            ctx.builtin_function("underanalyzer_test_function"),
+            Some(BuiltinFunction {
+                min_args: 2,
+                max_args: Some(2),
+            })
+        );
+        assert!(ctx.builtin_function("d3d_start").is_some());
+    }
+}
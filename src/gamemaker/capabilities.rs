@@ -0,0 +1,39 @@
+/// Feature flags that decide which decompilation rules Underanalyzer applies.
+///
+/// Normally, these are derived from the game's version, WAD version and code analysis results.
+/// If those are wrong (spoofed or stripped `GEN8` version info), the flags can be overridden
+/// using [`GameContext::set_capabilities`].
+///
+/// The field order has to match `ContextFlags` in the C# library.
+///
+/// [`GameContext::set_capabilities`]: crate::GameContext::set_capabilities
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Capabilities {
+    pub using_gms2_or_later: bool,
+    pub using_gmlv2: bool,
+    pub using_string_real_optimizations: bool,
+    pub using_typed_booleans: bool,
+    pub using_nullish_operator: bool,
+    pub using_asset_references: bool,
+    pub using_room_instance_references: bool,
+    pub using_function_script_references: bool,
+    pub using_new_function_resolution: bool,
+    pub bytecode_14_or_lower: bool,
+    pub using_logical_short_circuit: bool,
+    pub using_long_compound_bitwise: bool,
+    pub using_extra_repeat_instruction: bool,
+    pub using_finally_before_throw: bool,
+    pub using_constructor_set_static: bool,
+    pub using_array_copy_on_write: bool,
+    pub using_new_array_owners: bool,
+    pub using_reentrant_static: bool,
+    pub using_new_function_variables: bool,
+    pub using_self_to_builtin: bool,
+    pub using_global_constant_function: bool,
+    pub using_object_function_foresight: bool,
+    pub using_better_try_break_continue: bool,
+    pub using_builtin_default_arguments: bool,
+    pub using_optimized_function_declarations: bool,
+}
@@ -1,4 +1,6 @@
-use libgm::{gml::GMCode, prelude::*};
+use std::{ops::Range, ptr};
+
+use libgm::{gamemaker::elements::code_locals::GMCodeLocal, gml::GMCode, prelude::*};
 
 use crate::{
     gamemaker::Instruction,
@@ -10,6 +12,8 @@ pub struct Code<'a> {
     name: RustStr<'a>,
     instructions: RawArray<Instruction<'a>>,
     children: RawArray<Self>,
+    /// Null for root code entries.
+    parent: *const Self,
     length: u32,
     start_offset: u32,
     argument_count: u16,
@@ -17,36 +21,273 @@ pub struct Code<'a> {
 }
 
 impl<'a> Code<'a> {
-    pub fn try_from_libgm(code_ref: GMRef<GMCode>, data: &'a GMData) -> Result<Self> {
+    /// Converts the given code entry and all of its children.
+    ///
+    /// The result is boxed so that the children can point to their parent.
+    /// The parent of a child entry is not converted; use [`find_parent`] to convert the root instead.
+    pub fn try_from_libgm(code_ref: GMRef<GMCode>, data: &'a GMData) -> Result<Box<Self>> {
+        let mut code = Box::new(Self::convert(code_ref, data, ptr::null())?);
+        let parent: *const Self = &raw const *code;
+        code.children = RawArray::from_vec(get_children(code_ref, data, parent)?);
+        Ok(code)
+    }
+
+    /// Converts a root entry like [`Code::try_from_libgm`], but replaces `body`
+    /// (the address range of one of its function bodies) with a single `exit`.
+    ///
+    /// Branches, child offsets and the length are fixed up, and children declared
+    /// inside the body are left out. This way, the rest of the entry can be decompiled
+    /// if the decompiler fails on that function.
+    pub fn try_from_libgm_stubbed(
+        code_ref: GMRef<GMCode>,
+        data: &'a GMData,
+        body: Range<u32>,
+    ) -> Result<Box<Self>> {
+        let mut code = Self::try_from_libgm(code_ref, data)?;
+        let removed: u32 = (body.end - body.start)
+            .checked_sub(Instruction::EXIT.size())
+            .ok_or_else(|| libgm::Error::new(format!("Function body {body:?} is too short")))?;
+        let relocate = |address: u32| {
+            if address >= body.end {
+                address - removed
+            } else {
+                address.min(body.start)
+            }
+        };
+
+        let mut instructions = Vec::new();
+        let mut address: u32 = 0;
+        for mut instr in std::mem::replace(&mut code.instructions, RawArray::empty()).into_vec() {
+            let current = address;
+            address += instr.size();
+            if current == body.start {
+                instructions.push(Instruction::EXIT);
+            }
+            if body.contains(&current) {
+                continue;
+            }
+            if instr.is_branch() {
+                instr.retarget(relocate(current), relocate(instr.branch_target(current)));
+            }
+            instructions.push(instr);
+        }
+        code.instructions = RawArray::from_vec(instructions);
+
+        let old_length = code.length;
+        code.length -= removed;
+        let mut children = std::mem::replace(&mut code.children, RawArray::empty()).into_vec();
+        children.retain(|child| child.start_offset <= body.start || child.start_offset >= body.end);
+        for child in &mut children {
+            child.start_offset = relocate(child.start_offset);
+            if child.length == old_length {
+                child.length = code.length;
+            }
+        }
+        code.children = RawArray::from_vec(children);
+        Ok(code)
+    }
+
+    /// Converts a single code entry without its children.
+    fn convert(code_ref: GMRef<GMCode>, data: &'a GMData, parent: *const Self) -> Result<Self> {
         let code: &GMCode = data.codes.by_ref(code_ref)?;
+        let locals: Option<&GMCodeLocal> = find_code_locals(code, data);
 
-        let modern = code.modern_data.clone().unwrap_or_default();
-        let start_offset: u32 = modern.offset;
-        let argument_count = modern.arguments_count;
-        let local_count = modern.locals_count;
+        // Bytecode 14 and lower has no modern data; the code locals are the only
+        // source for the local count there. Arguments are accessed via builtins.
+        let (start_offset, argument_count, local_count) = match &code.modern_data {
+            Some(modern) => (modern.offset, modern.arguments_count, modern.locals_count),
+            None => (0, 0, legacy_local_count(locals)?),
+        };
 
         Ok(Self {
             name: RustStr::from_str(&code.name),
-            instructions: RawArray::from_vec(get_instructions(code, data)?),
-            children: RawArray::from_vec(get_children(code_ref, data)?),
+            instructions: RawArray::from_vec(get_instructions(code, data, locals)?),
+            children: RawArray::empty(),
+            parent,
             length: code.length(),
             start_offset,
             argument_count,
             local_count,
         })
     }
+
+    /// The converted instructions, paired with their byte address.
+    pub fn addressed_instructions(&self) -> impl Iterator<Item = (u32, &Instruction<'a>)> {
+        self.instructions
+            .as_slice()
+            .iter()
+            .scan(0, |address, instr| {
+                let current = *address;
+                *address += instr.size();
+                Some((current, instr))
+            })
+    }
+
+    /// Length of the instructions in bytes.
+    #[must_use]
+    pub const fn length(&self) -> u32 {
+        self.length
+    }
+
+    /// Disassembles the instructions of this entry, one per line.
+    ///
+    /// The entry points of child entries are labelled with their name.
+    #[must_use]
+    pub fn disassemble(&self) -> String {
+        self.disassemble_range(0..u32::MAX)
+    }
+
+    /// Disassembles the instructions in the given address range, like [`Code::disassemble`].
+    #[must_use]
+    pub fn disassemble_range(&self, range: Range<u32>) -> String {
+        let children = self.children.as_slice();
+        let mut output = String::new();
+        for (address, instr) in self
+            .addressed_instructions()
+            .filter(|(address, _)| range.contains(address))
+        {
+            for child in children.iter().filter(|c| c.start_offset == address) {
+                output += &format!("> {}\n", child.name.as_str());
+            }
+            output += &format!("{address:05}: {}\n", instr.disassemble(address));
+        }
+        output
+    }
 }
 
-fn get_instructions<'a>(code: &'a GMCode, data: &'a GMData) -> Result<Vec<Instruction<'a>>> {
+fn get_instructions<'a>(
+    code: &'a GMCode,
+    data: &'a GMData,
+    locals: Option<&'a GMCodeLocal>,
+) -> Result<Vec<Instruction<'a>>> {
     code.instructions
         .iter()
-        .map(|i| Instruction::try_from_libgm(i, data))
+        .map(|i| Instruction::try_from_libgm(i, data, locals))
         .collect()
 }
 
-fn get_children<'a>(code_ref: GMRef<GMCode>, data: &'a GMData) -> Result<Vec<Code<'a>>> {
+/// The local count of a code entry without modern data, which is the number of its code locals.
+fn legacy_local_count(locals: Option<&GMCodeLocal>) -> Result<u16> {
+    let count: usize = locals.map_or(0, |locals| locals.variables.len());
+    u16::try_from(count)
+        .map_err(|_| libgm::Error::new(format!("Code entry has too many locals ({count})")))
+}
+
+/// Finds the code locals entry (`FUNC` chunk) with the same name as the code entry.
+/// These only exist in games before GMS 2.3.
+fn find_code_locals<'a>(code: &GMCode, data: &'a GMData) -> Option<&'a GMCodeLocal> {
+    data.code_locals
+        .elements()
+        .iter()
+        .find(|locals| locals.name == code.name)
+}
+
+/// GameMaker stores all (nested) functions as direct children of the root entry,
+/// so children never have children of their own.
+fn get_children<'a>(
+    code_ref: GMRef<GMCode>,
+    data: &'a GMData,
+    parent: *const Code<'a>,
+) -> Result<Vec<Code<'a>>> {
     GMCode::find_children(code_ref, data)
         .into_iter()
-        .map(|child_ref| Code::try_from_libgm(child_ref, data))
+        .map(|child_ref| Code::convert(child_ref, data, parent))
         .collect()
 }
+
+/// Finds the root code entry the given child entry belongs to.
+/// Returns [`None`] if the code entry is a root entry itself.
+///
+/// Children link to their root directly, so this also works for functions of global scripts,
+/// whose names (`gml_Script_<function>`) do not mention their root.
+///
+/// # Errors
+/// This function fails if the code entry does not exist
+/// or if its parent is not a root entry that exists.
+pub fn find_parent(code_ref: GMRef<GMCode>, data: &GMData) -> Result<Option<GMRef<GMCode>>> {
+    let code: &GMCode = data.codes.by_ref(code_ref)?;
+    let Some(parent_ref) = code.modern_data.as_ref().and_then(|modern| modern.parent) else {
+        return Ok(None);
+    };
+    match data.codes.by_ref(parent_ref) {
+        Ok(parent) if parent.is_root() => Ok(Some(parent_ref)),
+        _ => Err(libgm::Error::new(format!(
+            "Could not find parent code entry of child entry {:?}",
+            code.name,
+        ))),
+    }
+}
+
+/// Returns the name of the function declared by a child code entry,
+/// as it appears in the decompiled parent (e.g. `take_damage` for
+/// `gml_Script_take_damage_gml_Object_obj_player_Create_0`).
+#[must_use]
+pub fn child_function_name<'n>(child_name: &'n str, parent_name: &str) -> &'n str {
+    let name = child_name.strip_prefix("gml_Script_").unwrap_or(child_name);
+    name.strip_suffix(parent_name)
+        .and_then(|name| name.strip_suffix('_'))
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use libgm::{
+        gamemaker::elements::code_locals::{GMCodeLocal, GMCodeLocalVariable},
+        gml::GMCode,
+        prelude::*,
+    };
+
+    use super::{find_parent, legacy_local_count};
+
+    fn code_locals(count: usize) -> GMCodeLocal {
+        GMCodeLocal {
+            name: "gml_Script_scr_a".to_owned(),
+            variables: (0..count)
+                .map(|index| GMCodeLocalVariable {
+                    index: u32::try_from(index).unwrap(),
+                    name: format!("local{index}"),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn legacy_local_count_comes_from_code_locals() {
+        assert_eq!(legacy_local_count(None).unwrap(), 0);
+        assert_eq!(legacy_local_count(Some(&code_locals(3))).unwrap(), 3);
+        assert!(legacy_local_count(Some(&code_locals(70_000))).is_err());
+    }
+
+    fn code(name: &str, parent: Option<usize>) -> GMCode {
+        let mut code = GMCode {
+            name: name.to_owned(),
+            ..GMCode::default()
+        };
+        let mut modern = code.modern_data.take().unwrap_or_default();
+        modern.parent = parent.map(GMRef::from);
+        code.modern_data = Some(modern);
+        code
+    }
+
+    #[test]
+    fn finds_parents_of_global_functions() {
+        let mut data = GMData::default();
+        data.codes.push(code("gml_GlobalScript_scr_a", None));
+        data.codes.push(code("gml_Script_f", Some(0)));
+        data.codes
+            .push(code("gml_Script_g_gml_Object_obj_a_Create_0", Some(3)));
+        data.codes.push(code("gml_Object_obj_a_Create_0", None));
+
+        assert_eq!(find_parent(GMRef::from(0), &data).unwrap(), None);
+        assert_eq!(
+            find_parent(GMRef::from(1), &data).unwrap(),
+            Some(GMRef::from(0))
+        );
+        assert_eq!(
+            find_parent(GMRef::from(2), &data).unwrap(),
+            Some(GMRef::from(3))
+        );
+        data.codes.push(code("gml_Script_h", Some(1)));
+        assert!(find_parent(GMRef::from(4), &data).is_err());
+    }
+}
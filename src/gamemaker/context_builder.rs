@@ -0,0 +1,124 @@
+use libgm::{
+    error::Context,
+    gamemaker::version::{GMVersion, LTSBranch},
+    gml::analysis::CodeAnalysis,
+    prelude::*,
+};
+
+use crate::gamemaker::{Capabilities, GameContext};
+
+/// Builder for a [`GameContext`] that overrides what would normally be inferred from the data file.
+///
+/// Many games report a generic version like `2.0.0.0` in their general info,
+/// which makes Underanalyzer pick the wrong decompilation rules.
+/// Every value that is not set explicitly is taken from the [`GMData`] as usual.
+///
+/// ```no_run
+/// # fn f(data: &libgm::prelude::GMData) -> libgm::Result<()> {
+/// use libgm::gamemaker::version::{GMVersion, LTSBranch};
+/// use underanalyzer::GameContext;
+///
+/// let ctx = GameContext::builder(data)
+///     .version(GMVersion::new(2023, 8, 0, 0, LTSBranch::PostLTS))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct GameContextBuilder<'a> {
+    data: &'a GMData,
+    version: Option<GMVersion>,
+    lts_branch: Option<LTSBranch>,
+    wad_version: Option<u8>,
+    short_circuit: Option<bool>,
+    array_copy_on_write: Option<bool>,
+    capabilities: Option<Capabilities>,
+}
+
+impl<'a> GameContextBuilder<'a> {
+    pub const fn new(data: &'a GMData) -> Self {
+        Self {
+            data,
+            version: None,
+            lts_branch: None,
+            wad_version: None,
+            short_circuit: None,
+            array_copy_on_write: None,
+            capabilities: None,
+        }
+    }
+
+    /// Uses this GameMaker version instead of the one stored in the data file.
+    pub fn version(mut self, version: GMVersion) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Uses this LTS branch instead of the one of the (possibly overridden) version.
+    pub const fn lts_branch(mut self, lts_branch: LTSBranch) -> Self {
+        self.lts_branch = Some(lts_branch);
+        self
+    }
+
+    /// Uses this bytecode (WAD) version instead of the one stored in the data file.
+    pub const fn wad_version(mut self, wad_version: u8) -> Self {
+        self.wad_version = Some(wad_version);
+        self
+    }
+
+    /// Skips the code analysis and declares whether the game's code uses logical short-circuiting.
+    pub const fn short_circuit(mut self, uses_short_circuit: bool) -> Self {
+        self.short_circuit = Some(uses_short_circuit);
+        self
+    }
+
+    /// Skips the code analysis and declares whether the game's code uses array copy-on-write.
+    pub const fn array_copy_on_write(mut self, uses_array_copy_on_write: bool) -> Self {
+        self.array_copy_on_write = Some(uses_array_copy_on_write);
+        self
+    }
+
+    /// Forces all feature flags, see [`GameContext::set_capabilities`].
+    pub const fn capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    /// Tries to build the [`GameContext`].
+    ///
+    /// The code analysis is only run if short-circuiting or array copy-on-write was not specified,
+    /// which makes this faster than [`GameContext::new`] if both are given.
+    ///
+    /// # Errors
+    /// This function may fail if the GameMaker data is malformed.
+    /// This mostly includes [`GMRef`]s out of bounds.
+    ///
+    /// [`GMRef`]: libgm::gamemaker::reference::GMRef
+    pub fn build(self) -> Result<GameContext<'a>> {
+        let gen8 = &self.data.general_info;
+
+        let mut version: GMVersion = self.version.unwrap_or_else(|| gen8.version.clone());
+        if let Some(branch) = self.lts_branch {
+            version.branch = branch;
+        }
+        let wad_version: u8 = self.wad_version.unwrap_or(gen8.wad_version);
+
+        let (short_circuit, array_cow) = match (self.short_circuit, self.array_copy_on_write) {
+            (Some(short_circuit), Some(array_cow)) => (short_circuit, array_cow),
+            (short_circuit, array_cow) => {
+                let analysis: CodeAnalysis = self.data.analyze_code();
+                (
+                    short_circuit.unwrap_or(analysis.uses_short_circuit),
+                    array_cow.unwrap_or(analysis.uses_array_copy_on_write),
+                )
+            }
+        };
+
+        let mut ctx =
+            GameContext::try_from_libgm(self.data, &version, wad_version, short_circuit, array_cow)
+                .with_context(|| format!("constructing GameContext for {}", gen8.game_name))?;
+        ctx.override_capabilities(self.capabilities);
+        Ok(ctx)
+    }
+}
@@ -0,0 +1,61 @@
+/// Settings for Underanalyzer's code printer, used when decompiling and formatting.
+///
+/// The defaults match the output of [`GameContext::decompile`].
+/// The field order has to match `DecompileSettings` in the C# library.
+///
+/// [`GameContext::decompile`]: crate::GameContext::decompile
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct DecompileSettings {
+    /// Number of spaces per indentation level, or 0 to indent with tabs.
+    pub indent_size: u8,
+    pub use_semicolon: bool,
+    /// Makes Underanalyzer wrap tokens in CSS color markup.
+    pub use_css_colors: bool,
+    /// Whether to print warnings (e.g. about leftover stack data) as comments.
+    pub print_warnings: bool,
+    pub macro_declarations_at_top: bool,
+    pub empty_line_after_block_locals: bool,
+    pub empty_line_around_enums: bool,
+    pub empty_line_around_branch_statements: bool,
+    pub empty_line_before_switch_cases: bool,
+    pub empty_line_after_switch_cases: bool,
+    pub empty_line_around_function_declarations: bool,
+    pub empty_line_around_static_initialization: bool,
+    pub open_block_brace_on_same_line: bool,
+    pub remove_single_line_block_braces: bool,
+    pub cleanup_try: bool,
+    pub cleanup_else_to_continue: bool,
+    pub cleanup_default_argument_values: bool,
+    pub cleanup_builtin_array_variables: bool,
+    pub cleanup_local_var_declarations: bool,
+    pub create_enum_declarations: bool,
+}
+
+impl Default for DecompileSettings {
+    fn default() -> Self {
+        Self {
+            indent_size: 4,
+            use_semicolon: true,
+            use_css_colors: false,
+            print_warnings: true,
+            macro_declarations_at_top: true,
+            empty_line_after_block_locals: true,
+            empty_line_around_enums: true,
+            empty_line_around_branch_statements: true,
+            empty_line_before_switch_cases: false,
+            empty_line_after_switch_cases: false,
+            empty_line_around_function_declarations: true,
+            empty_line_around_static_initialization: true,
+            open_block_brace_on_same_line: true,
+            remove_single_line_block_braces: false,
+            cleanup_try: false,
+            cleanup_else_to_continue: true,
+            cleanup_default_argument_values: true,
+            cleanup_builtin_array_variables: true,
+            cleanup_local_var_declarations: true,
+            create_enum_declarations: true,
+        }
+    }
+}
@@ -15,6 +15,11 @@ impl<'a> Function<'a> {
         Self { name }
     }
 
+    #[must_use]
+    pub const fn name(&self) -> &'a str {
+        self.name.as_str()
+    }
+
     #[must_use]
     pub fn from_libgm(function: &'a GMFunction) -> Self {
         Self::new(RustStr::from_str(&function.name))
@@ -1,13 +1,17 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use libgm::{
-    gamemaker::{
-        elements::general_info::GMGeneralInfo,
-        version::{GMVersion, LTSBranch},
-    },
-    gml::analysis::CodeAnalysis,
+    gamemaker::version::{GMVersion, LTSBranch},
     prelude::*,
 };
 
-use crate::primitives::{RawArray, RustStr};
+use crate::{
+    dynlib::release_context,
+    gamemaker::{
+        AssetKind, Capabilities, ExtensionFunction, function::Function, variable::Variable,
+    },
+    primitives::{RawArray, RustStr},
+};
 
 #[repr(u8)]
 enum RawBranch {
@@ -28,6 +32,9 @@ pub struct GameContext<'a> {
     short_curcuit: bool,
     array_cow: bool,
 
+    has_capability_overrides: bool,
+    capability_overrides: Capabilities,
+
     asset_object_names: RawArray<RustStr<'a>>,
     asset_sprite_names: RawArray<RustStr<'a>>,
     asset_sound_names: RawArray<RustStr<'a>>,
@@ -41,37 +48,138 @@ pub struct GameContext<'a> {
     asset_sequence_names: RawArray<RustStr<'a>>,
     asset_animcurve_names: RawArray<RustStr<'a>>,
     asset_particlesystem_names: RawArray<RustStr<'a>>,
+
+    extension_functions: RawArray<ExtensionFunction<'a>>,
+
+    /// All variables of the data file, indexed like `data.variables`.
+    variables: RawArray<Variable<'a>>,
+    /// All functions of the data file, indexed like `data.functions`.
+    functions: RawArray<Function<'a>>,
+
+    /// Unique id, which Underanalyzer uses to cache data derived from this context
+    /// (such as the builtins extended by the extension functions) until it is dropped.
+    id: u64,
 }
 
+static NEXT_CONTEXT_ID: AtomicU64 = AtomicU64::new(0);
+
 impl<'a> GameContext<'a> {
-    pub(crate) fn try_from_libgm(data: &'a GMData) -> Result<Self> {
-        let gen8: &GMGeneralInfo = &data.general_info;
-        let ver: &GMVersion = &gen8.version;
-        let analysis: CodeAnalysis = data.analyze_code();
+    pub(crate) fn try_from_libgm(
+        data: &'a GMData,
+        ver: &GMVersion,
+        wad_version: u8,
+        short_circuit: bool,
+        array_cow: bool,
+    ) -> Result<Self> {
+        let mut ctx = Self::from_version(ver, wad_version, short_circuit, array_cow);
+        ctx.asset_object_names = get_asset_names(&data.game_objects);
+        ctx.asset_sprite_names = get_asset_names(&data.sprites);
+        ctx.asset_sound_names = get_asset_names(&data.sounds);
+        ctx.asset_room_names = get_asset_names(&data.rooms);
+        ctx.asset_background_names = get_asset_names(&data.backgrounds);
+        ctx.asset_path_names = get_asset_names(&data.paths);
+        ctx.asset_script_names = get_asset_names(&data.scripts);
+        ctx.asset_font_names = get_asset_names(&data.fonts);
+        ctx.asset_timeline_names = get_asset_names(&data.timelines);
+        ctx.asset_shader_names = get_asset_names(&data.shaders);
+        ctx.asset_sequence_names = get_asset_names(&data.sequences);
+        ctx.asset_animcurve_names = get_asset_names(&data.animation_curves);
+        ctx.asset_particlesystem_names = get_asset_names(&data.particle_systems);
+        ctx.extension_functions = get_extension_functions(data);
+        ctx.variables = get_variables(data);
+        ctx.functions = get_functions(data);
+        Ok(ctx)
+    }
+
+    pub(crate) fn override_capabilities(&mut self, capabilities: Option<Capabilities>) {
+        self.has_capability_overrides = capabilities.is_some();
+        if let Some(capabilities) = capabilities {
+            self.capability_overrides = capabilities;
+        }
+    }
+
+    /// Returns the asset name tables Underanalyzer knows about, labeled with their asset kind.
+    pub(crate) fn asset_tables(&self) -> [(AssetKind, &RawArray<RustStr<'a>>); 13] {
+        [
+            (AssetKind::Object, &self.asset_object_names),
+            (AssetKind::Sprite, &self.asset_sprite_names),
+            (AssetKind::Sound, &self.asset_sound_names),
+            (AssetKind::Room, &self.asset_room_names),
+            (AssetKind::Background, &self.asset_background_names),
+            (AssetKind::Path, &self.asset_path_names),
+            (AssetKind::Script, &self.asset_script_names),
+            (AssetKind::Font, &self.asset_font_names),
+            (AssetKind::Timeline, &self.asset_timeline_names),
+            (AssetKind::Shader, &self.asset_shader_names),
+            (AssetKind::Sequence, &self.asset_sequence_names),
+            (AssetKind::AnimationCurve, &self.asset_animcurve_names),
+            (AssetKind::ParticleSystem, &self.asset_particlesystem_names),
+        ]
+    }
+
+    /// Returns the names of all assets Underanalyzer knows about.
+    pub(crate) fn asset_names(&self) -> impl Iterator<Item = &'a str> {
+        self.asset_tables()
+            .into_iter()
+            .flat_map(|(_, names)| names.as_slice())
+            .map(RustStr::as_str)
+    }
+
+    /// Finds an asset by name, returning its kind and index.
+    pub(crate) fn find_asset(&self, name: &str) -> Option<(AssetKind, usize)> {
+        self.asset_tables().into_iter().find_map(|(kind, names)| {
+            let index = names.as_slice().iter().position(|n| n.as_str() == name)?;
+            Some((kind, index))
+        })
+    }
 
-        Ok(Self {
+    /// Returns the names of all functions in the data file's function table.
+    pub(crate) fn function_names(&self) -> impl Iterator<Item = &'a str> {
+        self.functions.as_slice().iter().map(Function::name)
+    }
+
+    /// Creates a game context without any asset names.
+    pub(crate) fn from_version(
+        ver: &GMVersion,
+        wad_version: u8,
+        short_circuit: bool,
+        array_cow: bool,
+    ) -> Self {
+        Self {
             ver_major: ver.major,
-            ver_minor: ver.major,
+            ver_minor: ver.minor,
             ver_release: ver.release,
             ver_build: ver.build,
-            wad_version: gen8.wad_version,
+            wad_version,
             lts_branch: convert_lts_branch(ver.branch),
-            short_curcuit: analysis.uses_short_circuit,
-            array_cow: analysis.uses_array_copy_on_write,
-            asset_object_names: get_asset_names(&data.game_objects),
-            asset_sprite_names: get_asset_names(&data.sprites),
-            asset_sound_names: get_asset_names(&data.sounds),
-            asset_room_names: get_asset_names(&data.rooms),
-            asset_background_names: get_asset_names(&data.backgrounds),
-            asset_path_names: get_asset_names(&data.paths),
-            asset_script_names: get_asset_names(&data.scripts),
-            asset_font_names: get_asset_names(&data.fonts),
-            asset_timeline_names: get_asset_names(&data.timelines),
-            asset_shader_names: get_asset_names(&data.shaders),
-            asset_sequence_names: get_asset_names(&data.sequences),
-            asset_animcurve_names: get_asset_names(&data.animation_curves),
-            asset_particlesystem_names: get_asset_names(&data.particle_systems),
-        })
+            short_curcuit: short_circuit,
+            array_cow,
+            has_capability_overrides: false,
+            capability_overrides: Capabilities::default(),
+            asset_object_names: RawArray::empty(),
+            asset_sprite_names: RawArray::empty(),
+            asset_sound_names: RawArray::empty(),
+            asset_room_names: RawArray::empty(),
+            asset_background_names: RawArray::empty(),
+            asset_path_names: RawArray::empty(),
+            asset_script_names: RawArray::empty(),
+            asset_font_names: RawArray::empty(),
+            asset_timeline_names: RawArray::empty(),
+            asset_shader_names: RawArray::empty(),
+            asset_sequence_names: RawArray::empty(),
+            asset_animcurve_names: RawArray::empty(),
+            asset_particlesystem_names: RawArray::empty(),
+            extension_functions: RawArray::empty(),
+            variables: RawArray::empty(),
+            functions: RawArray::empty(),
+            id: NEXT_CONTEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+impl Drop for GameContext<'_> {
+    fn drop(&mut self) {
+        release_context(self.id);
     }
 }
 
@@ -83,6 +191,34 @@ const fn convert_lts_branch(libgm_branch_type: LTSBranch) -> RawBranch {
     }
 }
 
+fn get_extension_functions(data: &GMData) -> RawArray<ExtensionFunction<'_>> {
+    let mut vector = Vec::new();
+    for extension in data.extensions.elements() {
+        for file in &extension.files {
+            for function in &file.functions {
+                vector.push(ExtensionFunction::from_libgm(function));
+            }
+        }
+    }
+    RawArray::from_vec(vector)
+}
+
+fn get_variables(data: &GMData) -> RawArray<Variable<'_>> {
+    let mut vector = Vec::with_capacity(data.variables.len());
+    for variable in data.variables.elements() {
+        vector.push(Variable::from_libgm(variable));
+    }
+    RawArray::from_vec(vector)
+}
+
+fn get_functions(data: &GMData) -> RawArray<Function<'_>> {
+    let mut vector = Vec::with_capacity(data.functions.len());
+    for function in data.functions.elements() {
+        vector.push(Function::from_libgm(function));
+    }
+    RawArray::from_vec(vector)
+}
+
 fn get_asset_names(chunk: &impl GMNamedListChunk) -> RawArray<RustStr<'_>> {
     let mut vector = Vec::with_capacity(chunk.len());
     for element in chunk.elements() {
@@ -91,3 +227,172 @@ fn get_asset_names(chunk: &impl GMNamedListChunk) -> RawArray<RustStr<'_>> {
     }
     RawArray::from_vec(vector)
 }
+
+#[cfg(test)]
+mod tests {
+    use libgm::gamemaker::version::{GMVersion, LTSBranch};
+
+    use super::GameContext;
+    use crate::gamemaker::Capabilities;
+
+    fn capabilities_for(
+        (major, minor, release, build): (u32, u32, u32, u32),
+        branch: LTSBranch,
+        wad_version: u8,
+    ) -> Capabilities {
+        let ver = GMVersion::new(major, minor, release, build, branch);
+        let modern = wad_version > 14;
+        GameContext::from_version(&ver, wad_version, modern, modern).capabilities()
+    }
+
+    const GMS1: Capabilities = Capabilities {
+        using_gms2_or_later: false,
+        using_gmlv2: false,
+        using_string_real_optimizations: true,
+        using_typed_booleans: false,
+        using_nullish_operator: false,
+        using_asset_references: false,
+        using_room_instance_references: false,
+        using_function_script_references: false,
+        using_new_function_resolution: false,
+        bytecode_14_or_lower: true,
+        using_logical_short_circuit: false,
+        using_long_compound_bitwise: false,
+        using_extra_repeat_instruction: true,
+        using_finally_before_throw: true,
+        using_constructor_set_static: false,
+        using_array_copy_on_write: false,
+        using_new_array_owners: false,
+        using_reentrant_static: true,
+        using_new_function_variables: false,
+        using_self_to_builtin: false,
+        using_global_constant_function: false,
+        using_object_function_foresight: false,
+        using_better_try_break_continue: false,
+        using_builtin_default_arguments: false,
+        using_optimized_function_declarations: false,
+    };
+
+    const GMS2_2: Capabilities = Capabilities {
+        using_gms2_or_later: true,
+        bytecode_14_or_lower: false,
+        using_logical_short_circuit: true,
+        using_array_copy_on_write: true,
+        ..GMS1
+    };
+
+    const GMS2_3_0: Capabilities = Capabilities {
+        using_gmlv2: true,
+        ..GMS2_2
+    };
+
+    const GMS2_3_7: Capabilities = Capabilities {
+        using_typed_booleans: true,
+        using_nullish_operator: true,
+        using_long_compound_bitwise: true,
+        using_new_array_owners: true,
+        ..GMS2_3_0
+    };
+
+    const GM2022_LTS: Capabilities = GMS2_3_7;
+
+    const GM2023_8: Capabilities = Capabilities {
+        using_asset_references: true,
+        using_extra_repeat_instruction: false,
+        ..GM2022_LTS
+    };
+
+    const GM2024_2: Capabilities = Capabilities {
+        using_room_instance_references: true,
+        using_function_script_references: true,
+        using_new_function_resolution: true,
+        using_new_function_variables: true,
+        using_self_to_builtin: true,
+        using_global_constant_function: true,
+        ..GM2023_8
+    };
+
+    const GM2024_11: Capabilities = Capabilities {
+        using_finally_before_throw: false,
+        using_constructor_set_static: true,
+        using_reentrant_static: false,
+        using_object_function_foresight: true,
+        using_better_try_break_continue: true,
+        using_builtin_default_arguments: true,
+        ..GM2024_2
+    };
+
+    const GM2024_14: Capabilities = Capabilities {
+        using_optimized_function_declarations: true,
+        ..GM2024_11
+    };
+
+    #[test]
+    fn gms_1_4() {
+        let caps = capabilities_for((1, 4, 0, 1804), LTSBranch::PreLTS, 14);
+        assert_eq!(caps, GMS1);
+    }
+
+    #[test]
+    fn gms_2_2() {
+        let caps = capabilities_for((2, 2, 5, 481), LTSBranch::PreLTS, 17);
+        assert_eq!(caps, GMS2_2);
+    }
+
+    #[test]
+    fn gms_2_3_0() {
+        let caps = capabilities_for((2, 3, 0, 529), LTSBranch::PreLTS, 17);
+        assert_eq!(caps, GMS2_3_0);
+    }
+
+    #[test]
+    fn gms_2_3_7() {
+        let caps = capabilities_for((2, 3, 7, 606), LTSBranch::PreLTS, 17);
+        assert_eq!(caps, GMS2_3_7);
+    }
+
+    #[test]
+    fn gm_2022_lts() {
+        let caps = capabilities_for((2022, 0, 3, 99), LTSBranch::LTS, 17);
+        assert_eq!(caps, GM2022_LTS);
+    }
+
+    #[test]
+    fn gm_2023_8() {
+        let caps = capabilities_for((2023, 8, 0, 0), LTSBranch::PostLTS, 17);
+        assert_eq!(caps, GM2023_8);
+    }
+
+    #[test]
+    fn gm_2024_2() {
+        let caps = capabilities_for((2024, 2, 0, 0), LTSBranch::PostLTS, 17);
+        assert_eq!(caps, GM2024_2);
+    }
+
+    #[test]
+    fn gm_2024_11() {
+        let caps = capabilities_for((2024, 11, 0, 0), LTSBranch::PostLTS, 17);
+        assert_eq!(caps, GM2024_11);
+    }
+
+    #[test]
+    fn gm_2024_14() {
+        let caps = capabilities_for((2024, 14, 0, 0), LTSBranch::PostLTS, 17);
+        assert_eq!(caps, GM2024_14);
+    }
+
+    #[test]
+    fn overrides_replace_derived_flags() {
+        let ver = GMVersion::new(2, 0, 0, 0, LTSBranch::PreLTS);
+        let mut ctx = GameContext::from_version(&ver, 17, true, true);
+
+        let mut caps = ctx.capabilities();
+        assert!(!caps.using_gmlv2);
+        caps.using_gmlv2 = true;
+        ctx.set_capabilities(caps);
+        assert_eq!(ctx.capabilities(), caps);
+
+        ctx.reset_capabilities();
+        assert!(!ctx.capabilities().using_gmlv2);
+    }
+}
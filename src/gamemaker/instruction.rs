@@ -4,6 +4,7 @@ use crate::{
 };
 
 use libgm::{
+    gamemaker::elements::code_locals::GMCodeLocal,
     gml::instruction::{Instruction as LibGMInstruction, PushValue},
     prelude::*,
 };
@@ -19,6 +20,10 @@ pub struct Instruction<'a> {
     branch_offset: i32,
     argument_count: i32,
     asset_reference: i32,
+    /// Index into the data file's variables, or -1.
+    variable_index: i32,
+    /// Index into the data file's functions, or -1.
+    function_index: i32,
     value_short: i16,
     extended_kind: i16,
     instance_type: i16,
@@ -34,10 +39,18 @@ pub struct Instruction<'a> {
 }
 
 impl<'a> Instruction<'a> {
-    pub fn try_from_libgm(instr: &'a LibGMInstruction, data: &'a GMData) -> Result<Self> {
+    /// Converts a LibGM instruction into the layout Underanalyzer expects.
+    ///
+    /// `locals` are the code locals of the code entry this instruction belongs to, if any.
+    /// They are used to name locals in older games.
+    pub fn try_from_libgm(
+        instr: &'a LibGMInstruction,
+        data: &'a GMData,
+        locals: Option<&'a GMCodeLocal>,
+    ) -> Result<Self> {
         Ok(Self {
-            variable: extract_variable(instr, data)?,
-            function: extract_function(instr, data)?,
+            variable: extract_variable(instr, data, locals),
+            function: extract_function(instr, data),
             value_string: extract_string(instr),
             value_double: extract_double(instr),
             value_long: extract_long(instr),
@@ -45,6 +58,8 @@ impl<'a> Instruction<'a> {
             branch_offset: 4 * instr.jump_offset().unwrap_or(0),
             argument_count: extract_argument_count(instr),
             asset_reference: extract_asset_reference(instr),
+            variable_index: instr.variable().map_or(-1, |v| ref_index(v.variable)),
+            function_index: instr.function().map_or(-1, ref_index),
             value_short: extract_short(instr),
             extended_kind: instr.extended_kind().unwrap_or(0),
             instance_type: instr.variable().map_or(0, |v| v.instance_type.build()),
@@ -61,24 +76,394 @@ impl<'a> Instruction<'a> {
     }
 }
 
-fn extract_variable<'a>(instr: &LibGMInstruction, data: &'a GMData) -> Result<Variable<'a>> {
-    if let Some(code_var) = instr.variable() {
-        let variable = data.variables.by_ref(code_var.variable)?;
-        Ok(Variable::from_libgm(variable))
-    } else {
-        Ok(Variable::NULL)
+// Underanalyzer opcodes (`IGMInstruction.Opcode`).
+pub(crate) const OPCODE_CONV: u8 = 0x07;
+pub(crate) const OPCODE_MUL: u8 = 0x08;
+pub(crate) const OPCODE_DIV: u8 = 0x09;
+pub(crate) const OPCODE_REM: u8 = 0x0A;
+pub(crate) const OPCODE_MOD: u8 = 0x0B;
+pub(crate) const OPCODE_ADD: u8 = 0x0C;
+pub(crate) const OPCODE_SUB: u8 = 0x0D;
+pub(crate) const OPCODE_AND: u8 = 0x0E;
+pub(crate) const OPCODE_OR: u8 = 0x0F;
+pub(crate) const OPCODE_XOR: u8 = 0x10;
+pub(crate) const OPCODE_NEG: u8 = 0x11;
+pub(crate) const OPCODE_NOT: u8 = 0x12;
+pub(crate) const OPCODE_SHL: u8 = 0x13;
+pub(crate) const OPCODE_SHR: u8 = 0x14;
+pub(crate) const OPCODE_CMP: u8 = 0x15;
+pub(crate) const OPCODE_POP: u8 = 0x45;
+pub(crate) const OPCODE_DUP: u8 = 0x86;
+pub(crate) const OPCODE_PUSH_IMMEDIATE: u8 = 0x84;
+pub(crate) const OPCODE_CALL_VARIABLE: u8 = 0x99;
+pub(crate) const OPCODE_RETURN: u8 = 0x9C;
+pub(crate) const OPCODE_EXIT: u8 = 0x9D;
+pub(crate) const OPCODE_POPZ: u8 = 0x9E;
+pub(crate) const OPCODE_BRANCH: u8 = 0xB6;
+pub(crate) const OPCODE_BRANCH_TRUE: u8 = 0xB7;
+pub(crate) const OPCODE_BRANCH_FALSE: u8 = 0xB8;
+pub(crate) const OPCODE_PUSH_ENV: u8 = 0xBA;
+pub(crate) const OPCODE_POP_ENV: u8 = 0xBB;
+pub(crate) const OPCODE_PUSH: u8 = 0xC0;
+pub(crate) const OPCODE_PUSH_LOCAL: u8 = 0xC1;
+pub(crate) const OPCODE_PUSH_GLOBAL: u8 = 0xC2;
+pub(crate) const OPCODE_PUSH_BUILTIN: u8 = 0xC3;
+pub(crate) const OPCODE_CALL: u8 = 0xD9;
+pub(crate) const OPCODE_EXTENDED: u8 = 0xFF;
+
+// Underanalyzer data types (`IGMInstruction.DataType`).
+pub(crate) const TYPE_DOUBLE: u8 = 0x0;
+pub(crate) const TYPE_INT32: u8 = 0x2;
+pub(crate) const TYPE_INT64: u8 = 0x3;
+pub(crate) const TYPE_BOOL: u8 = 0x4;
+pub(crate) const TYPE_VARIABLE: u8 = 0x5;
+pub(crate) const TYPE_STRING: u8 = 0x6;
+pub(crate) const TYPE_INT16: u8 = 0xF;
+
+impl<'a> Instruction<'a> {
+    /// An `exit.i` instruction.
+    pub const EXIT: Self = Self {
+        variable: Variable::NULL,
+        function: Function::NULL,
+        value_string: RustStr::EMPTY,
+        value_double: 0.0,
+        value_long: 0,
+        value_int: 0,
+        branch_offset: 0,
+        argument_count: 0,
+        asset_reference: 0,
+        variable_index: -1,
+        function_index: -1,
+        value_short: 0,
+        extended_kind: 0,
+        instance_type: 0,
+        opcode: OPCODE_EXIT,
+        type1: TYPE_INT32,
+        type2: 0,
+        comparison_kind: 0,
+        duplication_size: 0,
+        duplication_size2: 0,
+        variable_type: 0,
+        pop_swap_size: 0,
+        pop_with_context_exit: 0,
+    };
+
+    /// Size of the encoded instruction in bytes, including its operand.
+    #[must_use]
+    pub const fn size(&self) -> u32 {
+        match self.opcode {
+            OPCODE_PUSH | OPCODE_PUSH_LOCAL | OPCODE_PUSH_GLOBAL | OPCODE_PUSH_BUILTIN => {
+                match self.type1 {
+                    TYPE_INT16 => 4,
+                    TYPE_DOUBLE | TYPE_INT64 => 12,
+                    _ => 8,
+                }
+            }
+            OPCODE_POP if self.pop_swap_size != 0 => 4,
+            OPCODE_POP | OPCODE_CALL => 8,
+            OPCODE_EXTENDED if self.type1 == TYPE_INT32 => 8,
+            _ => 4,
+        }
+    }
+
+    /// Whether this instruction jumps to [`Self::branch_target`].
+    #[must_use]
+    pub const fn is_branch(&self) -> bool {
+        matches!(
+            self.opcode,
+            OPCODE_BRANCH
+                | OPCODE_BRANCH_TRUE
+                | OPCODE_BRANCH_FALSE
+                | OPCODE_PUSH_ENV
+                | OPCODE_POP_ENV
+        ) && self.pop_with_context_exit == 0
+    }
+
+    /// The absolute address this instruction jumps to, given its own address.
+    #[must_use]
+    pub const fn branch_target(&self, address: u32) -> u32 {
+        address.wrapping_add_signed(self.branch_offset)
+    }
+
+    /// Makes this branch jump to `target` instead, given its own address.
+    pub const fn retarget(&mut self, address: u32, target: u32) {
+        self.branch_offset = target.wrapping_sub(address).cast_signed();
+    }
+
+    /// Whether this instruction completes a statement (assignment, discarded call, jump, ...).
+    #[must_use]
+    pub const fn ends_statement(&self) -> bool {
+        match self.opcode {
+            OPCODE_POP => self.pop_swap_size == 0,
+            OPCODE_RETURN | OPCODE_EXIT | OPCODE_POPZ => true,
+            _ => self.is_branch() || self.opcode == OPCODE_POP_ENV,
+        }
+    }
+
+    /// The name of the variable this instruction reads or writes, if resolved.
+    #[must_use]
+    pub fn variable_name(&self) -> Option<&'a str> {
+        Some(self.variable.name()).filter(|name| !name.is_empty())
+    }
+
+    /// The name of the function this instruction calls or pushes, if resolved.
+    #[must_use]
+    pub fn function_name(&self) -> Option<&'a str> {
+        Some(self.function.name()).filter(|name| !name.is_empty())
+    }
+
+    /// Whether this instruction assigns to its variable (instead of reading it).
+    #[must_use]
+    pub const fn is_pop(&self) -> bool {
+        self.opcode == OPCODE_POP
+    }
+
+    /// Whether this instruction calls its function (instead of pushing a reference to it).
+    #[must_use]
+    pub const fn is_call(&self) -> bool {
+        self.opcode == OPCODE_CALL
+    }
+
+    /// Whether this instruction calls a function value taken from the stack (`callv`).
+    #[must_use]
+    pub const fn is_call_variable(&self) -> bool {
+        self.opcode == OPCODE_CALL_VARIABLE
+    }
+
+    /// Whether this instruction converts the value on top of the stack to another type.
+    #[must_use]
+    pub const fn is_conversion(&self) -> bool {
+        self.opcode == OPCODE_CONV
+    }
+
+    /// Whether this instruction always jumps to [`Self::branch_target`].
+    #[must_use]
+    pub const fn is_unconditional_branch(&self) -> bool {
+        self.opcode == OPCODE_BRANCH
+    }
+
+    /// The integer this instruction pushes, if it pushes an integer constant.
+    #[must_use]
+    pub const fn integer_value(&self) -> Option<i64> {
+        match (self.opcode, self.type1) {
+            (OPCODE_PUSH_IMMEDIATE, _) | (OPCODE_PUSH, TYPE_INT16) => Some(self.value_short as i64),
+            (OPCODE_PUSH, TYPE_INT32)
+                if self.function_index == -1 && self.function.name().is_empty() =>
+            {
+                Some(self.value_int as i64)
+            }
+            (OPCODE_PUSH, TYPE_INT64) => Some(self.value_long),
+            _ => None,
+        }
+    }
+
+    /// Formats this instruction in a textual assembly syntax, like `pushi.e 5`.
+    #[must_use]
+    pub fn disassemble(&self, address: u32) -> String {
+        let mut output = String::from(mnemonic(self.opcode));
+        match self.opcode {
+            OPCODE_CMP => {
+                output += &format!(
+                    ".{}.{} {}",
+                    type_suffix(self.type1),
+                    type_suffix(self.type2),
+                    comparison(self.comparison_kind),
+                );
+            }
+            OPCODE_DUP if self.duplication_size2 != 0 => {
+                output += &format!(
+                    ".{} {} {}",
+                    type_suffix(self.type1),
+                    self.duplication_size,
+                    self.duplication_size2,
+                );
+            }
+            OPCODE_DUP => {
+                output += &format!(".{} {}", type_suffix(self.type1), self.duplication_size);
+            }
+            OPCODE_POP if self.pop_swap_size != 0 => {
+                output += &format!(".e swap {}", self.pop_swap_size);
+            }
+            OPCODE_POP => {
+                output += &format!(
+                    ".{}.{} {}",
+                    type_suffix(self.type1),
+                    type_suffix(self.type2),
+                    self.variable_operand(),
+                );
+            }
+            OPCODE_POP_ENV if self.pop_with_context_exit != 0 => output += " [drop]",
+            _ if self.is_branch() => {
+                output += &format!(" {:05}", self.branch_target(address));
+            }
+            OPCODE_PUSH
+            | OPCODE_PUSH_LOCAL
+            | OPCODE_PUSH_GLOBAL
+            | OPCODE_PUSH_BUILTIN
+            | OPCODE_PUSH_IMMEDIATE => {
+                output += &format!(".{} {}", type_suffix(self.type1), self.push_operand());
+            }
+            OPCODE_CALL => {
+                output += &format!(
+                    ".i {}(argc={})",
+                    self.function_operand(),
+                    self.argument_count,
+                );
+            }
+            OPCODE_CALL_VARIABLE => output += &format!(".v {}", self.argument_count),
+            OPCODE_EXTENDED if self.type1 == TYPE_INT32 => {
+                output += &format!(" {} {}", self.extended_kind, self.asset_reference);
+            }
+            OPCODE_EXTENDED => output += &format!(" {}", self.extended_kind),
+            _ if self.type2 != 0 || self.type1 != 0 => {
+                output += &format!(".{}.{}", type_suffix(self.type1), type_suffix(self.type2));
+            }
+            _ => {}
+        }
+        output
+    }
+
+    fn push_operand(&self) -> String {
+        match self.type1 {
+            TYPE_INT16 => self.value_short.to_string(),
+            TYPE_INT32 if self.function_index != -1 || !self.function.name().is_empty() => {
+                self.function_operand()
+            }
+            TYPE_INT32 | TYPE_BOOL => self.value_int.to_string(),
+            TYPE_INT64 => self.value_long.to_string(),
+            TYPE_DOUBLE => self.value_double.to_string(),
+            TYPE_STRING => format!("{:?}", self.value_string.as_str()),
+            TYPE_VARIABLE => self.variable_operand(),
+            _ => String::from("?"),
+        }
+    }
+
+    fn variable_operand(&self) -> String {
+        let name = match self.variable.name() {
+            "" => format!("[variable #{}]", self.variable_index),
+            name => name.to_owned(),
+        };
+        format!("{}.{name}", instance_name(self.instance_type))
+    }
+
+    fn function_operand(&self) -> String {
+        match self.function.name() {
+            "" => format!("[function #{}]", self.function_index),
+            name => name.to_owned(),
+        }
     }
 }
 
-fn extract_function<'a>(instr: &LibGMInstruction, data: &'a GMData) -> Result<Function<'a>> {
-    if let Some(func_ref) = instr.function() {
-        let function = data.functions.by_ref(func_ref)?;
-        Ok(Function::from_libgm(function))
-    } else {
-        Ok(Function::NULL)
+const fn mnemonic(opcode: u8) -> &'static str {
+    match opcode {
+        0x07 => "conv",
+        0x08 => "mul",
+        0x09 => "div",
+        0x0A => "rem",
+        0x0B => "mod",
+        0x0C => "add",
+        0x0D => "sub",
+        0x0E => "and",
+        0x0F => "or",
+        0x10 => "xor",
+        0x11 => "neg",
+        0x12 => "not",
+        0x13 => "shl",
+        0x14 => "shr",
+        OPCODE_CMP => "cmp",
+        OPCODE_POP => "pop",
+        OPCODE_DUP => "dup",
+        OPCODE_RETURN => "ret",
+        OPCODE_EXIT => "exit",
+        OPCODE_POPZ => "popz",
+        OPCODE_BRANCH => "b",
+        OPCODE_BRANCH_TRUE => "bt",
+        OPCODE_BRANCH_FALSE => "bf",
+        OPCODE_PUSH_ENV => "pushenv",
+        OPCODE_POP_ENV => "popenv",
+        OPCODE_PUSH => "push",
+        OPCODE_PUSH_LOCAL => "pushloc",
+        OPCODE_PUSH_GLOBAL => "pushglb",
+        OPCODE_PUSH_BUILTIN => "pushbltn",
+        OPCODE_PUSH_IMMEDIATE => "pushi",
+        OPCODE_CALL => "call",
+        OPCODE_CALL_VARIABLE => "callv",
+        OPCODE_EXTENDED => "extended",
+        _ => "unknown",
     }
 }
 
+const fn type_suffix(data_type: u8) -> char {
+    match data_type {
+        TYPE_DOUBLE => 'd',
+        0x1 => 'f',
+        TYPE_INT32 => 'i',
+        TYPE_INT64 => 'l',
+        TYPE_BOOL => 'b',
+        TYPE_VARIABLE => 'v',
+        TYPE_STRING => 's',
+        TYPE_INT16 => 'e',
+        _ => '?',
+    }
+}
+
+const fn comparison(kind: u8) -> &'static str {
+    match kind {
+        1 => "LT",
+        2 => "LE",
+        3 => "EQ",
+        4 => "NE",
+        5 => "GE",
+        6 => "GT",
+        _ => "??",
+    }
+}
+
+fn instance_name(instance_type: i16) -> String {
+    match instance_type {
+        -1 => "self".to_owned(),
+        -2 => "other".to_owned(),
+        -3 => "all".to_owned(),
+        -4 => "noone".to_owned(),
+        -5 => "global".to_owned(),
+        -6 => "builtin".to_owned(),
+        -7 => "local".to_owned(),
+        -9 => "stacktop".to_owned(),
+        -15 => "arg".to_owned(),
+        -16 => "static".to_owned(),
+        object => object.to_string(),
+    }
+}
+
+/// Unresolvable variables are left empty; Underanalyzer then falls back to
+/// `TryFindVariable`, which looks them up in the game context's variable table.
+fn extract_variable<'a>(
+    instr: &LibGMInstruction,
+    data: &'a GMData,
+    locals: Option<&'a GMCodeLocal>,
+) -> Variable<'a> {
+    let Some(code_var) = instr.variable() else {
+        return Variable::NULL;
+    };
+    let Ok(variable) = data.variables.by_ref(code_var.variable) else {
+        return Variable::NULL;
+    };
+    Variable::from_reference(variable, code_var.instance_type.build(), locals)
+}
+
+/// Unresolvable functions are left empty; Underanalyzer then falls back to
+/// `TryFindFunction`, which looks them up in the game context's function table.
+fn extract_function<'a>(instr: &LibGMInstruction, data: &'a GMData) -> Function<'a> {
+    instr
+        .function()
+        .and_then(|func_ref| data.functions.by_ref(func_ref).ok())
+        .map_or(Function::NULL, Function::from_libgm)
+}
+
+fn ref_index<T>(gm_ref: GMRef<T>) -> i32 {
+    i32::try_from(u32::from(gm_ref)).unwrap_or(-1)
+}
+
 fn extract_string(instr: &LibGMInstruction) -> RustStr<'_> {
     if let LibGMInstruction::Push {
         value: PushValue::String(string),
@@ -179,3 +564,90 @@ const fn extract_popswap_size(instr: &LibGMInstruction) -> u8 {
         _ => 0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(instr: &LibGMInstruction) -> Instruction<'_> {
+        // Leaked so the converted instruction may borrow from it.
+        let data: &'static GMData = Box::leak(Box::default());
+        Instruction::try_from_libgm(instr, data, None).unwrap()
+    }
+
+    #[test]
+    fn push_immediate() {
+        let instr = LibGMInstruction::PushImmediate { integer: -3 };
+        let converted = convert(&instr);
+        assert_eq!(converted.opcode, OPCODE_PUSH_IMMEDIATE);
+        assert_eq!(converted.value_short, -3);
+        assert_eq!(converted.variable_index, -1);
+        assert_eq!(converted.function_index, -1);
+        assert_eq!(converted.size(), 4);
+        assert_eq!(converted.disassemble(0), "pushi.e -3");
+        assert_eq!(converted.integer_value(), Some(-3));
+    }
+
+    #[test]
+    fn push_values() {
+        let instr = LibGMInstruction::Push {
+            value: PushValue::Int32(70_000),
+        };
+        let converted = convert(&instr);
+        assert_eq!(converted.opcode, OPCODE_PUSH);
+        assert_eq!(converted.value_int, 70_000);
+        assert_eq!(converted.size(), 8);
+        assert_eq!(converted.disassemble(0), "push.i 70000");
+        assert_eq!(converted.integer_value(), Some(70_000));
+
+        let instr = LibGMInstruction::Push {
+            value: PushValue::Int16(12),
+        };
+        let converted = convert(&instr);
+        assert_eq!(converted.opcode, OPCODE_PUSH);
+        assert_eq!(converted.value_short, 12);
+    }
+
+    #[test]
+    fn pop_swap() {
+        let converted = convert(&LibGMInstruction::PopSwap { is_array: false });
+        assert_eq!(converted.opcode, OPCODE_POP);
+        assert_eq!(converted.pop_swap_size, 5);
+
+        let converted = convert(&LibGMInstruction::PopSwap { is_array: true });
+        assert_eq!(converted.pop_swap_size, 6);
+        assert_eq!(converted.size(), 4);
+        assert_eq!(converted.disassemble(0), "pop.e swap 6");
+    }
+
+    #[test]
+    fn call_variable() {
+        let converted = convert(&LibGMInstruction::CallVariable { argument_count: 2 });
+        assert_eq!(converted.opcode, OPCODE_CALL_VARIABLE);
+        assert_eq!(converted.argument_count, 2);
+    }
+
+    #[test]
+    fn pop_with_context_exit() {
+        let converted = convert(&LibGMInstruction::PopWithContextExit);
+        assert_eq!(converted.opcode, OPCODE_POP_ENV);
+        assert_eq!(converted.pop_with_context_exit, 1);
+        assert!(!converted.is_branch());
+        assert_eq!(converted.disassemble(0), "popenv [drop]");
+    }
+
+    #[test]
+    fn dangling_function_reference() {
+        // The data file has no functions; Underanalyzer resolves the index (or annotates it)
+        let instr = LibGMInstruction::Call {
+            function: GMRef::from(5usize),
+            argument_count: 1,
+        };
+        let converted = convert(&instr);
+        assert_eq!(converted.opcode, OPCODE_CALL);
+        assert_eq!(converted.function_index, 5);
+        assert_eq!(converted.function.name(), "");
+        assert_eq!(converted.variable_index, -1);
+        assert_eq!(converted.argument_count, 1);
+    }
+}
@@ -1,7 +1,10 @@
-use libgm::gamemaker::elements::variable::GMVariable;
+use libgm::gamemaker::elements::{code_locals::GMCodeLocal, variable::GMVariable};
 
 use crate::primitives::RustStr;
 
+/// Underanalyzer's `InstanceType.Local`.
+pub(crate) const INSTANCE_TYPE_LOCAL: i16 = -7;
+
 #[repr(C)]
 pub struct Variable<'a> {
     name: RustStr<'a>,
@@ -21,6 +24,44 @@ impl<'a> Variable<'a> {
         }
     }
 
+    #[must_use]
+    pub const fn name(&self) -> &'a str {
+        self.name.as_str()
+    }
+
+    /// Converts a variable referenced by an instruction.
+    ///
+    /// Variables of bytecode 14 and lower have no modern data (and therefore no instance type),
+    /// so the instance type of the referencing instruction is used for them instead.
+    /// Locals are named using the code entry's code locals, if available:
+    /// the variable ID of a local is its slot in the code locals.
+    #[must_use]
+    pub fn from_reference(
+        variable: &'a GMVariable,
+        instruction_instance_type: i16,
+        locals: Option<&'a GMCodeLocal>,
+    ) -> Self {
+        let mut converted = Self::from_libgm(variable);
+        let Some(modern) = &variable.modern_data else {
+            converted.instance_type = instruction_instance_type;
+            return converted;
+        };
+
+        if instruction_instance_type == INSTANCE_TYPE_LOCAL
+            && let Ok(slot) = u32::try_from(modern.variable_id)
+        {
+            let local = locals
+                .into_iter()
+                .flat_map(|locals| &locals.variables)
+                .find(|local| local.index == slot);
+            if let Some(local) = local {
+                converted.name = RustStr::from_str(&local.name);
+            }
+        }
+
+        converted
+    }
+
     #[must_use]
     pub fn from_libgm(variable: &'a GMVariable) -> Self {
         let name = RustStr::from_str(&variable.name);
@@ -32,3 +73,71 @@ impl<'a> Variable<'a> {
         Self::new(name, variable_id, instance_type)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use libgm::gamemaker::elements::{
+        code_locals::{GMCodeLocal, GMCodeLocalVariable},
+        variable::GMVariable,
+    };
+
+    use super::{INSTANCE_TYPE_LOCAL, Variable};
+
+    const INSTANCE_TYPE_SELF: i16 = -1;
+
+    fn variable(name: &str, variable_id: Option<i32>) -> GMVariable {
+        let mut variable = GMVariable {
+            name: name.to_owned(),
+            ..GMVariable::default()
+        };
+        variable.modern_data = variable_id.map(|variable_id| {
+            let mut modern = variable.modern_data.clone().unwrap_or_default();
+            modern.variable_id = variable_id;
+            modern
+        });
+        variable
+    }
+
+    fn code_locals(names: &[(u32, &str)]) -> GMCodeLocal {
+        GMCodeLocal {
+            name: "gml_Object_obj_a_Create_0".to_owned(),
+            variables: names
+                .iter()
+                .map(|&(index, name)| GMCodeLocalVariable {
+                    index,
+                    name: name.to_owned(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn legacy_variables_use_the_instruction_instance_type() {
+        let legacy = variable("hp", None);
+        let converted = Variable::from_reference(&legacy, INSTANCE_TYPE_SELF, None);
+        assert_eq!(converted.name(), "hp");
+        assert_eq!(converted.instance_type, INSTANCE_TYPE_SELF);
+
+        let converted = Variable::from_reference(&legacy, INSTANCE_TYPE_LOCAL, None);
+        assert_eq!(converted.instance_type, INSTANCE_TYPE_LOCAL);
+    }
+
+    #[test]
+    fn locals_are_named_by_their_slot() {
+        // The variable ID is the slot, which differs from the index in the variable table
+        let locals = code_locals(&[(0, "arguments"), (1, "i"), (2, "total")]);
+        let local = variable("shared_name", Some(2));
+        let converted = Variable::from_reference(&local, INSTANCE_TYPE_LOCAL, Some(&locals));
+        assert_eq!(converted.name(), "total");
+        assert_eq!(converted.variable_id, 2);
+
+        // Only locals are named by the code locals
+        let converted = Variable::from_reference(&local, INSTANCE_TYPE_SELF, Some(&locals));
+        assert_eq!(converted.name(), "shared_name");
+
+        // Without a matching slot, the name from the variable table is kept
+        let unknown = variable("j", Some(7));
+        let converted = Variable::from_reference(&unknown, INSTANCE_TYPE_LOCAL, Some(&locals));
+        assert_eq!(converted.name(), "j");
+    }
+}
@@ -0,0 +1,366 @@
+use std::{collections::HashSet, fmt::Write};
+
+/// The format decompiled code is returned in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Plain GML source code.
+    #[default]
+    Plain,
+
+    /// GML with ANSI escape sequences, for printing to terminals.
+    Ansi,
+
+    /// A standalone HTML document with a `<pre>` block.
+    /// Tokens are wrapped in `<span>`s with the classes listed in [`TokenClass::css_class`].
+    Html,
+
+    /// Underanalyzer's own CSS color markup (`UseCSSColors`).
+    CssColors,
+}
+
+/// The kind of a highlighted token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenClass {
+    Keyword,
+    Comment,
+    String,
+    Number,
+    /// Name of an asset (object, sprite, room, ...).
+    Asset,
+    BuiltinFunction,
+    BuiltinVariable,
+    /// Builtin constant, such as `c_white` or `vk_enter`.
+    Constant,
+    /// Name of an enum declared in the code, or one of its members.
+    Enum,
+}
+
+impl TokenClass {
+    /// The CSS class used for this token in [`OutputFormat::Html`].
+    #[must_use]
+    pub const fn css_class(self) -> &'static str {
+        match self {
+            Self::Keyword => "gml-keyword",
+            Self::Comment => "gml-comment",
+            Self::String => "gml-string",
+            Self::Number => "gml-number",
+            Self::Asset => "gml-asset",
+            Self::BuiltinFunction => "gml-function",
+            Self::BuiltinVariable => "gml-variable",
+            Self::Constant => "gml-constant",
+            Self::Enum => "gml-enum",
+        }
+    }
+
+    /// The SGR parameters used for this token in [`OutputFormat::Ansi`].
+    const fn ansi_style(self) -> &'static str {
+        match self {
+            Self::Keyword => "1;35",
+            Self::Comment => "90",
+            Self::String => "32",
+            Self::Number => "33",
+            Self::Asset => "36",
+            Self::BuiltinFunction => "34",
+            Self::BuiltinVariable => "31",
+            Self::Constant => "1;33",
+            Self::Enum => "1;36",
+        }
+    }
+}
+
+pub(crate) const KEYWORDS: &[&str] = &[
+    "all",
+    "and",
+    "begin",
+    "break",
+    "case",
+    "catch",
+    "constructor",
+    "continue",
+    "default",
+    "delete",
+    "div",
+    "do",
+    "else",
+    "end",
+    "enum",
+    "exit",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "global",
+    "globalvar",
+    "if",
+    "mod",
+    "new",
+    "noone",
+    "not",
+    "or",
+    "other",
+    "repeat",
+    "return",
+    "self",
+    "static",
+    "switch",
+    "then",
+    "throw",
+    "true",
+    "try",
+    "undefined",
+    "until",
+    "var",
+    "while",
+    "with",
+    "xor",
+];
+
+const HTML_STYLE: &str = "\
+pre.gml { background: #1e1e1e; color: #d4d4d4; padding: 1em; }
+.gml-keyword { color: #c586c0; font-weight: bold; }
+.gml-comment { color: #6a9955; }
+.gml-string { color: #ce9178; }
+.gml-number { color: #b5cea8; }
+.gml-asset { color: #4ec9b0; }
+.gml-function { color: #dcdcaa; }
+.gml-variable { color: #9cdcfe; }
+.gml-constant { color: #4fc1ff; }
+.gml-enum { color: #b8d7a3; }
+";
+
+/// Highlights decompiled GML.
+///
+/// `resolve` classifies identifiers that are not keywords or enums.
+/// Its second argument is whether the identifier is followed by a call.
+pub fn highlight(
+    source: &str,
+    format: OutputFormat,
+    mut resolve: impl FnMut(&str, bool) -> Option<TokenClass>,
+) -> String {
+    let enums: HashSet<&str> = enum_names(source);
+    let mut output = String::with_capacity(source.len() * 2);
+    if format == OutputFormat::Html {
+        output += "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<style>\n";
+        output += HTML_STYLE;
+        output += "</style>\n</head>\n<body>\n<pre class=\"gml\">";
+    }
+
+    let tokens: Vec<(&str, Option<TokenClass>)> = tokenize(source).collect();
+    // The last two tokens that are not whitespace or comments.
+    let mut last: [&str; 2] = [""; 2];
+    let mut in_enum_body = false;
+    for (i, &(text, class)) in tokens.iter().enumerate() {
+        let class = class.or_else(|| {
+            let first = text.chars().next()?;
+            if !(first.is_alphabetic() || first == '_') {
+                return None;
+            }
+            if KEYWORDS.contains(&text) {
+                return Some(TokenClass::Keyword);
+            }
+            let is_member = last[0] == "." && enums.contains(last[1]);
+            let is_declared_member = in_enum_body && matches!(last[0], "{" | ",");
+            if enums.contains(text) || is_member || is_declared_member {
+                return Some(TokenClass::Enum);
+            }
+            let is_call = tokens[i + 1..]
+                .iter()
+                .find(|(text, _)| !text.trim().is_empty())
+                .is_some_and(|&(text, _)| text == "(");
+            resolve(text, is_call)
+        });
+        write_token(&mut output, format, text, class);
+
+        if class == Some(TokenClass::Comment) || text.trim().is_empty() {
+            continue;
+        }
+        match text {
+            "{" if last[1] == "enum" => in_enum_body = true,
+            "}" => in_enum_body = false,
+            _ => {}
+        }
+        last = [text, last[0]];
+    }
+
+    if format == OutputFormat::Html {
+        output += "</pre>\n</body>\n</html>\n";
+    }
+    output
+}
+
+fn write_token(output: &mut String, format: OutputFormat, text: &str, class: Option<TokenClass>) {
+    match (format, class) {
+        (OutputFormat::Ansi, Some(class)) => {
+            let _ = write!(output, "\x1b[{}m{text}\x1b[0m", class.ansi_style());
+        }
+        (OutputFormat::Html, Some(class)) => {
+            let _ = write!(output, "<span class=\"{}\">", class.css_class());
+            escape_html(output, text);
+            *output += "</span>";
+        }
+        (OutputFormat::Html, None) => escape_html(output, text),
+        _ => *output += text,
+    }
+}
+
+pub(crate) fn escape_html(output: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => *output += "&amp;",
+            '<' => *output += "&lt;",
+            '>' => *output += "&gt;",
+            '"' => *output += "&quot;",
+            _ => output.push(c),
+        }
+    }
+}
+
+/// Collects the names of all enums declared in the source.
+fn enum_names(source: &str) -> HashSet<&str> {
+    let mut names = HashSet::new();
+    let mut tokens = tokenize(source)
+        .filter(|(text, class)| class.is_none() && !text.trim().is_empty())
+        .map(|(text, _)| text);
+    while let Some(text) = tokens.next() {
+        if text == "enum"
+            && let Some(name) = tokens.next()
+        {
+            names.insert(name);
+        }
+    }
+    names
+}
+
+/// Splits GML source into tokens, classifying comments, strings and numbers.
+///
+/// Concatenating all tokens yields the original source.
+/// Identifiers, whitespace and punctuation are returned unclassified.
+pub(crate) fn tokenize(source: &str) -> impl Iterator<Item = (&str, Option<TokenClass>)> {
+    let mut rest = source;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let (len, class) = if rest.starts_with("//") {
+            (
+                rest.find('\n').unwrap_or(rest.len()),
+                Some(TokenClass::Comment),
+            )
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            let len = comment.find("*/").map_or(rest.len(), |i| i + 4);
+            (len, Some(TokenClass::Comment))
+        } else if let Some(string) = rest
+            .strip_prefix('@')
+            .filter(|s| s.starts_with(['"', '\'']))
+        {
+            let quote = &string[..1];
+            let len = string[1..].find(quote).map_or(rest.len(), |i| i + 3);
+            (len, Some(TokenClass::String))
+        } else if first == '"' {
+            (string_length(rest), Some(TokenClass::String))
+        } else if first.is_ascii_digit() || (first == '.' && starts_with_digit(&rest[1..])) {
+            (number_length(rest), Some(TokenClass::Number))
+        } else if first == '$' && rest[1..].starts_with(|c: char| c.is_ascii_hexdigit()) {
+            (1 + number_length(&rest[1..]), Some(TokenClass::Number))
+        } else if first.is_alphabetic() || first == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (len, None)
+        } else if first.is_whitespace() {
+            let len = rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len());
+            (len, None)
+        } else {
+            (first.len_utf8(), None)
+        };
+        let (token, remaining) = rest.split_at(len);
+        rest = remaining;
+        Some((token, class))
+    })
+}
+
+fn starts_with_digit(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Length of a regular string literal, including both quotes.
+fn string_length(text: &str) -> usize {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return i + 1,
+            '\n' => return i,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// Length of a number literal (decimal, `0x` hex or the digits of a `$` hex literal).
+fn number_length(text: &str) -> usize {
+    let digits = text.strip_prefix("0x").unwrap_or(text);
+    let prefix = text.len() - digits.len();
+    prefix
+        + digits
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '_'))
+            .unwrap_or(digits.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OutputFormat, TokenClass, highlight, tokenize};
+
+    const SOURCE: &str = r#"enum Color { Red, Green }
+var c = Color.Red; // "comment"
+draw_sprite(spr_player, 0, x + 1.5);
+show_message(@"C:\path" + "c\"d");
+"#;
+
+    fn resolve(name: &str, is_call: bool) -> Option<TokenClass> {
+        match name {
+            "spr_player" => Some(TokenClass::Asset),
+            "draw_sprite" | "show_message" if is_call => Some(TokenClass::BuiltinFunction),
+            "x" => Some(TokenClass::BuiltinVariable),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn tokens_cover_source() {
+        let joined: String = tokenize(SOURCE).map(|(text, _)| text).collect();
+        assert_eq!(joined, SOURCE);
+    }
+
+    #[test]
+    fn plain_is_unchanged() {
+        assert_eq!(highlight(SOURCE, OutputFormat::Plain, resolve), SOURCE);
+    }
+
+    #[test]
+    fn html_classes() {
+        let html = highlight(SOURCE, OutputFormat::Html, resolve);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(r#"<span class="gml-keyword">enum</span>"#));
+        assert!(
+            html.contains(
+                r#"<span class="gml-enum">Color</span>.<span class="gml-enum">Red</span>"#
+            )
+        );
+        assert!(html.contains(r#"<span class="gml-comment">// &quot;comment&quot;</span>"#));
+        assert!(html.contains(r#"<span class="gml-function">draw_sprite</span>"#));
+        assert!(html.contains(r#"<span class="gml-asset">spr_player</span>"#));
+        assert!(html.contains(r#"<span class="gml-variable">x</span>"#));
+        assert!(html.contains(r#"<span class="gml-number">1.5</span>"#));
+        assert!(html.contains(r#"<span class="gml-enum">Green</span>"#));
+        assert!(html.contains(r#"<span class="gml-string">@&quot;C:\path&quot;</span>"#));
+        assert!(html.contains(r#"<span class="gml-string">&quot;c\&quot;d&quot;</span>"#));
+    }
+
+    #[test]
+    fn ansi_escapes() {
+        let ansi = highlight("return 1;", OutputFormat::Ansi, resolve);
+        assert_eq!(ansi, "\x1b[1;35mreturn\x1b[0m \x1b[33m1\x1b[0m;");
+    }
+}
@@ -0,0 +1,514 @@
+use std::{collections::HashSet, ops::Range};
+
+use libgm::{
+    gml::{GMCode, instruction::Instruction as LibGMInstruction},
+    prelude::*,
+};
+
+use crate::{
+    GameContext,
+    callgraph::function_ranges,
+    compiler::{CompiledCode, ScriptKind},
+    gamemaker::{Code, INSTANCE_TYPE_LOCAL, Instruction, find_parent},
+    import::{Linker, root_locals},
+};
+
+/// Size of `b`, `exit` and `ret` instructions in bytes.
+const INSTRUCTION_SIZE: u32 = 4;
+
+/// Where [`hook_code`] inserts the compiled snippet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookPosition {
+    /// Before the first instruction of the entry.
+    /// An `exit` (or `return`) in the snippet skips the original code.
+    Start,
+    /// Wherever the entry finishes: at its end and at every `exit` and `return`.
+    /// Return values stay on the stack while the snippet runs, so they are not changed.
+    End,
+}
+
+/// Compiles a GML snippet and splices its instructions into an existing code entry,
+/// without decompiling the entry (so this also works for entries that fail to decompile).
+///
+/// The entry can be a root entry (e.g. an object event) or a child entry (a GMLv2 function),
+/// in which case the snippet is spliced into the function's body.
+/// Branches, the offsets of child entries and the local count are fixed up;
+/// `exit` and `return` of the original code (outside of nested functions) branch to the snippet
+/// for [`HookPosition::End`]. Locals of the snippet share names with those of the entry.
+///
+/// The snippet is compiled like the code of an object event, so it cannot declare functions.
+///
+/// # Errors
+/// This function fails if the snippet has compile errors (returned as the error message),
+/// if it declares functions, or if the entry or compiled code cannot be converted.
+pub fn hook_code(
+    data: &mut GMData,
+    code_ref: GMRef<GMCode>,
+    source: &str,
+    position: HookPosition,
+) -> Result<()> {
+    let root_ref: GMRef<GMCode> = find_parent(code_ref, data)?.unwrap_or(code_ref);
+    let root_name: String = data.codes.by_ref(root_ref)?.name.clone();
+
+    let ctx = GameContext::new(data)?;
+    let kind = match ScriptKind::for_code_name(&root_name, ctx.capabilities().using_gmlv2) {
+        ScriptKind::GlobalScript => ScriptKind::ObjectEvent,
+        kind => kind,
+    };
+    let compiled: CompiledCode = match ctx.compile(source, &root_name, kind)? {
+        Ok(compiled) => compiled,
+        Err(messages) => {
+            return Err(libgm::Error::new(format!(
+                "The hook does not compile:\n{}",
+                messages.join("\n")
+            )));
+        }
+    };
+    drop(ctx);
+    if !compiled.functions.is_empty() {
+        return Err("Hooks cannot declare functions".into());
+    }
+
+    let layout = Layout::new(root_ref, code_ref, data)?;
+    let new_locals: Vec<&str> = root_locals(&compiled)
+        .into_iter()
+        .filter(|local| !layout.locals.contains(*local))
+        .collect();
+
+    let mut linker = Linker::new(data);
+    linker.update_code_locals(&root_name, &new_locals, true)?;
+    let snippet: Vec<LibGMInstruction> = linker.instructions(&compiled)?;
+
+    let root: &mut GMCode = data.codes.by_ref_mut(root_ref)?;
+    let original: Vec<LibGMInstruction> = std::mem::take(&mut root.instructions);
+    let (instructions, insertion) = splice(original, &layout, &snippet, compiled.length, position);
+    root.instructions = instructions;
+
+    for child_ref in GMCode::find_children(root_ref, data) {
+        let child: &mut GMCode = data.codes.by_ref_mut(child_ref)?;
+        if let Some(modern) = &mut child.modern_data {
+            let hooked_start = child_ref == code_ref && position == HookPosition::Start;
+            modern.offset = relocate_child(modern.offset, &insertion, hooked_start);
+        }
+    }
+    if let Some(modern) = &mut data.codes.by_ref_mut(code_ref)?.modern_data {
+        let added: u16 = u16::try_from(new_locals.len()).unwrap_or(u16::MAX);
+        modern.locals_count = modern.locals_count.saturating_add(added);
+    }
+    Ok(())
+}
+
+/// Addresses in the root entry that the splice depends on.
+struct Layout {
+    /// Address of every instruction of the root entry.
+    addresses: Vec<u32>,
+    length: u32,
+    /// Address range of the hooked entry.
+    region: Range<u32>,
+    /// Bodies of functions nested in the hooked entry, whose `exit` and `return` are left alone.
+    nested: Vec<Range<u32>>,
+    /// Names of the locals the hooked entry uses.
+    locals: HashSet<String>,
+}
+
+impl Layout {
+    fn new(root_ref: GMRef<GMCode>, code_ref: GMRef<GMCode>, data: &GMData) -> Result<Self> {
+        let code = Code::try_from_libgm(root_ref, data)?;
+        let instructions: Vec<(u32, &Instruction)> = code.addressed_instructions().collect();
+        let root: &GMCode = data.codes.by_ref(root_ref)?;
+        let length: u32 = root.length();
+        let functions = function_ranges(root_ref, data, &instructions, length);
+
+        let region: Range<u32> = if code_ref == root_ref {
+            0..length
+        } else {
+            functions
+                .iter()
+                .find(|(child_ref, _)| *child_ref == code_ref)
+                .map(|(_, range)| range.clone())
+                .ok_or("Could not find the body of the function in its root entry")?
+        };
+        let nested: Vec<Range<u32>> = functions
+            .into_iter()
+            .filter(|(child_ref, range)| *child_ref != code_ref && region.contains(&range.start))
+            .map(|(_, range)| range)
+            .collect();
+
+        let addresses: Vec<u32> = instructions.iter().map(|&(address, _)| address).collect();
+        let mut locals: HashSet<String> = HashSet::new();
+        for (instr, &address) in root.instructions.iter().zip(&addresses) {
+            if let Some(code_var) = instr.variable()
+                && code_var.instance_type.build() == INSTANCE_TYPE_LOCAL
+                && region.contains(&address)
+                && let Ok(variable) = data.variables.by_ref(code_var.variable)
+            {
+                locals.insert(variable.name.clone());
+            }
+        }
+
+        Ok(Self {
+            addresses,
+            length,
+            region,
+            nested,
+            locals,
+        })
+    }
+
+    /// Whether an instruction belongs to the hooked entry itself, not to a nested function.
+    fn in_body(&self, address: u32) -> bool {
+        self.region.contains(&address) && !self.nested.iter().any(|range| range.contains(&address))
+    }
+}
+
+/// Inserts the snippet into the root entry's instructions and fixes up all branches.
+/// Returns the new instructions and the (new) address range of the inserted instructions.
+fn splice(
+    original: Vec<LibGMInstruction>,
+    layout: &Layout,
+    snippet: &[LibGMInstruction],
+    snippet_length: u32,
+    position: HookPosition,
+) -> (Vec<LibGMInstruction>, Range<u32>) {
+    let is_return = |(instr, &address): (&LibGMInstruction, &u32)| {
+        *instr == LibGMInstruction::Return && layout.in_body(address)
+    };
+    let has_returns =
+        position == HookPosition::End && original.iter().zip(&layout.addresses).any(is_return);
+
+    // For `End`, the snippet runs at the end of the body and after every `exit` (then exits),
+    // and again (followed by `ret`) after every `return`, with the return value on the stack.
+    let mut inserted: Vec<LibGMInstruction> = snippet.to_vec();
+    let (at, exit_target, return_target) = match position {
+        HookPosition::Start => (layout.region.start, 0, 0),
+        HookPosition::End => {
+            inserted.push(LibGMInstruction::Exit);
+            if has_returns {
+                inserted.extend_from_slice(snippet);
+                inserted.push(LibGMInstruction::Return);
+            }
+            let at = layout.region.end;
+            (at, at, at + snippet_length + INSTRUCTION_SIZE)
+        }
+    };
+    let inserted_length: u32 = match (position, has_returns) {
+        (HookPosition::Start, _) => snippet_length,
+        (HookPosition::End, false) => snippet_length + INSTRUCTION_SIZE,
+        (HookPosition::End, true) => 2 * (snippet_length + INSTRUCTION_SIZE),
+    };
+    let relocate = |address: u32| {
+        if address >= at {
+            address + inserted_length
+        } else {
+            address
+        }
+    };
+
+    let mut instructions: Vec<LibGMInstruction> =
+        Vec::with_capacity(original.len() + inserted.len());
+    let mut inserted = Some(inserted);
+    for (mut instr, &address) in original.into_iter().zip(&layout.addresses) {
+        if address >= at
+            && let Some(inserted) = inserted.take()
+        {
+            instructions.extend(inserted);
+        }
+        let new_address: u32 = relocate(address);
+        let hooked_end = position == HookPosition::End && layout.in_body(address);
+        let target: Option<u32> = match instr {
+            LibGMInstruction::Exit if hooked_end => {
+                instr = LibGMInstruction::Branch { jump_offset: 0 };
+                Some(exit_target)
+            }
+            LibGMInstruction::Return if hooked_end => {
+                instr = LibGMInstruction::Branch { jump_offset: 0 };
+                Some(return_target)
+            }
+            _ => instr.jump_offset().map(|offset| {
+                let old_target = address.wrapping_add_signed(4 * offset);
+                // Branches to the end of the hooked entry run the snippet first.
+                if position == HookPosition::End
+                    && old_target == at
+                    && layout.region.contains(&address)
+                {
+                    exit_target
+                } else {
+                    relocate(old_target)
+                }
+            }),
+        };
+        if let Some(target) = target {
+            let offset = (i64::from(target) - i64::from(new_address)) / 4;
+            set_jump_offset(&mut instr, offset as i32);
+        }
+        instructions.push(instr);
+    }
+    if let Some(inserted) = inserted {
+        instructions.extend(inserted);
+    }
+    debug_assert!(at <= layout.length);
+    (instructions, at..at + inserted_length)
+}
+
+/// The offset of a child entry after inserting instructions at the given range.
+/// A snippet inserted at the start of the hooked function becomes part of it,
+/// so the function keeps its offset.
+fn relocate_child(offset: u32, insertion: &Range<u32>, hooked_start: bool) -> u32 {
+    if offset >= insertion.start && !hooked_start {
+        offset + (insertion.end - insertion.start)
+    } else {
+        offset
+    }
+}
+
+fn set_jump_offset(instr: &mut LibGMInstruction, offset: i32) {
+    match instr {
+        LibGMInstruction::Branch { jump_offset }
+        | LibGMInstruction::BranchIf { jump_offset }
+        | LibGMInstruction::BranchUnless { jump_offset }
+        | LibGMInstruction::PushWithContext { jump_offset }
+        | LibGMInstruction::PopWithContext { jump_offset } => *jump_offset = offset,
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use libgm::{
+        gml::{GMCode, instruction::Instruction as LibGMInstruction},
+        prelude::*,
+    };
+
+    use super::{HookPosition, Layout, relocate_child, splice};
+    use crate::gamemaker::find_parent;
+
+    /// `0: bt 8`, `4: exit`, `8: ret`
+    fn original() -> Vec<LibGMInstruction> {
+        vec![
+            LibGMInstruction::BranchIf { jump_offset: 2 },
+            LibGMInstruction::Exit,
+            LibGMInstruction::Return,
+        ]
+    }
+
+    fn layout(addresses: Vec<u32>, length: u32) -> Layout {
+        Layout {
+            addresses,
+            length,
+            region: 0..length,
+            nested: Vec::new(),
+            locals: HashSet::new(),
+        }
+    }
+
+    /// A snippet that branches to its own end.
+    const SNIPPET: [LibGMInstruction; 1] = [LibGMInstruction::Branch { jump_offset: 1 }];
+
+    #[test]
+    fn hooks_start() {
+        let (instructions, inserted) = splice(
+            original(),
+            &layout(vec![0, 4, 8], 12),
+            &SNIPPET,
+            4,
+            HookPosition::Start,
+        );
+        assert_eq!(inserted, 0..4);
+        assert_eq!(instructions[0], SNIPPET[0]);
+        assert_eq!(instructions[1..], original()[..]);
+    }
+
+    #[test]
+    fn hooks_exit_and_return() {
+        let (instructions, inserted) = splice(
+            original(),
+            &layout(vec![0, 4, 8], 12),
+            &SNIPPET,
+            4,
+            HookPosition::End,
+        );
+        assert_eq!(inserted, 12..28);
+        let expected = [
+            LibGMInstruction::BranchIf { jump_offset: 2 },
+            // exit -> snippet, then exit
+            LibGMInstruction::Branch { jump_offset: 2 },
+            // ret -> snippet, then ret
+            LibGMInstruction::Branch { jump_offset: 3 },
+            SNIPPET[0].clone(),
+            LibGMInstruction::Exit,
+            SNIPPET[0].clone(),
+            LibGMInstruction::Return,
+        ];
+        assert_eq!(instructions, expected);
+    }
+
+    #[test]
+    fn branches_to_end_run_hook() {
+        // `0: bt 8`, `4: exit`; the branch to the end must reach the snippet,
+        // and must skip it for a start hook.
+        let original = || {
+            vec![
+                LibGMInstruction::BranchIf { jump_offset: 2 },
+                LibGMInstruction::Exit,
+            ]
+        };
+        let (instructions, _) = splice(
+            original(),
+            &layout(vec![0, 4], 8),
+            &SNIPPET,
+            4,
+            HookPosition::End,
+        );
+        assert_eq!(
+            instructions[0],
+            LibGMInstruction::BranchIf { jump_offset: 2 }
+        );
+        assert_eq!(instructions[1], LibGMInstruction::Branch { jump_offset: 1 });
+
+        let (instructions, _) = splice(
+            original(),
+            &layout(vec![0, 4], 8),
+            &SNIPPET,
+            4,
+            HookPosition::Start,
+        );
+        assert_eq!(
+            instructions[1],
+            LibGMInstruction::BranchIf { jump_offset: 2 }
+        );
+    }
+
+    /// A root entry with three functions, `f` (with `g` nested in it) and `h`:
+    /// `0: b 24`, f: `4: bt 12`, `8: exit`, `12: b 20`, g: `16: exit`, f: `20: ret`,
+    /// `24: b 32`, h: `28: exit`, root: `32: exit`.
+    fn functions() -> Vec<LibGMInstruction> {
+        vec![
+            LibGMInstruction::Branch { jump_offset: 6 },
+            LibGMInstruction::BranchIf { jump_offset: 2 },
+            LibGMInstruction::Exit,
+            LibGMInstruction::Branch { jump_offset: 2 },
+            LibGMInstruction::Exit,
+            LibGMInstruction::Return,
+            LibGMInstruction::Branch { jump_offset: 2 },
+            LibGMInstruction::Exit,
+            LibGMInstruction::Exit,
+        ]
+    }
+
+    /// Layout for hooking `f`.
+    fn function_layout() -> Layout {
+        Layout {
+            addresses: (0..9).map(|i| 4 * i).collect(),
+            length: 36,
+            region: 4..24,
+            nested: vec![16..20],
+            locals: HashSet::new(),
+        }
+    }
+
+    #[test]
+    fn hooks_end_of_child_function() {
+        let (instructions, inserted) = splice(
+            functions(),
+            &function_layout(),
+            &SNIPPET,
+            4,
+            HookPosition::End,
+        );
+        assert_eq!(inserted, 24..40);
+        let expected = [
+            // Jumps over `f`, now including the inserted instructions.
+            LibGMInstruction::Branch { jump_offset: 10 },
+            LibGMInstruction::BranchIf { jump_offset: 2 },
+            // exit -> snippet, then exit
+            LibGMInstruction::Branch { jump_offset: 4 },
+            LibGMInstruction::Branch { jump_offset: 2 },
+            // The nested function's exit is left alone.
+            LibGMInstruction::Exit,
+            // ret -> snippet, then ret
+            LibGMInstruction::Branch { jump_offset: 3 },
+            SNIPPET[0].clone(),
+            LibGMInstruction::Exit,
+            SNIPPET[0].clone(),
+            LibGMInstruction::Return,
+            LibGMInstruction::Branch { jump_offset: 2 },
+            LibGMInstruction::Exit,
+            LibGMInstruction::Exit,
+        ];
+        assert_eq!(instructions, expected);
+
+        // `f` and the nested `g` start before the insertion, `h` after it.
+        assert_eq!(relocate_child(4, &inserted, false), 4);
+        assert_eq!(relocate_child(16, &inserted, false), 16);
+        assert_eq!(relocate_child(28, &inserted, false), 44);
+    }
+
+    #[test]
+    fn hooks_start_of_child_function() {
+        let (instructions, inserted) = splice(
+            functions(),
+            &function_layout(),
+            &SNIPPET,
+            4,
+            HookPosition::Start,
+        );
+        assert_eq!(inserted, 4..8);
+        assert_eq!(instructions[0], LibGMInstruction::Branch { jump_offset: 7 });
+        assert_eq!(instructions[1], SNIPPET[0]);
+        assert_eq!(instructions[2..], functions()[1..]);
+
+        // The snippet runs as part of `f`, so only the functions after it move.
+        assert_eq!(relocate_child(4, &inserted, true), 4);
+        assert_eq!(relocate_child(16, &inserted, false), 20);
+        assert_eq!(relocate_child(28, &inserted, false), 32);
+    }
+
+    fn code(name: &str, parent: Option<usize>, offset: u32) -> GMCode {
+        let mut code = GMCode {
+            name: name.to_owned(),
+            ..GMCode::default()
+        };
+        let mut modern = code.modern_data.take().unwrap_or_default();
+        modern.parent = parent.map(GMRef::from);
+        modern.offset = offset;
+        code.modern_data = Some(modern);
+        code
+    }
+
+    #[test]
+    fn hooks_global_script_function() {
+        // `gml_Script_f` has no root suffix: `0: b 8`, f: `4: exit`, root: `8: exit`.
+        let original = || {
+            vec![
+                LibGMInstruction::Branch { jump_offset: 2 },
+                LibGMInstruction::Exit,
+                LibGMInstruction::Exit,
+            ]
+        };
+        let mut data = GMData::default();
+        data.general_info.wad_version = 17;
+        let mut root = code("gml_GlobalScript_scr_a", None, 0);
+        root.instructions = original();
+        data.codes.push(root);
+        data.codes.push(code("gml_Script_f", Some(0), 4));
+
+        let code_ref: GMRef<GMCode> = GMRef::from(1);
+        let root_ref = find_parent(code_ref, &data).unwrap().unwrap();
+        assert_eq!(root_ref, GMRef::from(0));
+        let layout = Layout::new(root_ref, code_ref, &data).unwrap();
+        assert_eq!(layout.region, 4..8);
+
+        let (instructions, inserted) = splice(original(), &layout, &SNIPPET, 4, HookPosition::End);
+        assert_eq!(inserted, 8..16);
+        let expected = [
+            LibGMInstruction::Branch { jump_offset: 4 },
+            LibGMInstruction::Branch { jump_offset: 1 },
+            SNIPPET[0].clone(),
+            LibGMInstruction::Exit,
+            LibGMInstruction::Exit,
+        ];
+        assert_eq!(instructions, expected);
+    }
+}
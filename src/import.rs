@@ -0,0 +1,1046 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use libgm::{
+    error::Context,
+    gamemaker::elements::{
+        code_locals::GMCodeLocalVariable, function::GMFunction, script::GMScript,
+        variable::GMVariable,
+    },
+    gml::{
+        GMCode,
+        instruction::{
+            AssetReference, CodeVariable, ComparisonType, DataType, InstanceType,
+            Instruction as LibGMInstruction, PushValue, VariableType,
+        },
+    },
+    prelude::*,
+};
+
+use crate::{
+    GameContext,
+    compiler::{CompiledCode, CompiledFunction, CompiledInstruction, CompiledVariable, ScriptKind},
+    gamemaker::{
+        INSTANCE_TYPE_LOCAL, OPCODE_ADD, OPCODE_AND, OPCODE_BRANCH, OPCODE_BRANCH_FALSE,
+        OPCODE_BRANCH_TRUE, OPCODE_CALL, OPCODE_CALL_VARIABLE, OPCODE_CMP, OPCODE_CONV, OPCODE_DIV,
+        OPCODE_DUP, OPCODE_EXIT, OPCODE_EXTENDED, OPCODE_MOD, OPCODE_MUL, OPCODE_NEG, OPCODE_NOT,
+        OPCODE_OR, OPCODE_POP, OPCODE_POP_ENV, OPCODE_POPZ, OPCODE_PUSH, OPCODE_PUSH_BUILTIN,
+        OPCODE_PUSH_ENV, OPCODE_PUSH_GLOBAL, OPCODE_PUSH_IMMEDIATE, OPCODE_PUSH_LOCAL, OPCODE_REM,
+        OPCODE_RETURN, OPCODE_SHL, OPCODE_SHR, OPCODE_SUB, OPCODE_XOR, TYPE_BOOL, TYPE_DOUBLE,
+        TYPE_INT16, TYPE_INT32, TYPE_INT64, TYPE_STRING, TYPE_VARIABLE, child_function_name,
+        find_parent,
+    },
+};
+
+/// Result of [`import_directory`].
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// Existing code entries whose instructions were replaced.
+    pub replaced: Vec<String>,
+    /// Code entries created for files without a matching entry.
+    pub created: Vec<String>,
+    /// Number of sources identical to the current decompilation, which were skipped.
+    pub unchanged: usize,
+    /// Compiler error messages (and other reasons a file cannot be imported) by code entry name.
+    /// If there are any, the data was not modified.
+    pub errors: Vec<(String, Vec<String>)>,
+}
+
+/// Compiles the changed `.gml` files of a directory and links them into the data file.
+///
+/// Each file is named after its root code entry (`gml_Object_obj_player_Step_0.gml`),
+/// just like the entries in a decompilation dump. Files identical to the current decompilation
+/// are skipped. Files without a matching entry create a new code entry, and a script asset
+/// if they are named `gml_GlobalScript_<name>` or `gml_Script_<name>`. New object events
+/// (`gml_Object_*`) and room creation code (`gml_RoomCC_*`) are reported as errors instead,
+/// since nothing would run them; use [`crate::add_object_event`] and
+/// [`crate::add_room_creation_code`] for those.
+/// GMLv2 functions declared in the code replace or create child entries,
+/// and global functions of new scripts get a script asset as well.
+/// Functions cannot be removed, since other code may still call them.
+///
+/// The data is only modified if every file compiles; otherwise the compiler's messages
+/// (which include line and column) are returned in [`ImportReport::errors`].
+///
+/// # Errors
+/// This function fails if the directory cannot be read, if the compiler crashes,
+/// or if compiled code cannot be linked into the data file.
+/// In the last case, the data may be partially modified.
+pub fn import_directory(data: &mut GMData, dir: impl AsRef<Path>) -> Result<ImportReport> {
+    let mut sources: Vec<(String, String)> = Vec::new();
+    for (name, path) in gml_files(dir.as_ref())? {
+        let source: String = fs::read_to_string(&path)
+            .map_err(|e| libgm::Error::new(format!("reading {}: {e}", path.display())))?;
+        sources.push((name, source));
+    }
+    import_sources(data, sources)
+}
+
+/// Compiles GML sources by root code entry name and links them into the data file,
+/// as described in [`import_directory`].
+pub(crate) fn import_sources(
+    data: &mut GMData,
+    sources: Vec<(String, String)>,
+) -> Result<ImportReport> {
+    let mut report = ImportReport::default();
+    let mut importable: Vec<(String, String)> = Vec::with_capacity(sources.len());
+    for (name, source) in sources {
+        match missing_owner(&name) {
+            Some(message) if code_by_name(data, &name).is_none() => {
+                report.errors.push((name, vec![message.to_owned()]));
+            }
+            _ => importable.push((name, source)),
+        }
+    }
+    compile_and_link(data, importable, report)
+}
+
+/// Like [`import_sources`], but also creates entries that need an owner
+/// (which the caller has to attach them to), and adds to an existing report.
+pub(crate) fn compile_and_link(
+    data: &mut GMData,
+    sources: Vec<(String, String)>,
+    mut report: ImportReport,
+) -> Result<ImportReport> {
+    let mut compiled: Vec<(String, CompiledCode)> = Vec::new();
+
+    let ctx = GameContext::new(data)?;
+    let using_gmlv2: bool = ctx.capabilities().using_gmlv2;
+    for (name, source) in sources {
+        if let Some(code_ref) = code_by_name(data, &name) {
+            if find_parent(code_ref, data)?.is_some() {
+                let message = "this is a child entry; edit its root entry instead".to_owned();
+                report.errors.push((name, vec![message]));
+                continue;
+            }
+            let current = ctx.decompile_or_disassemble(code_ref, data);
+            if normalize(current.output()) == normalize(&source) {
+                report.unchanged += 1;
+                continue;
+            }
+        }
+        let kind = ScriptKind::for_code_name(&name, using_gmlv2);
+        match ctx
+            .compile(&source, &name, kind)
+            .with_context(|| format!("compiling {name:?}"))?
+        {
+            Ok(code) => {
+                let removed: Vec<String> = removed_functions(data, &name, &code);
+                if removed.is_empty() {
+                    compiled.push((name, code));
+                } else {
+                    let message = format!(
+                        "functions cannot be removed from existing entries (removed: {})",
+                        removed.join(", ")
+                    );
+                    report.errors.push((name, vec![message]));
+                }
+            }
+            Err(messages) => report.errors.push((name, messages)),
+        }
+    }
+    drop(ctx);
+
+    if !report.errors.is_empty() {
+        return Ok(report);
+    }
+    let mut linker = Linker::new(data);
+    for (name, code) in &compiled {
+        let replaced = linker
+            .link(name, code)
+            .with_context(|| format!("linking compiled code of {name:?}"))?;
+        if replaced {
+            report.replaced.push(name.clone());
+        } else {
+            report.created.push(name.clone());
+        }
+    }
+    Ok(report)
+}
+
+/// Why a new entry of this name cannot be created by importing it:
+/// object events and room creation code only run once an object or room refers to them.
+fn missing_owner(name: &str) -> Option<&'static str> {
+    if name.starts_with("gml_Object_") {
+        Some("the object has no such event; add it with `add_object_event` instead")
+    } else if name.starts_with("gml_RoomCC_") {
+        Some("the room has no creation code; add it with `add_room_creation_code` instead")
+    } else {
+        None
+    }
+}
+
+/// The `.gml` files of a directory with their code entry name, sorted by name.
+fn gml_files(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| libgm::Error::new(format!("reading directory {}: {e}", dir.display())))?;
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    for entry in entries {
+        let path: PathBuf = entry
+            .map_err(|e| libgm::Error::new(format!("reading directory {}: {e}", dir.display())))?
+            .path();
+        if path.extension().is_some_and(|ext| ext == "gml")
+            && let Some(name) = path.file_stem().and_then(|stem| stem.to_str())
+        {
+            files.push((name.to_owned(), path.clone()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Ignores line endings and trailing whitespace, which editors like to change.
+fn normalize(source: &str) -> String {
+    source.replace("\r\n", "\n").trim_end().to_owned()
+}
+
+fn code_by_name(data: &GMData, name: &str) -> Option<GMRef<GMCode>> {
+    data.codes
+        .elements()
+        .iter()
+        .position(|code| code.name == name)
+        .map(GMRef::from)
+}
+
+/// Name of the child entry of a GMLv2 function.
+/// Global functions (declared in global scripts) are named `gml_Script_<function>`,
+/// all others `gml_Script_<function>_<root>`.
+fn child_code_name(function: &str, root_name: &str) -> String {
+    if root_name.starts_with("gml_GlobalScript_") {
+        format!("gml_Script_{function}")
+    } else {
+        format!("gml_Script_{function}_{root_name}")
+    }
+}
+
+/// Child entries of an existing root entry whose function the compiled code no longer declares.
+fn removed_functions(data: &GMData, root_name: &str, code: &CompiledCode) -> Vec<String> {
+    let Some(root_ref) = code_by_name(data, root_name) else {
+        return Vec::new();
+    };
+    let declared: HashSet<String> = code
+        .functions
+        .iter()
+        .map(|function| child_code_name(child_function_name(&function.name, root_name), root_name))
+        .collect();
+    GMCode::find_children(root_ref, data)
+        .into_iter()
+        .filter_map(|child_ref| data.codes.by_ref(child_ref).ok())
+        .map(|child| child.name.clone())
+        .filter(|name| !declared.contains(name))
+        .collect()
+}
+
+/// Byte ranges of the function bodies in compiled code.
+/// Every body is jumped over by the branch instruction right before it.
+fn function_ranges(code: &CompiledCode) -> Vec<(u32, u32)> {
+    code.functions
+        .iter()
+        .map(|function| {
+            let end = code
+                .instructions
+                .iter()
+                .take_while(|instr| instr.address < function.offset)
+                .last()
+                .and_then(|branch| branch.address.checked_add_signed(branch.branch_offset))
+                .unwrap_or(code.length);
+            (function.offset, end)
+        })
+        .collect()
+}
+
+/// Names of the local variables used by the root of the compiled code (outside any function),
+/// in order of first use.
+pub(crate) fn root_locals(code: &CompiledCode) -> Vec<&str> {
+    let ranges = function_ranges(code);
+    let mut seen: HashSet<&str> = HashSet::new();
+    code.instructions
+        .iter()
+        .filter(|instr| {
+            !ranges
+                .iter()
+                .any(|&(start, end)| (start..end).contains(&instr.address))
+        })
+        .filter_map(|instr| instr.variable.as_ref())
+        .filter(|variable| variable.instance_type == INSTANCE_TYPE_LOCAL)
+        .map(|variable| variable.name.as_str())
+        .filter(|name| seen.insert(*name))
+        .collect()
+}
+
+/// Variable ID of builtin variables in GMLv2 games.
+const BUILTIN_VARIABLE_ID: i32 = -6;
+
+/// Links compiled code into a data file, creating the variables, functions,
+/// code entries and scripts it needs.
+pub(crate) struct Linker<'d> {
+    data: &'d mut GMData,
+    /// Whether variables have an instance type and ID (bytecode 15+).
+    modern: bool,
+    /// By name and instance type (always 0 for older games).
+    variables: HashMap<(String, i16), GMRef<GMVariable>>,
+    functions: HashMap<String, GMRef<GMFunction>>,
+    /// Slots of the locals of the entry being linked, by name (see [`Self::update_code_locals`]).
+    local_slots: HashMap<String, u32>,
+}
+
+impl<'d> Linker<'d> {
+    pub(crate) fn new(data: &'d mut GMData) -> Self {
+        let modern = data
+            .variables
+            .elements()
+            .iter()
+            .any(|variable| variable.modern_data.is_some());
+        let mut variables = HashMap::new();
+        for (i, variable) in data.variables.elements().iter().enumerate() {
+            let instance_type = variable
+                .modern_data
+                .as_ref()
+                .map_or(0, |modern| modern.instance_type.build());
+            variables
+                .entry((variable.name.clone(), instance_type))
+                .or_insert_with(|| GMRef::from(i));
+        }
+        let mut functions = HashMap::new();
+        for (i, function) in data.functions.elements().iter().enumerate() {
+            functions
+                .entry(function.name.clone())
+                .or_insert_with(|| GMRef::from(i));
+        }
+        Self {
+            data,
+            modern,
+            variables,
+            functions,
+            local_slots: HashMap::new(),
+        }
+    }
+
+    /// Converts the instructions of compiled code that declares no functions.
+    pub(crate) fn instructions(&mut self, code: &CompiledCode) -> Result<Vec<LibGMInstruction>> {
+        let no_functions = HashMap::new();
+        code.instructions
+            .iter()
+            .map(|instr| self.instruction(instr, &no_functions))
+            .collect()
+    }
+
+    /// Replaces the instructions of the named root entry, or creates it.
+    /// Returns whether the entry existed.
+    fn link(&mut self, name: &str, code: &CompiledCode) -> Result<bool> {
+        let existing: Option<GMRef<GMCode>> = code_by_name(self.data, name);
+        let root_ref: GMRef<GMCode> = match existing {
+            Some(root_ref) => root_ref,
+            None => self.create_code(name, None)?,
+        };
+
+        // Declared functions are referenced by the name the compiler gave them
+        // (or their plain name), but their entries follow GameMaker's naming.
+        let mut child_names: HashMap<&str, String> = HashMap::new();
+        for function in &code.functions {
+            let function_name = child_function_name(&function.name, name);
+            let child_name = child_code_name(function_name, name);
+            child_names.insert(&function.name, child_name.clone());
+            child_names.insert(function_name, child_name);
+        }
+
+        // The locals have to be in the code locals before the instructions referencing them.
+        let locals = root_locals(code);
+        self.update_code_locals(name, &locals, false)?;
+        let instructions = code
+            .instructions
+            .iter()
+            .map(|instr| self.instruction(instr, &child_names))
+            .collect::<Result<Vec<_>>>()?;
+        let root: &mut GMCode = self.data.codes.by_ref_mut(root_ref)?;
+        root.instructions = instructions;
+        if let Some(modern) = &mut root.modern_data {
+            modern.locals_count = int(locals.len(), "local count")?;
+        }
+
+        for function in &code.functions {
+            self.link_function(root_ref, name, function, existing.is_none())?;
+        }
+        if existing.is_none()
+            && let Some(script) = name
+                .strip_prefix("gml_GlobalScript_")
+                .or_else(|| name.strip_prefix("gml_Script_"))
+        {
+            self.add_script(script, root_ref);
+        }
+        Ok(existing.is_some())
+    }
+
+    /// Points the child entry of a declared function at its new offset, or creates it.
+    fn link_function(
+        &mut self,
+        root_ref: GMRef<GMCode>,
+        root_name: &str,
+        function: &CompiledFunction,
+        new_root: bool,
+    ) -> Result<()> {
+        let function_name = child_function_name(&function.name, root_name);
+        let child_name = child_code_name(function_name, root_name);
+        let child_ref = match code_by_name(self.data, &child_name) {
+            Some(child_ref) => child_ref,
+            None => {
+                let child_ref = self.create_code(&child_name, Some(root_ref))?;
+                if new_root && root_name.starts_with("gml_GlobalScript_") {
+                    self.add_script(function_name, child_ref);
+                }
+                child_ref
+            }
+        };
+        let child: &mut GMCode = self.data.codes.by_ref_mut(child_ref)?;
+        if let Some(modern) = &mut child.modern_data {
+            modern.offset = int(function.offset, "function offset")?;
+            modern.arguments_count = int(function.arguments, "argument count")?;
+            modern.locals_count = int(function.locals, "local count")?;
+        }
+        Ok(())
+    }
+
+    /// Creates an empty code entry, modelled after the first existing root entry.
+    fn create_code(&mut self, name: &str, parent: Option<GMRef<GMCode>>) -> Result<GMRef<GMCode>> {
+        let template = self
+            .data
+            .codes
+            .elements()
+            .iter()
+            .find(|code| code.is_root())
+            .ok_or("The data file has no code entry to model a new one after")?;
+        let mut code: GMCode = template.clone();
+        code.name = name.to_owned();
+        code.instructions = Vec::new();
+        if let Some(modern) = &mut code.modern_data {
+            modern.parent = parent;
+            modern.offset = 0;
+            modern.arguments_count = 0;
+            modern.locals_count = 0;
+        }
+        let code_ref = GMRef::from(self.data.codes.len());
+        self.data.codes.push(code);
+        Ok(code_ref)
+    }
+
+    /// Adds a script asset for a code entry, unless a script with that name exists.
+    fn add_script(&mut self, name: &str, code: GMRef<GMCode>) {
+        let scripts = self.data.scripts.elements();
+        if scripts.iter().any(|script| script.name == name) {
+            return;
+        }
+        self.data.scripts.push(GMScript {
+            name: name.to_owned(),
+            code: Some(code),
+            ..GMScript::default()
+        });
+    }
+
+    /// Replaces (or creates) the code locals of an entry, which name the local variables of every
+    /// entry in bytecode 15+ games before GMS 2.3. Other games have an empty `FUNC` chunk,
+    /// in which case nothing is done.
+    /// With `keep_existing`, the given locals are added to the existing ones instead.
+    ///
+    /// The ID of a local variable is its slot in the code locals, so until the next call,
+    /// locals of the entry are linked to the variable with their name and slot as ID.
+    pub(crate) fn update_code_locals(
+        &mut self,
+        name: &str,
+        locals: &[&str],
+        keep_existing: bool,
+    ) -> Result<()> {
+        self.local_slots.clear();
+        let Some(template) = self.data.code_locals.elements().first() else {
+            return Ok(());
+        };
+        let template = template.clone();
+        let existing: Option<usize> = self
+            .data
+            .code_locals
+            .elements()
+            .iter()
+            .position(|locals| locals.name == name);
+        let mut variables: Vec<GMCodeLocalVariable> = match existing {
+            Some(index) if keep_existing => {
+                self.data.code_locals.elements()[index].variables.clone()
+            }
+            _ => vec![GMCodeLocalVariable {
+                index: 0,
+                name: "arguments".to_owned(),
+            }],
+        };
+        for &local in locals {
+            if variables.iter().any(|variable| variable.name == local) {
+                continue;
+            }
+            let slot: u32 = variables
+                .iter()
+                .map(|variable| variable.index + 1)
+                .max()
+                .unwrap_or(0);
+            variables.push(GMCodeLocalVariable {
+                index: slot,
+                name: local.to_owned(),
+            });
+        }
+        if self.modern {
+            for variable in &variables {
+                self.local_slots
+                    .insert(variable.name.clone(), variable.index);
+            }
+        }
+
+        match existing {
+            Some(index) => {
+                self.data
+                    .code_locals
+                    .by_ref_mut(GMRef::from(index))?
+                    .variables = variables;
+            }
+            None => {
+                let mut code_locals = template;
+                code_locals.name = name.to_owned();
+                code_locals.variables = variables;
+                self.data.code_locals.push(code_locals);
+            }
+        }
+        Ok(())
+    }
+
+    fn variable(&mut self, variable: &CompiledVariable) -> Result<GMRef<GMVariable>> {
+        let instance_type: i16 = if self.modern {
+            variable.instance_type
+        } else {
+            0
+        };
+        if instance_type == INSTANCE_TYPE_LOCAL
+            && let Some(&slot) = self.local_slots.get(&variable.name)
+        {
+            return self.local_variable(&variable.name, slot);
+        }
+        let key = (variable.name.clone(), instance_type);
+        if let Some(&variable_ref) = self.variables.get(&key) {
+            return Ok(variable_ref);
+        }
+
+        let mut new_variable = GMVariable {
+            name: variable.name.clone(),
+            ..GMVariable::default()
+        };
+        if self.modern {
+            let variable_id: i32 = if variable.builtin {
+                BUILTIN_VARIABLE_ID
+            } else {
+                self.next_variable_id(instance_type)
+            };
+            let mut modern = new_variable.modern_data.take().unwrap_or_default();
+            modern.instance_type = parse_instance_type(instance_type)?;
+            modern.variable_id = variable_id;
+            new_variable.modern_data = Some(modern);
+        }
+        let variable_ref = GMRef::from(self.data.variables.len());
+        self.data.variables.push(new_variable);
+        self.variables.insert(key, variable_ref);
+        Ok(variable_ref)
+    }
+
+    /// The local variable with the given name and slot as ID, created if needed.
+    fn local_variable(&mut self, name: &str, slot: u32) -> Result<GMRef<GMVariable>> {
+        let variable_id: i32 = int(slot, "local slot")?;
+        let position = self.data.variables.elements().iter().position(|variable| {
+            variable.name == name
+                && variable.modern_data.as_ref().is_some_and(|modern| {
+                    modern.instance_type.build() == INSTANCE_TYPE_LOCAL
+                        && modern.variable_id == variable_id
+                })
+        });
+        if let Some(index) = position {
+            return Ok(GMRef::from(index));
+        }
+
+        let mut new_variable = GMVariable {
+            name: name.to_owned(),
+            ..GMVariable::default()
+        };
+        let mut modern = new_variable.modern_data.take().unwrap_or_default();
+        modern.instance_type = parse_instance_type(INSTANCE_TYPE_LOCAL)?;
+        modern.variable_id = variable_id;
+        new_variable.modern_data = Some(modern);
+        let variable_ref = GMRef::from(self.data.variables.len());
+        self.data.variables.push(new_variable);
+        Ok(variable_ref)
+    }
+
+    /// The next free ID among the variables of an instance type.
+    fn next_variable_id(&self, instance_type: i16) -> i32 {
+        self.data
+            .variables
+            .elements()
+            .iter()
+            .filter_map(|variable| variable.modern_data.as_ref())
+            .filter(|modern| modern.instance_type.build() == instance_type)
+            .map(|modern| modern.variable_id + 1)
+            .max()
+            .unwrap_or(0)
+            .max(0)
+    }
+
+    fn function(&mut self, name: &str) -> GMRef<GMFunction> {
+        if let Some(&function_ref) = self.functions.get(name) {
+            return function_ref;
+        }
+        let function_ref = GMRef::from(self.data.functions.len());
+        self.data.functions.push(GMFunction {
+            name: name.to_owned(),
+            ..GMFunction::default()
+        });
+        self.functions.insert(name.to_owned(), function_ref);
+        function_ref
+    }
+
+    fn code_variable(&mut self, instr: &CompiledInstruction) -> Result<CodeVariable> {
+        let variable = instr.variable.as_ref().ok_or_else(|| {
+            libgm::Error::new(format!("Instruction at {} has no variable", instr.address))
+        })?;
+        Ok(CodeVariable {
+            variable: self.variable(variable)?,
+            variable_type: VariableType::try_from(variable.variable_type)
+                .ok()
+                .ok_or_else(|| {
+                    libgm::Error::new(format!("Invalid variable type {}", variable.variable_type))
+                })?,
+            instance_type: parse_instance_type(instr.instance_type)?,
+        })
+    }
+
+    /// The function an instruction refers to, with declared functions
+    /// renamed to their child entry names.
+    fn instruction_function(
+        &mut self,
+        instr: &CompiledInstruction,
+        child_names: &HashMap<&str, String>,
+    ) -> Result<GMRef<GMFunction>> {
+        let name: &str = instr.function.as_deref().ok_or_else(|| {
+            libgm::Error::new(format!("Instruction at {} has no function", instr.address))
+        })?;
+        let name: &str = child_names.get(name).map_or(name, String::as_str);
+        Ok(self.function(name))
+    }
+
+    fn push_value(
+        &mut self,
+        instr: &CompiledInstruction,
+        child_names: &HashMap<&str, String>,
+    ) -> Result<PushValue> {
+        Ok(match instr.type1 {
+            TYPE_DOUBLE => PushValue::Double(instr.value_double),
+            TYPE_INT32 if instr.function.is_some() => {
+                PushValue::Function(self.instruction_function(instr, child_names)?)
+            }
+            TYPE_INT32 => PushValue::Int32(instr.value_int),
+            TYPE_INT64 => PushValue::Int64(instr.value_long),
+            TYPE_BOOL => PushValue::Boolean(instr.value_short != 0),
+            TYPE_VARIABLE => PushValue::Variable(self.code_variable(instr)?),
+            TYPE_STRING => PushValue::String(instr.value_string.clone().unwrap_or_default()),
+            TYPE_INT16 => PushValue::Int16(instr.value_short),
+            other => return Err(libgm::Error::new(format!("Invalid push type {other}"))),
+        })
+    }
+
+    fn instruction(
+        &mut self,
+        instr: &CompiledInstruction,
+        child_names: &HashMap<&str, String>,
+    ) -> Result<LibGMInstruction> {
+        let type1 = || parse_data_type(instr.type1);
+        let type2 = || parse_data_type(instr.type2);
+        let jump_offset: i32 = instr.branch_offset / 4;
+        Ok(match instr.opcode {
+            OPCODE_CONV => LibGMInstruction::Convert {
+                from: type1()?,
+                to: type2()?,
+            },
+            OPCODE_MUL => LibGMInstruction::Multiply {
+                multiplicand: type1()?,
+                multiplier: type2()?,
+            },
+            OPCODE_DIV => LibGMInstruction::Divide {
+                dividend: type1()?,
+                divisor: type2()?,
+            },
+            OPCODE_REM => LibGMInstruction::Remainder {
+                dividend: type1()?,
+                divisor: type2()?,
+            },
+            OPCODE_MOD => LibGMInstruction::Modulus {
+                dividend: type1()?,
+                divisor: type2()?,
+            },
+            OPCODE_ADD => LibGMInstruction::Add {
+                augend: type1()?,
+                addend: type2()?,
+            },
+            OPCODE_SUB => LibGMInstruction::Subtract {
+                minuend: type1()?,
+                subtrahend: type2()?,
+            },
+            OPCODE_AND => LibGMInstruction::And {
+                lhs: type1()?,
+                rhs: type2()?,
+            },
+            OPCODE_OR => LibGMInstruction::Or {
+                lhs: type1()?,
+                rhs: type2()?,
+            },
+            OPCODE_XOR => LibGMInstruction::Xor {
+                lhs: type1()?,
+                rhs: type2()?,
+            },
+            OPCODE_NEG => LibGMInstruction::Negate {
+                data_type: type1()?,
+            },
+            OPCODE_NOT => LibGMInstruction::Not {
+                data_type: type1()?,
+            },
+            OPCODE_SHL => LibGMInstruction::ShiftLeft {
+                value: type1()?,
+                shift_amount: type2()?,
+            },
+            OPCODE_SHR => LibGMInstruction::ShiftRight {
+                value: type1()?,
+                shift_amount: type2()?,
+            },
+            OPCODE_CMP => LibGMInstruction::Compare {
+                lhs: type1()?,
+                rhs: type2()?,
+                comparison_type: ComparisonType::try_from(instr.comparison_kind)
+                    .ok()
+                    .ok_or_else(|| {
+                        libgm::Error::new(format!("Invalid comparison {}", instr.comparison_kind))
+                    })?,
+            },
+            OPCODE_POP if instr.pop_swap_size != 0 => LibGMInstruction::PopSwap {
+                is_array: instr.pop_swap_size == 6,
+            },
+            OPCODE_POP => LibGMInstruction::Pop {
+                variable: self.code_variable(instr)?,
+                type1: type1()?,
+                type2: type2()?,
+            },
+            OPCODE_DUP if instr.duplication_size2 != 0 => LibGMInstruction::DuplicateSwap {
+                data_type: type1()?,
+                size1: instr.duplication_size,
+                size2: instr.duplication_size2,
+            },
+            OPCODE_DUP => LibGMInstruction::Duplicate {
+                data_type: type1()?,
+                size: instr.duplication_size,
+            },
+            OPCODE_RETURN => LibGMInstruction::Return,
+            OPCODE_EXIT => LibGMInstruction::Exit,
+            OPCODE_POPZ => LibGMInstruction::PopDiscard {
+                data_type: type1()?,
+            },
+            OPCODE_BRANCH => LibGMInstruction::Branch { jump_offset },
+            OPCODE_BRANCH_TRUE => LibGMInstruction::BranchIf { jump_offset },
+            OPCODE_BRANCH_FALSE => LibGMInstruction::BranchUnless { jump_offset },
+            OPCODE_PUSH_ENV => LibGMInstruction::PushWithContext { jump_offset },
+            OPCODE_POP_ENV if instr.pop_with_context_exit => LibGMInstruction::PopWithContextExit,
+            OPCODE_POP_ENV => LibGMInstruction::PopWithContext { jump_offset },
+            OPCODE_PUSH => LibGMInstruction::Push {
+                value: self.push_value(instr, child_names)?,
+            },
+            OPCODE_PUSH_LOCAL => LibGMInstruction::PushLocal {
+                variable: self.code_variable(instr)?,
+            },
+            OPCODE_PUSH_GLOBAL => LibGMInstruction::PushGlobal {
+                variable: self.code_variable(instr)?,
+            },
+            OPCODE_PUSH_BUILTIN => LibGMInstruction::PushBuiltin {
+                variable: self.code_variable(instr)?,
+            },
+            OPCODE_PUSH_IMMEDIATE => LibGMInstruction::PushImmediate {
+                integer: instr.value_short,
+            },
+            OPCODE_CALL => LibGMInstruction::Call {
+                function: self.instruction_function(instr, child_names)?,
+                argument_count: int(instr.argument_count, "argument count")?,
+            },
+            OPCODE_CALL_VARIABLE => LibGMInstruction::CallVariable {
+                argument_count: int(instr.argument_count, "argument count")?,
+            },
+            OPCODE_EXTENDED => extended_instruction(instr)?,
+            other => return Err(libgm::Error::new(format!("Unknown opcode {other:#04X}"))),
+        })
+    }
+}
+
+fn extended_instruction(instr: &CompiledInstruction) -> Result<LibGMInstruction> {
+    Ok(match instr.extended_kind {
+        -1 => LibGMInstruction::CheckArrayIndex,
+        -2 => LibGMInstruction::PushArrayFinal,
+        -3 => LibGMInstruction::PopArrayFinal,
+        -4 => LibGMInstruction::PushArrayContainer,
+        -5 => LibGMInstruction::SetArrayOwner,
+        -6 => LibGMInstruction::HasStaticInitialized,
+        -7 => LibGMInstruction::SetStaticInitialized,
+        -8 => LibGMInstruction::SaveArrayReference,
+        -9 => LibGMInstruction::RestoreArrayReference,
+        -10 => LibGMInstruction::IsNullishValue,
+        -11 => LibGMInstruction::PushReference {
+            asset_reference: AssetReference::try_from(instr.value_int as u32)
+                .ok()
+                .ok_or_else(|| {
+                    libgm::Error::new(format!("Invalid asset reference {:#X}", instr.value_int))
+                })?,
+        },
+        other => {
+            return Err(libgm::Error::new(format!(
+                "Unknown extended instruction kind {other}"
+            )));
+        }
+    })
+}
+
+fn parse_data_type(raw: u8) -> Result<DataType> {
+    DataType::try_from(raw)
+        .ok()
+        .ok_or_else(|| libgm::Error::new(format!("Invalid data type {raw}")))
+}
+
+fn parse_instance_type(raw: i16) -> Result<InstanceType> {
+    InstanceType::try_from(raw)
+        .ok()
+        .ok_or_else(|| libgm::Error::new(format!("Invalid instance type {raw}")))
+}
+
+/// Converts between integer types of the compiler output and LibGM.
+fn int<T: TryFrom<U>, U: Copy + std::fmt::Display>(value: U, what: &str) -> Result<T> {
+    T::try_from(value)
+        .ok()
+        .ok_or_else(|| libgm::Error::new(format!("{what} {value} is out of range")))
+}
+
+#[cfg(test)]
+mod tests {
+    use libgm::{
+        gamemaker::elements::{
+            code_locals::{GMCodeLocal, GMCodeLocalVariable},
+            variable::GMVariable,
+        },
+        gml::{GMCode, instruction::Instruction as LibGMInstruction},
+        prelude::*,
+    };
+
+    use super::{
+        Linker, child_code_name, function_ranges, missing_owner, normalize, removed_functions,
+        root_locals,
+    };
+    use crate::{
+        compiler::{CompiledCode, CompiledFunction, CompiledInstruction, CompiledVariable},
+        gamemaker::{
+            INSTANCE_TYPE_LOCAL, OPCODE_BRANCH, OPCODE_EXIT, OPCODE_POP, OPCODE_RETURN, find_parent,
+        },
+    };
+
+    fn local(address: u32, name: &str) -> CompiledInstruction {
+        CompiledInstruction {
+            address,
+            opcode: OPCODE_POP,
+            variable: Some(CompiledVariable {
+                name: name.to_owned(),
+                instance_type: INSTANCE_TYPE_LOCAL,
+                variable_type: 160,
+                builtin: false,
+            }),
+            ..CompiledInstruction::default()
+        }
+    }
+
+    #[test]
+    fn locals_outside_functions() {
+        // 0: pop local.a, 8: b [24], 12: pop local.b (function body), 20: ret, 24: pop local.c
+        let branch = CompiledInstruction {
+            address: 8,
+            opcode: OPCODE_BRANCH,
+            branch_offset: 16,
+            ..CompiledInstruction::default()
+        };
+        let ret = CompiledInstruction {
+            address: 20,
+            opcode: OPCODE_RETURN,
+            ..CompiledInstruction::default()
+        };
+        let code = CompiledCode {
+            length: 32,
+            instructions: vec![
+                local(0, "a"),
+                branch,
+                local(12, "b"),
+                ret,
+                local(24, "c"),
+                local(28, "a"),
+            ],
+            functions: vec![CompiledFunction {
+                name: "gml_Script_f_gml_Object_o_Create_0".to_owned(),
+                offset: 12,
+                arguments: 0,
+                locals: 1,
+            }],
+        };
+        assert_eq!(function_ranges(&code), [(12, 24)]);
+        assert_eq!(root_locals(&code), ["a", "c"]);
+    }
+
+    fn slots(data: &GMData, name: &str) -> Vec<(u32, String)> {
+        let entry = data
+            .code_locals
+            .elements()
+            .iter()
+            .find(|entry| entry.name == name);
+        entry
+            .into_iter()
+            .flat_map(|entry| &entry.variables)
+            .map(|local| (local.index, local.name.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn locals_are_linked_by_slot() {
+        // A bytecode 15+ game before GMS 2.3: variables have IDs and entries have code locals.
+        let mut data = GMData::default();
+        let mut variable = GMVariable {
+            name: "x".to_owned(),
+            ..GMVariable::default()
+        };
+        variable.modern_data = Some(variable.modern_data.clone().unwrap_or_default());
+        data.variables.push(variable);
+        data.code_locals.push(GMCodeLocal {
+            name: "gml_Object_o_Create_0".to_owned(),
+            variables: vec![GMCodeLocalVariable {
+                index: 0,
+                name: "arguments".to_owned(),
+            }],
+        });
+
+        let mut linker = Linker::new(&mut data);
+        let name = "gml_Object_o_Step_0";
+        linker.update_code_locals(name, &["j", "i"], false).unwrap();
+        let i = linker.variable(&local(0, "i").variable.unwrap()).unwrap();
+        linker.update_code_locals(name, &["k", "i"], true).unwrap();
+        let k = linker.variable(&local(0, "k").variable.unwrap()).unwrap();
+        assert_eq!(
+            linker.variable(&local(0, "i").variable.unwrap()).unwrap(),
+            i
+        );
+
+        let expected = [(0, "arguments"), (1, "j"), (2, "i"), (3, "k")];
+        let expected = expected.map(|(slot, name)| (slot, name.to_owned()));
+        assert_eq!(slots(&data, name), expected);
+        for (variable_ref, slot) in [(i, 2), (k, 3)] {
+            let variable = data.variables.by_ref(variable_ref).unwrap();
+            let modern = variable.modern_data.as_ref().unwrap();
+            assert_eq!(modern.variable_id, slot);
+            assert_eq!(modern.instance_type.build(), INSTANCE_TYPE_LOCAL);
+        }
+    }
+
+    #[test]
+    fn no_code_locals_without_func_entries() {
+        let mut data = GMData::default();
+        let mut linker = Linker::new(&mut data);
+        linker
+            .update_code_locals("gml_Object_o_Step_0", &["i"], false)
+            .unwrap();
+        linker.variable(&local(0, "i").variable.unwrap()).unwrap();
+        assert!(data.code_locals.elements().is_empty());
+        assert_eq!(data.variables.elements().len(), 1);
+    }
+
+    fn code(name: &str, parent: Option<usize>, offset: u32) -> GMCode {
+        let mut code = GMCode {
+            name: name.to_owned(),
+            ..GMCode::default()
+        };
+        let mut modern = code.modern_data.take().unwrap_or_default();
+        modern.parent = parent.map(GMRef::from);
+        modern.offset = offset;
+        code.modern_data = Some(modern);
+        code
+    }
+
+    #[test]
+    fn relinks_global_script_functions() {
+        let mut data = GMData::default();
+        data.general_info.wad_version = 17;
+        data.codes.push(code("gml_GlobalScript_scr_a", None, 0));
+        data.codes.push(code("gml_Script_f", Some(0), 0));
+        let name = "gml_GlobalScript_scr_a";
+        assert_eq!(find_parent(GMRef::from(0), &data).unwrap(), None);
+        assert_eq!(
+            find_parent(GMRef::from(1), &data).unwrap(),
+            Some(GMRef::from(0))
+        );
+
+        // 0: b 8, f: 4: exit, 8: exit
+        let instruction = |address, opcode, branch_offset| CompiledInstruction {
+            address,
+            opcode,
+            branch_offset,
+            ..CompiledInstruction::default()
+        };
+        let mut compiled = CompiledCode {
+            length: 12,
+            instructions: vec![
+                instruction(0, OPCODE_BRANCH, 8),
+                instruction(4, OPCODE_EXIT, 0),
+                instruction(8, OPCODE_EXIT, 0),
+            ],
+            functions: vec![CompiledFunction {
+                name: "gml_Script_f".to_owned(),
+                offset: 4,
+                arguments: 0,
+                locals: 0,
+            }],
+        };
+        assert!(removed_functions(&data, name, &compiled).is_empty());
+
+        let mut linker = Linker::new(&mut data);
+        assert!(linker.link(name, &compiled).unwrap());
+        assert_eq!(data.codes.len(), 2);
+        assert!(data.scripts.elements().is_empty());
+        let root = data.codes.by_ref(GMRef::from(0)).unwrap();
+        assert_eq!(
+            root.instructions[0],
+            LibGMInstruction::Branch { jump_offset: 2 }
+        );
+        let child = data.codes.by_ref(GMRef::from(1)).unwrap();
+        assert_eq!(child.modern_data.as_ref().unwrap().offset, 4);
+
+        compiled.functions.clear();
+        assert_eq!(removed_functions(&data, name, &compiled), ["gml_Script_f"]);
+    }
+
+    #[test]
+    fn new_events_need_an_owner() {
+        assert!(missing_owner("gml_Object_obj_a_Step_0").is_some());
+        assert!(missing_owner("gml_RoomCC_rm_a_0_Create").is_some());
+        assert!(missing_owner("gml_GlobalScript_scr_a").is_none());
+    }
+
+    #[test]
+    fn names_children() {
+        assert_eq!(
+            child_code_name("f", "gml_GlobalScript_scr_a"),
+            "gml_Script_f"
+        );
+        assert_eq!(
+            child_code_name("f", "gml_Object_o_Create_0"),
+            "gml_Script_f_gml_Object_o_Create_0"
+        );
+        assert_eq!(normalize("a\r\nb\n\n"), normalize("a\nb"));
+    }
+}
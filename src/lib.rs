@@ -2,9 +2,30 @@
 // #![warn(clippy::pedantic)]
 // #![warn(clippy::nursery)]
 
+mod callgraph;
+mod compiler;
+mod create;
 mod dynlib;
+mod extract;
+mod fallback;
+mod formatter;
+mod function_lookup;
 mod gamemaker;
+mod highlight;
+mod hook;
+mod import;
+mod lint;
+mod listing;
+mod lsp;
+mod owner;
+mod patch;
 mod primitives;
+mod project;
+mod server;
+mod unused;
+pub mod xref;
+
+use std::{collections::HashSet, ops::Range, path::Path};
 
 use libgm::{
     error::Context,
@@ -12,13 +33,43 @@ use libgm::{
     gml::GMCode,
 };
 
-use crate::{dynlib::decompile_to_string, gamemaker::Code};
+use crate::{
+    dynlib::{decompile_to_string, query_context_flags},
+    extract::{extract_lines, mark_lines},
+    function_lookup::find_declaration,
+    gamemaker::{Code, find_parent},
+    highlight::highlight,
+};
 
-pub use crate::gamemaker::GameContext;
+pub use crate::{
+    callgraph::{Call, CallGraph, CallKind, build_call_graph},
+    create::{add_object_event, add_room_creation_code, create_script},
+    dynlib::DynlibSource,
+    fallback::Decompilation,
+    formatter::format,
+    function_lookup::{FunctionCandidate, FunctionLookup},
+    gamemaker::{
+        AssetKind, BuiltinFunction, BuiltinVariable, Capabilities, DecompileSettings, GameContext,
+        GameContextBuilder,
+    },
+    highlight::{OutputFormat, TokenClass},
+    hook::{HookPosition, hook_code},
+    import::{ImportReport, import_directory},
+    lint::{Diagnostic, DiagnosticKind, Span},
+    lsp::serve_language_server,
+    owner::{CodeOwner, Event, EventType, OwnerIndex, build_owner_index},
+    patch::{Hunk, Operation, Patch, apply_patch},
+    server::serve,
+    unused::{UnusedReport, find_unused_assets},
+    xref::{AccessKind, Xref, XrefIndex},
+};
 
 /// Tries to initialize to dynamic library cache.
 /// Otherwise, it will be initialized on the first [`GameContext::decompile`] call.
 ///
+/// The embedded library is extracted into a persistent cache directory
+/// (see [`DynlibSource::Cache`]) once and reused by later processes.
+///
 /// Calling this function is not needed, but has two benefits:
 /// * You can explicitly choose *when* to initialize the dynamic library,
 ///   since it may take a few hundred milliseconds
@@ -29,12 +80,61 @@ pub use crate::gamemaker::GameContext;
 ///
 /// # Errors
 /// This function may fail in these ways:
-/// * creating temporary file
-/// * writing library data to temporary file
+/// * determining or creating the cache directory
+/// * writing library data to the cache file
 /// * loading dynamic library
 /// * loading symbols from library
 pub fn init_dynlib() -> libgm::Result<()> {
-    dynlib::init_externs()
+    dynlib::init_externs(&DynlibSource::default())
+}
+
+/// Initializes the dynamic library from an explicit file path instead of the embedded copy.
+///
+/// This is useful if the temporary or cache directories are mounted `noexec`.
+/// Just like [`init_dynlib`], this function does nothing if the library was already initialized.
+///
+/// # Errors
+/// This function fails if the library cannot be loaded from the given path
+/// or if it does not export the expected symbols.
+pub fn init_dynlib_from(path: impl AsRef<Path>) -> libgm::Result<()> {
+    dynlib::init_externs(&DynlibSource::Path(path.as_ref().to_owned()))
+}
+
+/// Initializes the dynamic library using the given loading strategy.
+///
+/// Just like [`init_dynlib`], this function does nothing if the library was already initialized.
+///
+/// # Errors
+/// See [`init_dynlib`] and [`init_dynlib_from`].
+pub fn init_dynlib_with(source: &DynlibSource) -> libgm::Result<()> {
+    dynlib::init_externs(source)
+}
+
+/// Loads an additional builtin database, in the format of `data/builtins.json`.
+///
+/// Its entries extend the embedded database; entries with the same name override it.
+/// This affects every [`GameContext`], including existing ones.
+///
+/// # Errors
+/// This function fails if the JSON cannot be parsed
+/// (the details are printed to stderr by the dynamic library).
+///
+/// # Panics
+/// This function panics if the dynamic library was not initialized yet and fails to load.
+/// See [`init_dynlib`].
+pub fn load_builtins(json: &str) -> libgm::Result<()> {
+    dynlib::load_builtins(json, false)
+}
+
+/// Replaces the builtin database (including the embedded one) with the given one.
+///
+/// # Errors
+/// See [`load_builtins`].
+///
+/// # Panics
+/// See [`load_builtins`].
+pub fn replace_builtins(json: &str) -> libgm::Result<()> {
+    dynlib::load_builtins(json, true)
 }
 
 impl<'a> GameContext<'a> {
@@ -54,52 +154,231 @@ impl<'a> GameContext<'a> {
     ///
     /// [`GMRef`]: libgm::gamemaker::reference::GMRef
     pub fn new(gm_data: &'a GMData) -> libgm::Result<Self> {
-        Self::try_from_libgm(gm_data).with_context(|| {
-            format!(
-                "constructing GameContext for {}",
-                gm_data.general_info.game_name,
-            )
-        })
+        GameContextBuilder::new(gm_data).build()
+    }
+
+    /// Creates a [`GameContextBuilder`], which allows overriding the version info
+    /// and code analysis results that would otherwise be inferred from the [`GMData`].
+    pub const fn builder(gm_data: &'a GMData) -> GameContextBuilder<'a> {
+        GameContextBuilder::new(gm_data)
+    }
+
+    /// Returns the feature flags Underanalyzer uses when decompiling with this context.
+    ///
+    /// These are derived from the game's version info and code analysis,
+    /// unless they were overridden using [`GameContext::set_capabilities`].
+    ///
+    /// # Panics
+    /// This function panics if the dynamic library was not initialized yet and fails to load.
+    /// See [`init_dynlib`].
+    #[must_use]
+    pub fn capabilities(&self) -> Capabilities {
+        unsafe { query_context_flags(self) }
+    }
+
+    /// Forces Underanalyzer to use the given feature flags instead of deriving them from the version.
+    ///
+    /// This makes games with spoofed or stripped version info decompilable.
+    /// To only change individual flags, modify the result of [`GameContext::capabilities`]:
+    /// ```no_run
+    /// # fn f(ctx: &mut underanalyzer::GameContext) {
+    /// let mut caps = ctx.capabilities();
+    /// caps.using_gmlv2 = true;
+    /// ctx.set_capabilities(caps);
+    /// # }
+    /// ```
+    pub fn set_capabilities(&mut self, capabilities: Capabilities) {
+        self.override_capabilities(Some(capabilities));
+    }
+
+    /// Removes any overrides set by [`GameContext::set_capabilities`],
+    /// deriving the feature flags from the version info again.
+    pub fn reset_capabilities(&mut self) {
+        self.override_capabilities(None);
     }
 
     /// Tries to decompile the given code entry by calling `DecompileToString` in Underanalyzer.
     ///
+    /// Child code entries (GMLv2 functions and struct constructors) are supported as well.
+    /// For those, the parent entry is decompiled and only the function declaration is returned,
+    /// with its real name and arguments.
+    /// To see the function within its parent, use [`GameContext::decompile_in_parent`].
+    ///
     /// # Errors
     /// This function will return an error if:
     /// * any error occurred in Underanalyzer (signalled by the returned `error` byte being non-zero)
     /// * the returned string is null
     /// * the returned string contains invalid UTF-8
+    /// * the declaration of a child entry could not be located in its decompiled parent
+    ///   (e.g. for anonymous functions passed as arguments)
     ///
     /// The most likely error cause will definitely be a decompilation error in Underanalyzer, though.
     pub fn decompile(&self, code_ref: GMRef<GMCode>, gm_data: &GMData) -> libgm::Result<String> {
+        self.decompile_with_settings(code_ref, gm_data, &DecompileSettings::default())
+    }
+
+    /// Decompiles the given code entry like [`GameContext::decompile`],
+    /// using the given settings for Underanalyzer's code printer.
+    ///
+    /// If CSS colors are enabled, child entries are located in a decompilation without
+    /// colors first, so their parent entry is decompiled twice.
+    ///
+    /// # Errors
+    /// See [`GameContext::decompile`].
+    pub fn decompile_with_settings(
+        &self,
+        code_ref: GMRef<GMCode>,
+        gm_data: &GMData,
+        settings: &DecompileSettings,
+    ) -> libgm::Result<String> {
+        let Some(parent_ref) = find_parent(code_ref, gm_data)? else {
+            return self.decompile_root(code_ref, gm_data, settings);
+        };
+
+        // The markup does not add any lines, so the lines located in the plain output
+        // are the same as in the marked up output.
+        let plain = DecompileSettings {
+            use_css_colors: false,
+            ..*settings
+        };
+        let (source, lines) = self.locate_child(code_ref, parent_ref, gm_data, &plain)?;
+        let Some(lines) = lines else {
+            let name = &gm_data.codes.by_ref(code_ref)?.name;
+            let message = format!("Could not locate declaration of {name:?} in its parent");
+            return Err(libgm::Error::new(message));
+        };
+        if settings.use_css_colors {
+            let source = self.decompile_root(parent_ref, gm_data, settings)?;
+            return Ok(extract_lines(&source, lines));
+        }
+        Ok(extract_lines(&source, lines))
+    }
+
+    /// Decompiles the given code entry like [`GameContext::decompile`],
+    /// returning the output in the given format.
+    ///
+    /// For [`OutputFormat::Ansi`] and [`OutputFormat::Html`], asset names, builtin functions,
+    /// builtin variables, constants, enums, strings and comments get distinct token classes.
+    /// [`OutputFormat::CssColors`] uses Underanalyzer's own markup instead;
+    /// for child entries, this decompiles the parent entry twice.
+    ///
+    /// # Errors
+    /// See [`GameContext::decompile`].
+    pub fn decompile_with_format(
+        &self,
+        code_ref: GMRef<GMCode>,
+        gm_data: &GMData,
+        format: OutputFormat,
+    ) -> libgm::Result<String> {
+        match format {
+            OutputFormat::Plain => self.decompile(code_ref, gm_data),
+            OutputFormat::Ansi | OutputFormat::Html => {
+                let source = self.decompile(code_ref, gm_data)?;
+                Ok(self.highlight(&source, format))
+            }
+            OutputFormat::CssColors => {
+                let settings = DecompileSettings {
+                    use_css_colors: true,
+                    ..DecompileSettings::default()
+                };
+                self.decompile_with_settings(code_ref, gm_data, &settings)
+            }
+        }
+    }
+
+    /// Highlights already decompiled GML (see [`GameContext::decompile_with_format`]).
+    ///
+    /// [`OutputFormat::CssColors`] requires Underanalyzer and is treated as [`OutputFormat::Plain`].
+    ///
+    /// # Panics
+    /// This function panics if the dynamic library was not initialized yet and fails to load.
+    #[must_use]
+    pub fn highlight(&self, source: &str, format: OutputFormat) -> String {
+        if matches!(format, OutputFormat::Plain | OutputFormat::CssColors) {
+            return source.to_owned();
+        }
+        let assets: HashSet<&str> = self.asset_names().collect();
+        highlight(source, format, |name, is_call| {
+            if assets.contains(name) {
+                Some(TokenClass::Asset)
+            } else if is_call {
+                self.builtin_function(name)
+                    .map(|_| TokenClass::BuiltinFunction)
+            } else if self.builtin_variable(name).is_some() {
+                Some(TokenClass::BuiltinVariable)
+            } else {
+                self.constant(name).map(|_| TokenClass::Constant)
+            }
+        })
+    }
+
+    /// Decompiles the root code entry the given code entry belongs to.
+    ///
+    /// If the given entry is a child entry, its declaration is surrounded by
+    /// `// >>> <name>` and `// <<< <name>` marker comments.
+    /// The markers are omitted if the declaration could not be located.
+    ///
+    /// # Errors
+    /// See [`GameContext::decompile`].
+    pub fn decompile_in_parent(
+        &self,
+        code_ref: GMRef<GMCode>,
+        gm_data: &GMData,
+    ) -> libgm::Result<String> {
+        let Some(parent_ref) = find_parent(code_ref, gm_data)? else {
+            return self.decompile_root(code_ref, gm_data, &DecompileSettings::default());
+        };
+
+        let settings = DecompileSettings::default();
+        let (source, lines) = self.locate_child(code_ref, parent_ref, gm_data, &settings)?;
+        let name = &gm_data.codes.by_ref(code_ref)?.name;
+        Ok(match lines {
+            Some(lines) => mark_lines(&source, lines, name),
+            None => source,
+        })
+    }
+
+    /// Decompiles the parent entry and finds the lines declaring the child entry.
+    fn locate_child(
+        &self,
+        code_ref: GMRef<GMCode>,
+        parent_ref: GMRef<GMCode>,
+        gm_data: &GMData,
+        settings: &DecompileSettings,
+    ) -> libgm::Result<(String, Option<Range<usize>>)> {
+        let source = self.decompile_root(parent_ref, gm_data, settings)?;
+        let lines = find_declaration(&source, code_ref, parent_ref, gm_data)?;
+        Ok((source, lines))
+    }
+
+    fn decompile_root(
+        &self,
+        code_ref: GMRef<GMCode>,
+        gm_data: &GMData,
+        settings: &DecompileSettings,
+    ) -> libgm::Result<String> {
         let code = Code::try_from_libgm(code_ref, gm_data).with_context(|| {
             format!(
                 "converting LibGM code entry #{} into FFI struct",
                 u32::from(code_ref),
             )
         })?;
+        self.decompile_converted(&code, settings)
+    }
 
-        let code = &raw const code;
+    /// Decompiles an already converted root entry.
+    fn decompile_converted(
+        &self,
+        code: &Code,
+        settings: &DecompileSettings,
+    ) -> libgm::Result<String> {
+        let code = &raw const *code;
         let ctx = self as *const Self;
 
-        let ret = unsafe { decompile_to_string(ctx, code) };
-
-        let string: &str = unsafe { ret.string.to_str() }
-            .context("constructing string from return value of Underanalyzer DecompileToString")?;
-
-        if ret.error == 0 {
-            return Ok(string.to_owned());
-        }
-
-        // Error occurred
-        let errno: u8 = ret.error;
-        let message = if errno == 1 {
-            string.to_owned()
-        } else {
-            // print extra stuff for unknown error codes
-            format!("Decompilation failed with error code {errno}: {string}")
-        };
-
-        Err(libgm::Error::new(message).push_context("decompiling code entry using Underanalyzer"))
+        let ret = unsafe { decompile_to_string(ctx, code, settings) };
+        ret.into_result(
+            "DecompileToString",
+            "decompiling code entry using Underanalyzer",
+        )
     }
 }
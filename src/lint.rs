@@ -0,0 +1,455 @@
+use std::collections::HashSet;
+
+use libgm::prelude::*;
+
+use crate::{
+    GameContext,
+    compiler::CompileError,
+    gamemaker::{BuiltinFunction, BuiltinVariable, child_function_name, find_parent},
+    highlight::{KEYWORDS, TokenClass, tokenize},
+};
+
+/// A problem found by [`GameContext::lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub message: String,
+    pub span: Span,
+}
+
+/// The kind of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    /// An error reported by Underanalyzer's compiler, e.g. a syntax error.
+    CompileError,
+    /// A called function is neither builtin, nor declared in the game or the source.
+    UnknownFunction,
+    /// A builtin function is called with too few or too many arguments.
+    ArgumentCount,
+    /// A builtin variable that cannot be set is assigned to.
+    ReadOnlyAssignment,
+}
+
+/// A range of source text. Lines and columns start at 1; columns count characters.
+/// The end is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub line: u32,
+    pub column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+/// What the game knows about an identifier.
+#[derive(Debug, Clone, Copy, Default)]
+struct Symbol {
+    builtin_function: Option<BuiltinFunction>,
+    builtin_variable: Option<BuiltinVariable>,
+    /// Declared by the game itself (script, function, asset, extension).
+    declared: bool,
+}
+
+impl GameContext<'_> {
+    /// Checks GML source for mistakes, without modifying the [`GMData`].
+    ///
+    /// The source is compiled with Underanalyzer first (see [`GameContext::compile_errors`]),
+    /// and each compiler error becomes a [`DiagnosticKind::CompileError`] at the position of the
+    /// token the compiler reported it at. Only if the source compiles, it is checked for calls to
+    /// unknown functions, builtin function calls with the wrong number of arguments, and
+    /// assignments to read-only builtin variables. Builtins are looked up for this game's version.
+    /// Functions are resolved like the decompiler does: scripts, extensions, assets, the
+    /// function table, and the functions declared by every code entry of the game
+    /// (so methods declared in another event of an object are known as well).
+    ///
+    /// # Errors
+    /// This function fails if the compiler crashes, not if the source has errors.
+    pub fn lint(&self, source: &str, gm_data: &GMData) -> libgm::Result<Vec<Diagnostic>> {
+        let errors = self.compile_error_positions(source)?;
+        if !errors.is_empty() {
+            return Ok(errors
+                .into_iter()
+                .map(|error| compile_error(source, error))
+                .collect());
+        }
+
+        let declared: HashSet<&str> = self
+            .asset_names()
+            .chain(self.function_names())
+            .chain(declared_functions(gm_data))
+            .collect();
+        Ok(lint(source, |name| Symbol {
+            builtin_function: self.builtin_function(name),
+            builtin_variable: self.builtin_variable(name),
+            declared: declared.contains(name)
+                || declared.contains(format!("gml_Script_{name}").as_str())
+                || self.constant(name).is_some(),
+        }))
+    }
+}
+
+/// Names of the GMLv2 functions declared by child entries, as they are called in GML
+/// (e.g. `take_damage` for `gml_Script_take_damage_gml_Object_obj_player_Create_0`).
+fn declared_functions(gm_data: &GMData) -> impl Iterator<Item = &str> {
+    gm_data
+        .codes
+        .elements()
+        .iter()
+        .enumerate()
+        .filter_map(|(i, code)| {
+            let parent_ref = find_parent(GMRef::from(i), gm_data).ok()??;
+            let parent = gm_data.codes.by_ref(parent_ref).ok()?;
+            Some(child_function_name(&code.name, &parent.name))
+        })
+}
+
+/// Turns a compiler error into a diagnostic spanning the character it was reported at
+/// (or the start of the source if the compiler reported no position).
+fn compile_error(source: &str, error: CompileError) -> Diagnostic {
+    let line = error.line.max(1);
+    let column = error.column.max(1);
+    let line_length = source
+        .lines()
+        .nth(line as usize - 1)
+        .map_or(0, |text| text.chars().count() as u32);
+    let message = if error.base_message.is_empty() {
+        error.message
+    } else {
+        error.base_message
+    };
+    Diagnostic {
+        kind: DiagnosticKind::CompileError,
+        span: Span {
+            line,
+            column,
+            end_line: line,
+            end_column: (column + 1).min(line_length + 1).max(column),
+        },
+        message,
+    }
+}
+
+struct Token<'s> {
+    text: &'s str,
+    class: Option<TokenClass>,
+    offset: usize,
+}
+
+impl Token<'_> {
+    fn is_identifier(&self) -> bool {
+        self.class.is_none()
+            && self
+                .text
+                .starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && !KEYWORDS.contains(&self.text)
+    }
+}
+
+fn lint(source: &str, mut resolve: impl FnMut(&str) -> Symbol) -> Vec<Diagnostic> {
+    let mut offset = 0;
+    let tokens: Vec<Token> = tokenize(source)
+        .filter_map(|(text, class)| {
+            let token = Token {
+                text,
+                class,
+                offset,
+            };
+            offset += text.len();
+            let skip = class == Some(TokenClass::Comment) || text.trim().is_empty();
+            (!skip).then_some(token)
+        })
+        .collect();
+
+    let local: HashSet<&str> = local_names(&tokens);
+    let mut diagnostics = Vec::new();
+    let mut diagnose = |kind, message: String, start: &Token, end: &Token| {
+        let span = span(source, start.offset, end.offset + end.text.len());
+        diagnostics.push(Diagnostic {
+            kind,
+            message,
+            span,
+        });
+    };
+
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_identifier() {
+            continue;
+        }
+        let previous: Option<&str> = i.checked_sub(1).map(|p| tokens[p].text);
+        let next: Option<&str> = tokens.get(i + 1).map(|t| t.text);
+        // Members of structs and instances may shadow anything.
+        let is_member = previous == Some(".");
+
+        if next == Some("(") && !is_member && previous != Some("function") {
+            if local.contains(token.text) {
+                continue;
+            }
+            let symbol = resolve(token.text);
+            let Some(function) = symbol.builtin_function else {
+                if !symbol.declared {
+                    diagnose(
+                        DiagnosticKind::UnknownFunction,
+                        format!("Unknown function {:?}", token.text),
+                        token,
+                        token,
+                    );
+                }
+                continue;
+            };
+            let Some((count, close)) = count_arguments(&tokens[i + 1..]) else {
+                continue;
+            };
+            let too_few = count < function.min_args;
+            let too_many = function.max_args.is_some_and(|max| count > max);
+            if too_few || too_many {
+                let expected = match function.max_args {
+                    Some(max) if max == function.min_args => format!("{max}"),
+                    Some(max) => format!("{} to {max}", function.min_args),
+                    None => format!("at least {}", function.min_args),
+                };
+                diagnose(
+                    DiagnosticKind::ArgumentCount,
+                    format!(
+                        "{} expects {expected} arguments, but got {count}",
+                        token.text
+                    ),
+                    token,
+                    close,
+                );
+            }
+        } else if is_assignment(&tokens[i + 1..]) && starts_statement(previous) {
+            let symbol = resolve(token.text);
+            if symbol.builtin_variable.is_some_and(|v| !v.can_set) {
+                diagnose(
+                    DiagnosticKind::ReadOnlyAssignment,
+                    format!("Builtin variable {} cannot be set", token.text),
+                    token,
+                    token,
+                );
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Names declared by the source itself that may be called: functions, parameters, locals,
+/// statics, globalvars, enums, macros, and variables assigned a function or method.
+fn local_names<'s>(tokens: &[Token<'s>]) -> HashSet<&'s str> {
+    let mut names = HashSet::new();
+    let mut in_parameters = false;
+    for (i, token) in tokens.iter().enumerate() {
+        let previous: Option<&str> = i.checked_sub(1).map(|p| tokens[p].text);
+        match token.text {
+            "(" if previous == Some("function")
+                || i.checked_sub(2)
+                    .is_some_and(|p| tokens[p].text == "function") =>
+            {
+                in_parameters = true;
+            }
+            ")" => in_parameters = false,
+            _ if !token.is_identifier() => {}
+            _ if in_parameters && matches!(previous, Some("(" | ",")) => {
+                names.insert(token.text);
+            }
+            _ if matches!(
+                previous,
+                Some("function" | "var" | "static" | "globalvar" | "enum" | "macro")
+            ) || assigns_function(&tokens[i + 1..]) =>
+            {
+                names.insert(token.text);
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Whether the tokens start with `= function` or `= method(`.
+fn assigns_function(tokens: &[Token]) -> bool {
+    let text = |i: usize| tokens.get(i).map(|t| t.text);
+    text(0) == Some("=") && matches!(text(1), Some("function" | "method"))
+}
+
+/// Whether the tokens start with an assignment operator (`=`, `+=`, `++`, ...),
+/// as opposed to a comparison (`==`, `<=`, ...).
+fn is_assignment(tokens: &[Token]) -> bool {
+    let text = |i: usize| tokens.get(i).map(|t| t.text);
+    match (text(0), text(1)) {
+        (Some("="), second) => second != Some("="),
+        (Some("+"), Some("+")) | (Some("-"), Some("-")) => true,
+        (Some("+" | "-" | "*" | "/" | "%" | "|" | "&" | "^"), Some("=")) => true,
+        (Some("?"), Some("?")) => text(2) == Some("="),
+        _ => false,
+    }
+}
+
+/// GML allows `=` as a comparison in conditions, so assignments are only
+/// recognized at the start of a statement.
+fn starts_statement(previous: Option<&str>) -> bool {
+    matches!(
+        previous,
+        None | Some(";" | "{" | "}" | ")" | "else" | "do" | "then" | "var" | "static")
+    )
+}
+
+/// Counts the arguments of a call, given the tokens starting at its opening parenthesis.
+/// Returns the count and the closing parenthesis.
+fn count_arguments<'t, 's>(tokens: &'t [Token<'s>]) -> Option<(u32, &'t Token<'s>)> {
+    let mut depth: u32 = 0;
+    let mut count: u32 = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.class.is_some() {
+            continue;
+        }
+        match token.text {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    let is_empty = i == 1;
+                    return Some((if is_empty { 0 } else { count + 1 }, token));
+                }
+            }
+            "," if depth == 1 => count += 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn span(source: &str, start: usize, end: usize) -> Span {
+    let (line, column) = position(source, start);
+    let (end_line, end_column) = position(source, end);
+    Span {
+        line,
+        column,
+        end_line,
+        end_column,
+    }
+}
+
+fn position(source: &str, offset: usize) -> (u32, u32) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line as u32, column as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiagnosticKind, Span, Symbol, compile_error, lint};
+    use crate::{
+        compiler::CompileError,
+        gamemaker::{BuiltinFunction, BuiltinVariable},
+    };
+
+    fn resolve(name: &str) -> Symbol {
+        match name {
+            "show_message" => Symbol {
+                builtin_function: Some(BuiltinFunction {
+                    min_args: 1,
+                    max_args: Some(1),
+                }),
+                ..Symbol::default()
+            },
+            "instance_count" => Symbol {
+                builtin_variable: Some(BuiltinVariable {
+                    can_set: false,
+                    is_global: true,
+                    is_automatic_array: false,
+                }),
+                ..Symbol::default()
+            },
+            "scr_known" => Symbol {
+                declared: true,
+                ..Symbol::default()
+            },
+            _ => Symbol::default(),
+        }
+    }
+
+    fn kinds(source: &str) -> Vec<DiagnosticKind> {
+        lint(source, resolve).into_iter().map(|d| d.kind).collect()
+    }
+
+    #[test]
+    fn accepts_valid_code() {
+        let source = "function helper(a) { return a; }\n\
+                      var f = function() {};\n\
+                      g = method(self, helper);\n\
+                      show_message(helper(scr_known(1, 2)));\n\
+                      f();\n\
+                      g();\n\
+                      if (instance_count = 0) { other.unknown_method(); }\n";
+        assert_eq!(kinds(source), []);
+    }
+
+    #[test]
+    fn reports_unknown_functions() {
+        let diagnostics = lint("x = 1;\nfoo(x);", resolve);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::UnknownFunction);
+        assert_eq!(
+            diagnostics[0].span,
+            Span {
+                line: 2,
+                column: 1,
+                end_line: 2,
+                end_column: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn assigned_values_are_not_functions() {
+        assert_eq!(kinds("x = 1;\nx();"), [DiagnosticKind::UnknownFunction]);
+    }
+
+    #[test]
+    fn reports_argument_counts() {
+        assert_eq!(kinds("show_message();"), [DiagnosticKind::ArgumentCount]);
+        assert_eq!(
+            kinds("show_message(scr_known(1, 2), [3, 4]);"),
+            [DiagnosticKind::ArgumentCount]
+        );
+        assert_eq!(kinds("show_message(\"a, b\");"), []);
+    }
+
+    #[test]
+    fn reports_read_only_assignments() {
+        assert_eq!(
+            kinds("instance_count = 5;"),
+            [DiagnosticKind::ReadOnlyAssignment]
+        );
+        assert_eq!(
+            kinds("instance_count++;"),
+            [DiagnosticKind::ReadOnlyAssignment]
+        );
+        assert_eq!(kinds("if (instance_count == 5) {}"), []);
+    }
+
+    #[test]
+    fn compile_errors_point_at_their_position() {
+        let error = |line, column| CompileError {
+            message: "Expected ')' around line 2, column 7".to_owned(),
+            base_message: "Expected ')'".to_owned(),
+            line,
+            column,
+        };
+        let diagnostic = compile_error("x = 1;\ny = (2;", error(2, 7));
+        assert_eq!(diagnostic.kind, DiagnosticKind::CompileError);
+        assert_eq!(diagnostic.message, "Expected ')'");
+        assert_eq!(
+            diagnostic.span,
+            Span {
+                line: 2,
+                column: 7,
+                end_line: 2,
+                end_column: 8,
+            }
+        );
+        let diagnostic = compile_error("", error(0, 0));
+        assert_eq!((diagnostic.span.line, diagnostic.span.column), (1, 1));
+    }
+}
@@ -0,0 +1,174 @@
+use std::{collections::BTreeSet, ops::Range};
+
+use libgm::{gml::GMCode, prelude::*};
+
+use crate::{
+    GameContext,
+    dynlib::decompile_listing,
+    gamemaker::{Code, DecompileSettings, Instruction, find_parent},
+};
+
+impl GameContext<'_> {
+    /// Decompiles the root entry the given code entry belongs to and interleaves
+    /// each line of GML with the bytecode instructions it was compiled from.
+    ///
+    /// The instructions are split into statements (at assignments, discarded calls, returns and
+    /// branches). Underanalyzer reports which instructions the variables and functions on each
+    /// printed line come from, and each statement is listed under the first line one of its
+    /// references was printed on. Statements without references (like `exit` or `return 0`)
+    /// are listed under the next line without references of its own following
+    /// the previous statement's line.
+    ///
+    /// # Errors
+    /// This function fails if the code entry cannot be converted or decompiled.
+    pub fn listing(&self, code_ref: GMRef<GMCode>, gm_data: &GMData) -> Result<String> {
+        let root_ref = find_parent(code_ref, gm_data)?.unwrap_or(code_ref);
+        let code = Code::try_from_libgm(root_ref, gm_data)?;
+        let settings = DecompileSettings::default();
+        let ret = unsafe { decompile_listing(self, &raw const *code, &settings) };
+        let output = ret.into_result("DecompileListing", "decompiling code entry listing")?;
+        let (trace, source) = parse_trace(&output)?;
+
+        let instructions: Vec<(u32, &Instruction)> = code.addressed_instructions().collect();
+        let groups: Vec<Range<usize>> = split_statements(&instructions);
+        let mut anchors: Vec<Option<usize>> = vec![None; instructions.len()];
+        for (line, index) in trace {
+            if let Some(anchor) = anchors.get_mut(index) {
+                *anchor = Some(anchor.map_or(line, |l| l.min(line)));
+            }
+        }
+        let lines: Vec<&str> = source.lines().collect();
+        let group_lines: Vec<usize> = assign_lines(&lines, &groups, &anchors);
+
+        let mut output = String::new();
+        for (i, line) in lines.iter().enumerate() {
+            output += line;
+            output.push('\n');
+            let indent = &line[..line.len() - line.trim_start().len()];
+            for (group, _) in groups.iter().zip(&group_lines).filter(|(_, l)| **l == i) {
+                for &(address, instr) in &instructions[group.clone()] {
+                    output += &format!(
+                        "{indent}    | {address:05}: {}\n",
+                        instr.disassemble(address)
+                    );
+                }
+            }
+        }
+        Ok(output)
+    }
+}
+
+/// Splits the output of [`decompile_listing`] into its `(line, instruction index)` trace
+/// and the decompiled source.
+fn parse_trace(output: &str) -> Result<(Vec<(usize, usize)>, &str)> {
+    let invalid = || libgm::Error::new("Invalid listing trace from Underanalyzer".to_owned());
+    let (count, mut rest) = output.split_once('\n').ok_or_else(invalid)?;
+    let count: usize = count.parse().map_err(|_| invalid())?;
+    let mut trace = Vec::with_capacity(count);
+    for _ in 0..count {
+        let (entry, remaining) = rest.split_once('\n').ok_or_else(invalid)?;
+        let (line, index) = entry.split_once(' ').ok_or_else(invalid)?;
+        let line: usize = line.parse().map_err(|_| invalid())?;
+        let index: usize = index.parse().map_err(|_| invalid())?;
+        trace.push((line, index));
+        rest = remaining;
+    }
+    Ok((trace, rest))
+}
+
+/// Index ranges of instructions up to (and including) one that completes a statement.
+fn split_statements(instructions: &[(u32, &Instruction)]) -> Vec<Range<usize>> {
+    let mut groups = Vec::new();
+    let mut start: usize = 0;
+    for (i, (_, instr)) in instructions.iter().enumerate() {
+        if instr.ends_statement() {
+            groups.push(start..i + 1);
+            start = i + 1;
+        }
+    }
+    if start < instructions.len() {
+        groups.push(start..instructions.len());
+    }
+    groups
+}
+
+/// Picks the line to list each statement group under, given the first line
+/// each instruction's references were printed on (see [`GameContext::listing`]).
+fn assign_lines(lines: &[&str], groups: &[Range<usize>], anchors: &[Option<usize>]) -> Vec<usize> {
+    let anchored: BTreeSet<usize> = anchors.iter().flatten().copied().collect();
+    let mut used: BTreeSet<usize> = BTreeSet::new();
+    let mut previous: usize = 0;
+    let mut assigned = Vec::with_capacity(groups.len());
+    for group in groups {
+        let line = anchors[group.clone()]
+            .iter()
+            .flatten()
+            .min()
+            .copied()
+            .unwrap_or_else(|| {
+                let next_anchored = anchored.range(previous + 1..).next().copied();
+                (previous + 1..next_anchored.unwrap_or(lines.len()))
+                    .find(|l| is_statement(lines[*l]) && !used.contains(l))
+                    .unwrap_or(previous)
+            });
+        if anchors[group.clone()].iter().all(Option::is_none) {
+            used.insert(line);
+        }
+        previous = line;
+        assigned.push(line);
+    }
+    assigned
+}
+
+/// Whether the line contains code that produces instructions,
+/// as opposed to braces, `else`, comments or empty lines.
+fn is_statement(line: &str) -> bool {
+    let line = line.trim();
+    !(line.is_empty()
+        || line.starts_with("//")
+        || matches!(line, "{" | "}" | "};" | "else" | "} else {" | "else {"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assign_lines, is_statement, parse_trace};
+
+    #[test]
+    fn parses_the_trace_before_the_source() {
+        let (trace, source) = parse_trace("2\n0 3\n1 7\nx = 1;\ny = x;\n").unwrap();
+        assert_eq!(trace, [(0, 3), (1, 7)]);
+        assert_eq!(source, "x = 1;\ny = x;\n");
+        assert!(parse_trace("2\n0 3\n").is_err());
+    }
+
+    #[test]
+    fn groups_follow_their_references() {
+        let lines = ["x = 1;", "if (x)", "{", "    exit;", "}", "y = 2;"];
+        // pushi 1, pop x | push x, bf | exit | pushi 2, pop y
+        let groups = [0..2, 2..4, 4..5, 5..7];
+        let anchors = [None, Some(0), Some(1), None, None, None, Some(5)];
+        assert_eq!(assign_lines(&lines, &groups, &anchors), [0, 1, 3, 5]);
+    }
+
+    #[test]
+    fn groups_may_be_listed_out_of_order() {
+        // The increment of a `for` loop is compiled after its body.
+        let lines = ["for (i = 0; i < 3; i++)", "{", "    f(i);", "}"];
+        let groups = [0..2, 2..5, 5..8, 8..12, 12..13];
+        let mut anchors = [None; 13];
+        anchors[1] = Some(0);
+        anchors[2] = Some(0);
+        anchors[5] = Some(2);
+        anchors[8] = Some(0);
+        anchors[11] = Some(0);
+        assert_eq!(assign_lines(&lines, &groups, &anchors), [0, 0, 2, 0, 0]);
+    }
+
+    #[test]
+    fn skips_structural_lines() {
+        assert!(is_statement("    if (hp <= 0)"));
+        assert!(!is_statement("    {"));
+        assert!(!is_statement("    } else {"));
+        assert!(!is_statement("    // comment"));
+    }
+}
@@ -0,0 +1,543 @@
+use std::{collections::HashMap, ops::Range, path::Path, sync::OnceLock, thread};
+
+use libgm::{gml::GMCode, prelude::*};
+use lsp_server::{Connection, ErrorCode, Message, Response};
+use lsp_types::{
+    DocumentSymbol, DocumentSymbolParams, DocumentSymbolResponse, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverContents, HoverParams, HoverProviderCapability, Location,
+    MarkupContent, MarkupKind, OneOf, Position, ReferenceParams, ServerCapabilities,
+    SymbolInformation, SymbolKind, TextDocumentIdentifier, TextDocumentPositionParams, Url,
+    WorkspaceSymbolParams, WorkspaceSymbolResponse,
+    request::{
+        DocumentSymbolRequest, GotoDefinition, HoverRequest, References, Request as LspRequest,
+        WorkspaceSymbolRequest,
+    },
+};
+
+use crate::{
+    BuiltinFunction, BuiltinVariable, GameContext,
+    extract::find_function,
+    function_lookup::{find_candidates, find_declaration},
+    highlight::{KEYWORDS, tokenize},
+};
+
+/// Base URI of the virtual workspace. Documents are named after their root code entry,
+/// like `gml:///gml_Object_obj_player_Step_0.gml`.
+const WORKSPACE_ROOT: &str = "gml:///";
+
+/// Maximum number of results for workspace symbol queries.
+const MAX_WORKSPACE_SYMBOLS: usize = 1000;
+
+/// Returns the decompiled source of a document, for clients which cannot read `gml:` URIs
+/// themselves (e.g. to implement a VS Code `TextDocumentContentProvider`).
+enum DocumentContent {}
+
+impl LspRequest for DocumentContent {
+    type Params = TextDocumentIdentifier;
+    type Result = Option<String>;
+    const METHOD: &'static str = "underanalyzer/documentContent";
+}
+
+/// Lists all documents of the virtual workspace, one per root code entry.
+enum ListDocuments {}
+
+impl LspRequest for ListDocuments {
+    type Params = ();
+    type Result = Vec<Url>;
+    const METHOD: &'static str = "underanalyzer/documents";
+}
+
+/// Runs a language server on stdin/stdout which presents a data file as a workspace
+/// of decompiled, read-only GML documents (one per root code entry).
+///
+/// Supported requests are go-to-definition (scripts and GMLv2 functions), hover
+/// (assets, builtins, scripts and functions), find-references, document symbols and
+/// workspace symbols (root code entries). Documents use the `gml:` scheme
+/// (see `underanalyzer/documents` and `underanalyzer/documentContent`), but any URI
+/// whose file name is `<code entry name>.gml` works, for example files of a dump.
+/// Locations are returned relative to the URI of the request.
+///
+/// Entries which fail to decompile are shown as disassembly, like in
+/// [`GameContext::decompile_or_disassemble`].
+///
+/// # Errors
+/// This function fails if the data file cannot be loaded,
+/// if the [`GameContext`] cannot be built or if the connection to the client fails.
+pub fn serve_language_server(data_path: impl AsRef<Path>) -> Result<()> {
+    let data: GMData = libgm::parse_file(data_path)?;
+    let workspace = Workspace::new(&data, GameContext::new(&data)?);
+
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = ServerCapabilities {
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
+        ..ServerCapabilities::default()
+    };
+    let capabilities = serde_json::to_value(capabilities)
+        .map_err(|e| libgm::Error::new(format!("serializing server capabilities: {e}")))?;
+    connection
+        .initialize(capabilities)
+        .map_err(|e| libgm::Error::new(format!("initializing language server: {e}")))?;
+
+    for message in &connection.receiver {
+        let Message::Request(request) = message else {
+            // Documents are read-only, so notifications (like edits) are irrelevant
+            continue;
+        };
+        let shutdown = connection
+            .handle_shutdown(&request)
+            .map_err(|e| libgm::Error::new(format!("shutting down language server: {e}")))?;
+        if shutdown {
+            break;
+        }
+        let response = workspace.handle(&request);
+        connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| libgm::Error::new(format!("sending response: {e}")))?;
+    }
+
+    drop(connection);
+    io_threads
+        .join()
+        .map_err(|e| libgm::Error::new(format!("closing language server connection: {e}")))?;
+    Ok(())
+}
+
+fn dispatch<R: LspRequest>(
+    request: &lsp_server::Request,
+    handler: impl FnOnce(R::Params) -> R::Result,
+) -> Response {
+    match serde_json::from_value::<R::Params>(request.params.clone()) {
+        Ok(params) => Response::new_ok(request.id.clone(), handler(params)),
+        Err(e) => Response::new_err(
+            request.id.clone(),
+            ErrorCode::InvalidParams as i32,
+            e.to_string(),
+        ),
+    }
+}
+
+struct Workspace<'a> {
+    data: &'a GMData,
+    ctx: GameContext<'a>,
+    /// Index in `data.codes` of every root code entry, by name.
+    roots: HashMap<&'a str, usize>,
+    /// Decompiled source (or disassembly) of each root entry, indexed like `data.codes`.
+    sources: Vec<OnceLock<String>>,
+}
+
+impl<'a> Workspace<'a> {
+    fn new(data: &'a GMData, ctx: GameContext<'a>) -> Self {
+        let codes: &[GMCode] = data.codes.elements();
+        let roots = codes
+            .iter()
+            .enumerate()
+            .filter(|(_, code)| code.is_root())
+            .map(|(i, code)| (code.name.as_str(), i))
+            .collect();
+        let sources = codes.iter().map(|_| OnceLock::new()).collect();
+        Self {
+            data,
+            ctx,
+            roots,
+            sources,
+        }
+    }
+
+    fn handle(&self, request: &lsp_server::Request) -> Response {
+        match request.method.as_str() {
+            GotoDefinition::METHOD => dispatch::<GotoDefinition>(request, |p| self.definition(&p)),
+            HoverRequest::METHOD => dispatch::<HoverRequest>(request, |p| self.hover(&p)),
+            References::METHOD => dispatch::<References>(request, |p| self.references(&p)),
+            DocumentSymbolRequest::METHOD => {
+                dispatch::<DocumentSymbolRequest>(request, |p| self.document_symbols(&p))
+            }
+            WorkspaceSymbolRequest::METHOD => {
+                dispatch::<WorkspaceSymbolRequest>(request, |p| Some(self.workspace_symbols(&p)))
+            }
+            DocumentContent::METHOD => dispatch::<DocumentContent>(request, |p| {
+                self.document(&p.uri).map(|(_, source)| source.to_owned())
+            }),
+            ListDocuments::METHOD => dispatch::<ListDocuments>(request, |()| {
+                let mut names: Vec<&str> = self.roots.keys().copied().collect();
+                names.sort_unstable();
+                names.into_iter().filter_map(workspace_uri).collect()
+            }),
+            _ => Response::new_err(
+                request.id.clone(),
+                ErrorCode::MethodNotFound as i32,
+                format!("Unknown method {:?}", request.method),
+            ),
+        }
+    }
+
+    fn source(&self, index: usize) -> &str {
+        self.sources[index].get_or_init(|| {
+            self.ctx
+                .decompile_or_disassemble(GMRef::from(index), self.data)
+                .into_output()
+        })
+    }
+
+    /// Decompiles all root entries which are not cached yet, spread over all CPU cores.
+    fn decompile_all(&self) {
+        let threads: usize = thread::available_parallelism().map_or(1, usize::from);
+        let indices: Vec<usize> = self.roots.values().copied().collect();
+        thread::scope(|scope| {
+            for chunk in indices.chunks(indices.len().div_ceil(threads).max(1)) {
+                scope.spawn(move || {
+                    for &index in chunk {
+                        self.source(index);
+                    }
+                });
+            }
+        });
+    }
+
+    /// Finds the document for a URI by its file name, returning its code index and source.
+    fn document(&self, uri: &Url) -> Option<(usize, &str)> {
+        let file_name: &str = uri.path_segments()?.next_back()?;
+        let index: usize = *self.roots.get(file_name.strip_suffix(".gml")?)?;
+        Some((index, self.source(index)))
+    }
+
+    fn word_at<'s>(&'s self, params: &'s TextDocumentPositionParams) -> Option<(&'s str, &'s Url)> {
+        let uri = &params.text_document.uri;
+        let (_, source) = self.document(uri)?;
+        let word = identifiers(source)
+            .find(|id| {
+                id.line == params.position.line && id.columns.contains(&params.position.character)
+            })?
+            .name;
+        Some((word, uri))
+    }
+
+    /// Location of the first occurrence of `word` in the given line of a document,
+    /// or the start of the line if it does not occur there.
+    fn location(&self, base: &Url, index: usize, line: usize, word: &str) -> Option<Location> {
+        let uri = base
+            .join(&format!("{}.gml", self.data.codes.elements()[index].name))
+            .ok()?;
+        let line = u32::try_from(line).ok()?;
+        let columns = identifiers(self.source(index))
+            .find(|id| id.line == line && id.name == word)
+            .map_or(0..0, |id| id.columns);
+        Some(Location::new(uri, line_range(line, columns)))
+    }
+
+    fn definition(&self, params: &GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let (word, base) = self.word_at(&params.text_document_position_params)?;
+        let mut locations: Vec<Location> = Vec::new();
+
+        // GMS1 scripts are root entries; GMLv2 scripts are global scripts declaring functions
+        for prefix in ["gml_Script_", "gml_GlobalScript_"] {
+            if let Some(&index) = self.roots.get(format!("{prefix}{word}").as_str()) {
+                locations.extend(self.location(base, index, 0, word));
+            }
+        }
+        for (index, line) in self.function_declarations(word) {
+            locations.extend(self.location(base, index, line, word));
+        }
+
+        (!locations.is_empty()).then_some(GotoDefinitionResponse::Array(locations))
+    }
+
+    /// Code index of the root entry and line of each GMLv2 function declaration with this name.
+    fn function_declarations(&self, name: &str) -> Vec<(usize, usize)> {
+        let Ok(candidates) = find_candidates(self.data, name) else {
+            return Vec::new();
+        };
+        candidates
+            .iter()
+            .filter_map(|candidate| {
+                let index: usize = *self.roots.get(candidate.parent_name.as_str())?;
+                let source = self.source(index);
+                let lines: Range<usize> =
+                    find_declaration(source, candidate.code, candidate.parent, self.data)
+                        .ok()??;
+                Some((index, lines.start))
+            })
+            .collect()
+    }
+
+    fn hover(&self, params: &HoverParams) -> Option<Hover> {
+        let (word, _) = self.word_at(&params.text_document_position_params)?;
+        let value: String = self.describe(word)?;
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: None,
+        })
+    }
+
+    /// Markdown description of an asset, builtin, script or function.
+    fn describe(&self, word: &str) -> Option<String> {
+        if KEYWORDS.contains(&word) {
+            return None;
+        }
+        if let Some((kind, index)) = self.ctx.find_asset(word) {
+            let kind = kind.as_str().replace('_', " ");
+            return Some(format!("**{word}**: {kind} asset (index {index})"));
+        }
+        if let Some(function) = self.ctx.builtin_function(word) {
+            return Some(format!(
+                "**{word}**: builtin function, {}",
+                describe_arguments(function)
+            ));
+        }
+        if let Some(variable) = self.ctx.builtin_variable(word) {
+            return Some(format!(
+                "**{word}**: builtin variable, {}",
+                describe_variable(variable)
+            ));
+        }
+        if let Some(value) = self.ctx.constant(word) {
+            return Some(format!("**{word}**: constant = `{value}`"));
+        }
+
+        let declarations: Vec<String> = self
+            .function_declarations(word)
+            .into_iter()
+            .filter_map(|(index, line)| {
+                let declaration = self.source(index).lines().nth(line)?.trim();
+                let parent = &self.data.codes.elements()[index].name;
+                Some(format!(
+                    "```gml\n{declaration}\n```\nDeclared in `{parent}`"
+                ))
+            })
+            .collect();
+        if !declarations.is_empty() {
+            return Some(declarations.join("\n\n---\n\n"));
+        }
+        if self
+            .roots
+            .contains_key(format!("gml_Script_{word}").as_str())
+        {
+            return Some(format!("**{word}**: script"));
+        }
+        None
+    }
+
+    fn references(&self, params: &ReferenceParams) -> Option<Vec<Location>> {
+        let (word, base) = self.word_at(&params.text_document_position)?;
+        self.decompile_all();
+
+        let mut indices: Vec<(&str, usize)> = self.roots.iter().map(|(&n, &i)| (n, i)).collect();
+        indices.sort_unstable();
+        let mut locations: Vec<Location> = Vec::new();
+        for (name, index) in indices {
+            let Ok(uri) = base.join(&format!("{name}.gml")) else {
+                continue;
+            };
+            for id in identifiers(self.source(index)).filter(|id| id.name == word) {
+                locations.push(Location::new(uri.clone(), line_range(id.line, id.columns)));
+            }
+        }
+        Some(locations)
+    }
+
+    fn document_symbols(&self, params: &DocumentSymbolParams) -> Option<DocumentSymbolResponse> {
+        let (_, source) = self.document(&params.text_document.uri)?;
+        let lines: Vec<&str> = source.lines().collect();
+
+        let mut symbols: Vec<DocumentSymbol> = Vec::new();
+        for (number, line) in lines.iter().enumerate() {
+            let Some((name, kind)) = declaration(line) else {
+                continue;
+            };
+            // The first declaration with this name may be another one
+            let body: Option<Range<usize>> = if kind == SymbolKind::FUNCTION {
+                find_function(source, name).filter(|body| body.start == number)
+            } else {
+                None
+            };
+            let body: Range<usize> = body.unwrap_or(number..number + 1);
+            let Ok(start) = u32::try_from(number) else {
+                break;
+            };
+            let end = u32::try_from(body.end - 1).unwrap_or(start);
+            let columns = identifiers(line)
+                .find(|id| id.name == name)
+                .map_or(0..0, |id| id.columns);
+
+            #[allow(deprecated)]
+            symbols.push(DocumentSymbol {
+                name: name.to_owned(),
+                detail: None,
+                kind,
+                tags: None,
+                deprecated: None,
+                range: lsp_types::Range::new(
+                    Position::new(start, 0),
+                    Position::new(end, utf16_len(lines[body.end - 1])),
+                ),
+                selection_range: line_range(start, columns),
+                children: None,
+            });
+        }
+        Some(DocumentSymbolResponse::Nested(symbols))
+    }
+
+    fn workspace_symbols(&self, params: &WorkspaceSymbolParams) -> WorkspaceSymbolResponse {
+        let query: String = params.query.to_lowercase();
+        let mut names: Vec<&str> = self
+            .roots
+            .keys()
+            .copied()
+            .filter(|name| name.to_lowercase().contains(&query))
+            .collect();
+        names.sort_unstable();
+        names.truncate(MAX_WORKSPACE_SYMBOLS);
+
+        let symbols = names
+            .into_iter()
+            .filter_map(|name| {
+                let uri = workspace_uri(name)?;
+                #[allow(deprecated)]
+                Some(SymbolInformation {
+                    name: name.to_owned(),
+                    kind: SymbolKind::FILE,
+                    tags: None,
+                    deprecated: None,
+                    location: Location::new(uri, line_range(0, 0..0)),
+                    container_name: None,
+                })
+            })
+            .collect();
+        WorkspaceSymbolResponse::Flat(symbols)
+    }
+}
+
+/// URI of the document for a root code entry in the virtual workspace.
+fn workspace_uri(name: &str) -> Option<Url> {
+    Url::parse(WORKSPACE_ROOT)
+        .ok()?
+        .join(&format!("{name}.gml"))
+        .ok()
+}
+
+fn describe_arguments(function: BuiltinFunction) -> String {
+    match (function.min_args, function.max_args) {
+        (1, Some(1)) => "1 argument".to_owned(),
+        (min, Some(max)) if min == max => format!("{min} arguments"),
+        (min, Some(max)) => format!("{min} to {max} arguments"),
+        (min, None) => format!("at least {min} arguments"),
+    }
+}
+
+fn describe_variable(variable: BuiltinVariable) -> String {
+    let mut traits: Vec<&str> = vec![if variable.can_set {
+        "writable"
+    } else {
+        "read-only"
+    }];
+    if variable.is_global {
+        traits.push("global");
+    }
+    if variable.is_automatic_array {
+        traits.push("array");
+    }
+    traits.join(", ")
+}
+
+/// An identifier token in GML source, with its position in UTF-16 code units
+/// (the default position encoding of LSP).
+struct Identifier<'s> {
+    name: &'s str,
+    line: u32,
+    columns: Range<u32>,
+}
+
+fn identifiers(source: &str) -> impl Iterator<Item = Identifier<'_>> {
+    let mut line: u32 = 0;
+    let mut line_start: usize = 0;
+    let mut offset: usize = 0;
+    tokenize(source).filter_map(move |(token, class)| {
+        let start = offset;
+        offset += token.len();
+        let identifier = (class.is_none()
+            && token.starts_with(|c: char| c.is_alphabetic() || c == '_'))
+        .then(|| {
+            let column = utf16_len(&source[line_start..start]);
+            Identifier {
+                name: token,
+                line,
+                columns: column..column + utf16_len(token),
+            }
+        });
+        for (i, _) in token.match_indices('\n') {
+            line += 1;
+            line_start = start + i + 1;
+        }
+        identifier
+    })
+}
+
+fn utf16_len(text: &str) -> u32 {
+    u32::try_from(text.encode_utf16().count()).unwrap_or(u32::MAX)
+}
+
+fn line_range(line: u32, columns: Range<u32>) -> lsp_types::Range {
+    lsp_types::Range::new(
+        Position::new(line, columns.start),
+        Position::new(line, columns.end),
+    )
+}
+
+/// Recognizes the declaration of a function or enum on a line of decompiled GML.
+fn declaration(line: &str) -> Option<(&str, SymbolKind)> {
+    let line = line.trim_start();
+    let line = line.strip_prefix("static ").unwrap_or(line);
+    let (name, kind) = if let Some(rest) = line.strip_prefix("function ") {
+        (rest.split('(').next()?, SymbolKind::FUNCTION)
+    } else if let Some((name, _)) = line.split_once(" = function(") {
+        (name, SymbolKind::FUNCTION)
+    } else if let Some(rest) = line.strip_prefix("enum ") {
+        (rest.trim_end(), SymbolKind::ENUM)
+    } else {
+        return None;
+    };
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    (!name.is_empty() && name.chars().all(is_ident)).then_some((name, kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::SymbolKind;
+
+    use super::{declaration, identifiers};
+
+    #[test]
+    fn finds_identifier_positions() {
+        let source = "var s = \"hp\"; // hp\nhp = 2;\n/* hp\n*/ é = hp;";
+        let positions: Vec<(u32, u32)> = identifiers(source)
+            .filter(|id| id.name == "hp")
+            .map(|id| (id.line, id.columns.start))
+            .collect();
+        assert_eq!(positions, [(1, 0), (3, 7)]);
+    }
+
+    #[test]
+    fn recognizes_declarations() {
+        assert_eq!(
+            declaration("function scr_move(dx, dy)"),
+            Some(("scr_move", SymbolKind::FUNCTION))
+        );
+        assert_eq!(
+            declaration("    static update = function()"),
+            Some(("update", SymbolKind::FUNCTION))
+        );
+        assert_eq!(
+            declaration("enum Colors"),
+            Some(("Colors", SymbolKind::ENUM))
+        );
+        assert_eq!(declaration("x = scr_function(1);"), None);
+        assert_eq!(declaration("self.a.b = function()"), None);
+    }
+}
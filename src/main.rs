@@ -1,17 +1,198 @@
-use libgm::prelude::{GMListChunk, GMRef};
-use underanalyzer::GameContext;
+use std::io::IsTerminal;
+
+use libgm::prelude::{GMData, GMListChunk, GMRef};
+use underanalyzer::{Decompilation, GameContext, ImportReport, OutputFormat};
+
+/// Colors the output when printing to a terminal, unless `NO_COLOR` is set.
+fn output_format() -> OutputFormat {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    if std::io::stdout().is_terminal() && !no_color {
+        OutputFormat::Ansi
+    } else {
+        OutputFormat::Plain
+    }
+}
+
+/// `underanalyzer listing <data.win> <code>`
+fn run_listing(mut args: impl Iterator<Item = String>) -> libgm::Result<()> {
+    let (Some(data_file_path), Some(code_name), None) = (args.next(), args.next(), args.next())
+    else {
+        return Err("Usage: underanalyzer listing <data file> <code entry name>".into());
+    };
+
+    let data = libgm::parse_file(data_file_path)?;
+    let ctx = GameContext::new(&data)?;
+    let code_ref = data.codes.ref_by_name(&code_name)?;
+    if let Some(owner) = underanalyzer::build_owner_index(&data).describe(code_ref, &data) {
+        println!("// {owner}");
+    }
+    print!("{}", ctx.listing(code_ref, &data)?);
+    Ok(())
+}
+
+/// `underanalyzer callgraph <data.win> [dot|graphml|json]`
+fn run_callgraph(mut args: impl Iterator<Item = String>) -> libgm::Result<()> {
+    let usage = "Usage: underanalyzer callgraph <data file> [dot|graphml|json]";
+    let (Some(data_file_path), format, None) = (args.next(), args.next(), args.next()) else {
+        return Err(usage.into());
+    };
+
+    let data = libgm::parse_file(data_file_path)?;
+    let graph = underanalyzer::build_call_graph(&data);
+    match format.as_deref().unwrap_or("dot") {
+        "dot" => print!("{}", graph.to_dot(&data)),
+        "graphml" => print!("{}", graph.to_graphml(&data)),
+        "json" => println!("{:#}", graph.to_json(&data)),
+        _ => return Err(usage.into()),
+    }
+    Ok(())
+}
+
+/// `underanalyzer unused <data.win> [text|json]`
+fn run_unused(mut args: impl Iterator<Item = String>) -> libgm::Result<()> {
+    let usage = "Usage: underanalyzer unused <data file> [text|json]";
+    let (Some(data_file_path), format, None) = (args.next(), args.next(), args.next()) else {
+        return Err(usage.into());
+    };
+
+    let data = libgm::parse_file(data_file_path)?;
+    let ctx = GameContext::new(&data)?;
+    let report = underanalyzer::find_unused_assets(&ctx, &data);
+    match format.as_deref().unwrap_or("text") {
+        "text" => print!("{}", report.to_text(&data)),
+        "json" => println!("{:#}", report.to_json(&data)),
+        _ => return Err(usage.into()),
+    }
+    Ok(())
+}
+
+/// `underanalyzer project <data.win> <dir>`
+fn run_project(mut args: impl Iterator<Item = String>) -> libgm::Result<()> {
+    let (Some(data_file_path), Some(dir), None) = (args.next(), args.next(), args.next()) else {
+        return Err("Usage: underanalyzer project <data file> <output directory>".into());
+    };
+
+    let data = libgm::parse_file(data_file_path)?;
+    let ctx = GameContext::new(&data)?;
+    for code_ref in ctx.export_project(&data, &dir)? {
+        let name = &data.codes.by_ref(code_ref)?.name;
+        eprintln!("Decompilation of {name:?} failed, exported disassembly instead");
+    }
+    Ok(())
+}
+
+/// `underanalyzer import <data.win> <dir> --out <patched.win>`
+fn run_import(mut args: impl Iterator<Item = String>) -> libgm::Result<()> {
+    let usage = "Usage: underanalyzer import <data file> <directory> --out <output file>";
+    let (Some(data_file_path), Some(dir), Some(flag), Some(out), None) = (
+        args.next(),
+        args.next(),
+        args.next(),
+        args.next(),
+        args.next(),
+    ) else {
+        return Err(usage.into());
+    };
+    if flag != "--out" {
+        return Err(usage.into());
+    }
+
+    let mut data = libgm::parse_file(data_file_path)?;
+    let report = underanalyzer::import_directory(&mut data, &dir)?;
+    write_imported(&report, &data, &out)
+}
+
+/// `underanalyzer patch apply <data.win> <patch>... --out <patched.win>`
+fn run_patch(args: impl Iterator<Item = String>) -> libgm::Result<()> {
+    let usage = "Usage: underanalyzer patch apply <data file> <patch file>... --out <output file>";
+    let args: Vec<String> = args.collect();
+    let [command, data_file_path, rest @ ..] = args.as_slice() else {
+        return Err(usage.into());
+    };
+    let [patch_paths @ .., flag, out] = rest else {
+        return Err(usage.into());
+    };
+    if command != "apply" || flag != "--out" || patch_paths.is_empty() {
+        return Err(usage.into());
+    }
+
+    let mut patch = underanalyzer::Patch::default();
+    for path in patch_paths {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| libgm::Error::new(format!("reading {path}: {e}")))?;
+        let parsed = underanalyzer::Patch::parse(&text)
+            .map_err(|e| e.push_context(&format!("parsing {path}")))?;
+        patch.hunks.extend(parsed.hunks);
+    }
+
+    let mut data = libgm::parse_file(data_file_path)?;
+    let report = underanalyzer::apply_patch(&mut data, &patch)?;
+    write_imported(&report, &data, out)
+}
+
+/// Prints the outcome of an import and writes the data file, unless anything failed to compile.
+fn write_imported(report: &ImportReport, data: &GMData, out: &str) -> libgm::Result<()> {
+    if !report.errors.is_empty() {
+        for (name, messages) in &report.errors {
+            for message in messages {
+                eprintln!("{name}: {message}");
+            }
+        }
+        return Err(libgm::Error::new(format!(
+            "{} code entries failed to compile, nothing was written",
+            report.errors.len()
+        )));
+    }
+    for name in &report.replaced {
+        eprintln!("Replaced {name}");
+    }
+    for name in &report.created {
+        eprintln!("Created {name}");
+    }
+    eprintln!("{} code entries unchanged", report.unchanged);
+    libgm::write_file(data, out)
+}
+
+/// `underanalyzer serve <data.win>`
+fn run_server(mut args: impl Iterator<Item = String>) -> libgm::Result<()> {
+    let (Some(data_file_path), None) = (args.next(), args.next()) else {
+        return Err("Usage: underanalyzer serve <data file>".into());
+    };
+    underanalyzer::serve(data_file_path, std::io::stdin().lock(), std::io::stdout())
+}
+
+/// `underanalyzer lsp <data.win>`
+fn run_language_server(mut args: impl Iterator<Item = String>) -> libgm::Result<()> {
+    let (Some(data_file_path), None) = (args.next(), args.next()) else {
+        return Err("Usage: underanalyzer lsp <data file>".into());
+    };
+    underanalyzer::serve_language_server(data_file_path)
+}
 
 fn run() -> libgm::Result<()> {
     let mut args = std::env::args().skip(1);
     let data_file_path = args
         .next()
         .ok_or("Please specify data file path via commandline")?;
+    match data_file_path.as_str() {
+        "listing" => return run_listing(args),
+        "callgraph" => return run_callgraph(args),
+        "unused" => return run_unused(args),
+        "project" => return run_project(args),
+        "import" => return run_import(args),
+        "patch" => return run_patch(args),
+        "serve" => return run_server(args),
+        "lsp" => return run_language_server(args),
+        _ => {}
+    }
     if args.next().is_some() {
         return Err("Only expected one commandline argument".into());
     }
 
     let data = libgm::parse_file(data_file_path)?;
     let ctx = GameContext::new(&data)?;
+    let format = output_format();
+    let owners = underanalyzer::build_owner_index(&data);
 
     for i in 0..data.codes.len() {
         let code_ref = GMRef::from(i);
@@ -21,16 +202,27 @@ fn run() -> libgm::Result<()> {
             continue;
         }
 
-        let output = match ctx.decompile(code_ref, &data) {
-            Ok(str) => str,
-            Err(e) => {
-                println!("Decompilation of {name:?} failed:\n{}", e.chain_pretty());
-                // This can easily be changed between `continue` and `break` depending on needs.
-                break;
+        // Failed entries are printed as disassembly instead of aborting the dump.
+        // Only GML is highlighted; a full disassembly is printed as is.
+        let output = match ctx.decompile_or_disassemble(code_ref, &data) {
+            Decompilation::Decompiled(source) => ctx.highlight(&source, format),
+            Decompilation::Partial { output, error } => {
+                eprintln!(
+                    "Decompilation of {name:?} failed partially: {}",
+                    error.chain_pretty()
+                );
+                ctx.highlight(&output, format)
+            }
+            Decompilation::Disassembled { output, error } => {
+                eprintln!("Decompilation of {name:?} failed: {}", error.chain_pretty());
+                output
             }
         };
 
-        println!("Decompilation of {name:?}:\n{output}\n");
+        match owners.describe(code_ref, &data) {
+            Some(owner) => println!("Decompilation of {name:?} ({owner}):\n{output}\n"),
+            None => println!("Decompilation of {name:?}:\n{output}\n"),
+        }
     }
 
     Ok(())
@@ -0,0 +1,542 @@
+use libgm::{gml::GMCode, prelude::*};
+
+/// The type of an object event, in the order of the object's event lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EventType {
+    Create,
+    Destroy,
+    Alarm,
+    Step,
+    Collision,
+    Keyboard,
+    Mouse,
+    Other,
+    Draw,
+    KeyPress,
+    KeyRelease,
+    Trigger,
+    CleanUp,
+    Gesture,
+    PreCreate,
+}
+
+impl EventType {
+    /// All event types, indexed by their numeric value.
+    pub const ALL: [Self; 15] = [
+        Self::Create,
+        Self::Destroy,
+        Self::Alarm,
+        Self::Step,
+        Self::Collision,
+        Self::Keyboard,
+        Self::Mouse,
+        Self::Other,
+        Self::Draw,
+        Self::KeyPress,
+        Self::KeyRelease,
+        Self::Trigger,
+        Self::CleanUp,
+        Self::Gesture,
+        Self::PreCreate,
+    ];
+
+    #[must_use]
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+
+    /// The name used in code entry names, e.g. `KeyPress` in `gml_Object_obj_player_KeyPress_32`.
+    #[must_use]
+    pub const fn code_name(self) -> &'static str {
+        match self {
+            Self::Create => "Create",
+            Self::Destroy => "Destroy",
+            Self::Alarm => "Alarm",
+            Self::Step => "Step",
+            Self::Collision => "Collision",
+            Self::Keyboard => "Keyboard",
+            Self::Mouse => "Mouse",
+            Self::Other => "Other",
+            Self::Draw => "Draw",
+            Self::KeyPress => "KeyPress",
+            Self::KeyRelease => "KeyRelease",
+            Self::Trigger => "Trigger",
+            Self::CleanUp => "CleanUp",
+            Self::Gesture => "Gesture",
+            Self::PreCreate => "PreCreate",
+        }
+    }
+}
+
+/// An object event: its type and subtype (e.g. the alarm number or the key code).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Event {
+    pub kind: EventType,
+    pub subtype: u32,
+}
+
+impl Event {
+    /// The event name as shown in the GameMaker IDE, e.g. `Begin Step`, `Alarm 3`,
+    /// `Key Press Space`, `User Event 0` or `Collision with obj_enemy`.
+    ///
+    /// Unknown subtypes are shown as numbers.
+    #[must_use]
+    pub fn describe(&self, data: &GMData) -> String {
+        let subtype = self.subtype;
+        let known: Option<&str> = match self.kind {
+            EventType::Create => Some("Create"),
+            EventType::Destroy => Some("Destroy"),
+            EventType::CleanUp => Some("Clean Up"),
+            EventType::PreCreate => Some("Pre-Create"),
+            EventType::Alarm => return format!("Alarm {subtype}"),
+            EventType::Step => match subtype {
+                0 => Some("Step"),
+                1 => Some("Begin Step"),
+                2 => Some("End Step"),
+                _ => None,
+            },
+            EventType::Collision => {
+                let object = data
+                    .game_objects
+                    .elements()
+                    .get(subtype as usize)
+                    .map_or_else(|| format!("object {subtype}"), |o| o.name().to_owned());
+                return format!("Collision with {object}");
+            }
+            EventType::Keyboard => return format!("Keyboard {}", key_name(subtype)),
+            EventType::KeyPress => return format!("Key Press {}", key_name(subtype)),
+            EventType::KeyRelease => return format!("Key Release {}", key_name(subtype)),
+            EventType::Mouse => mouse_event(subtype),
+            EventType::Other => match subtype {
+                10..=25 => return format!("User Event {}", subtype - 10),
+                40..=47 => return format!("Outside View {}", subtype - 40),
+                50..=57 => return format!("Intersect View {} Boundary", subtype - 50),
+                _ => other_event(subtype),
+            },
+            EventType::Draw => draw_event(subtype),
+            EventType::Trigger => return format!("Trigger {subtype}"),
+            EventType::Gesture => gesture_event(subtype),
+        };
+        known.map_or_else(
+            || format!("{} {subtype}", self.kind.code_name()),
+            str::to_owned,
+        )
+    }
+}
+
+fn key_name(key: u32) -> String {
+    let name = match key {
+        0 => "No Key",
+        1 => "Any Key",
+        8 => "Backspace",
+        9 => "Tab",
+        13 => "Enter",
+        16 => "Shift",
+        17 => "Control",
+        18 => "Alt",
+        19 => "Pause",
+        27 => "Escape",
+        32 => "Space",
+        33 => "Page Up",
+        34 => "Page Down",
+        35 => "End",
+        36 => "Home",
+        37 => "Left",
+        38 => "Up",
+        39 => "Right",
+        40 => "Down",
+        45 => "Insert",
+        46 => "Delete",
+        48..=57 | 65..=90 => return char::from_u32(key).map_or_else(String::new, String::from),
+        96..=105 => return format!("Numpad {}", key - 96),
+        106 => "Numpad *",
+        107 => "Numpad +",
+        109 => "Numpad -",
+        110 => "Numpad .",
+        111 => "Numpad /",
+        112..=123 => return format!("F{}", key - 111),
+        _ => return format!("Key {key}"),
+    };
+    name.to_owned()
+}
+
+const fn mouse_event(subtype: u32) -> Option<&'static str> {
+    Some(match subtype {
+        0 => "Left Button",
+        1 => "Right Button",
+        2 => "Middle Button",
+        3 => "No Button",
+        4 => "Left Pressed",
+        5 => "Right Pressed",
+        6 => "Middle Pressed",
+        7 => "Left Released",
+        8 => "Right Released",
+        9 => "Middle Released",
+        10 => "Mouse Enter",
+        11 => "Mouse Leave",
+        50 => "Global Left Button",
+        51 => "Global Right Button",
+        52 => "Global Middle Button",
+        53 => "Global Left Pressed",
+        54 => "Global Right Pressed",
+        55 => "Global Middle Pressed",
+        56 => "Global Left Released",
+        57 => "Global Right Released",
+        58 => "Global Middle Released",
+        60 => "Mouse Wheel Up",
+        61 => "Mouse Wheel Down",
+        _ => return None,
+    })
+}
+
+const fn other_event(subtype: u32) -> Option<&'static str> {
+    Some(match subtype {
+        0 => "Outside Room",
+        1 => "Intersect Boundary",
+        2 => "Game Start",
+        3 => "Game End",
+        4 => "Room Start",
+        5 => "Room End",
+        6 => "No More Lives",
+        7 => "Animation End",
+        8 => "End of Path",
+        9 => "No More Health",
+        30 => "Close Button",
+        58 => "Animation Update",
+        59 => "Animation Event",
+        60 => "Async Image Loaded",
+        62 => "Async HTTP",
+        63 => "Async Dialog",
+        66 => "Async In-App Purchase",
+        67 => "Async Cloud",
+        68 => "Async Networking",
+        69 => "Async Steam",
+        70 => "Async Social",
+        71 => "Async Push Notification",
+        72 => "Async Save/Load",
+        73 => "Async Audio Recording",
+        74 => "Async Audio Playback",
+        75 => "Async System",
+        76 => "Broadcast Message",
+        _ => return None,
+    })
+}
+
+const fn draw_event(subtype: u32) -> Option<&'static str> {
+    Some(match subtype {
+        0 => "Draw",
+        64 => "Draw GUI",
+        65 => "Window Resize",
+        72 => "Draw Begin",
+        73 => "Draw End",
+        74 => "Draw GUI Begin",
+        75 => "Draw GUI End",
+        76 => "Pre-Draw",
+        77 => "Post-Draw",
+        _ => return None,
+    })
+}
+
+const fn gesture_event(subtype: u32) -> Option<&'static str> {
+    Some(match subtype {
+        0 => "Tap",
+        1 => "Double Tap",
+        2 => "Drag Start",
+        3 => "Dragging",
+        4 => "Drag End",
+        5 => "Flick",
+        6 => "Pinch Start",
+        7 => "Pinch In",
+        8 => "Pinch Out",
+        9 => "Pinch End",
+        10 => "Rotate Start",
+        11 => "Rotating",
+        12 => "Rotate End",
+        64 => "Global Tap",
+        65 => "Global Double Tap",
+        66 => "Global Drag Start",
+        67 => "Global Dragging",
+        68 => "Global Drag End",
+        69 => "Global Flick",
+        70 => "Global Pinch Start",
+        71 => "Global Pinch In",
+        72 => "Global Pinch Out",
+        73 => "Global Pinch End",
+        74 => "Global Rotate Start",
+        75 => "Global Rotating",
+        76 => "Global Rotate End",
+        _ => return None,
+    })
+}
+
+/// What a root code entry is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeOwner {
+    /// An event of an object.
+    ObjectEvent {
+        object: GMRef<GMGameObject>,
+        event: Event,
+    },
+    /// The creation code of a room.
+    Room { room: GMRef<GMRoom> },
+    /// The creation code (or pre-creation code) of an instance placed in a room.
+    RoomInstance {
+        room: GMRef<GMRoom>,
+        /// Index into the room's instance list.
+        instance: usize,
+        pre_create: bool,
+    },
+    /// A moment of a timeline, executed at the given step.
+    TimelineMoment {
+        timeline: GMRef<GMTimeline>,
+        step: u32,
+    },
+    /// A script; in GMLv2, the global script declaring the script's functions.
+    Script { script: GMRef<GMScript> },
+}
+
+impl CodeOwner {
+    /// A human-readable description, e.g. `obj_player: Collision with obj_enemy`,
+    /// `rm_intro: Creation Code` or `tl_cutscene: Moment 30`.
+    #[must_use]
+    pub fn describe(&self, data: &GMData) -> String {
+        match *self {
+            Self::ObjectEvent { object, event } => {
+                let object = data.game_objects.by_ref(object).map_or("?", |o| o.name());
+                format!("{object}: {}", event.describe(data))
+            }
+            Self::Room { room } => {
+                let room = data.rooms.by_ref(room).map_or("?", |r| r.name());
+                format!("{room}: Creation Code")
+            }
+            Self::RoomInstance {
+                room,
+                instance,
+                pre_create,
+            } => {
+                let Ok(room) = data.rooms.by_ref(room) else {
+                    return format!("?: Instance {instance}");
+                };
+                let code = if pre_create {
+                    "Pre-Creation Code"
+                } else {
+                    "Creation Code"
+                };
+                let Some(instance) = room.game_objects.get(instance) else {
+                    return format!("{}: Instance {instance} {code}", room.name());
+                };
+                let object = data
+                    .game_objects
+                    .by_ref(instance.object_definition)
+                    .map_or("?", |o| o.name());
+                format!(
+                    "{}: Instance {} ({object}) {code}",
+                    room.name(),
+                    instance.instance_id,
+                )
+            }
+            Self::TimelineMoment { timeline, step } => {
+                let timeline = data.timelines.by_ref(timeline).map_or("?", |t| t.name());
+                format!("{timeline}: Moment {step}")
+            }
+            Self::Script { script } => {
+                let script = data.scripts.by_ref(script).map_or("?", |s| s.name());
+                format!("Script {script}")
+            }
+        }
+    }
+}
+
+/// The owner of every code entry. Build it using [`build_owner_index`].
+#[derive(Debug, Clone, Default)]
+pub struct OwnerIndex {
+    /// Indexed like `data.codes`.
+    owners: Vec<Option<CodeOwner>>,
+    /// Whether the owner was inherited from the root entry, indexed like `data.codes`.
+    inherited: Vec<bool>,
+}
+
+/// Walks the event lists of all objects, the creation code of all rooms and their instances,
+/// the moments of all timelines and all scripts to find the owner of each code entry.
+///
+/// Child entries (GMLv2 functions) get the owner of their root entry.
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> libgm::Result<()> {
+/// let data = libgm::parse_file("./data.win")?;
+/// let owners = underanalyzer::build_owner_index(&data);
+/// let code_ref = data.codes.ref_by_name("gml_Object_obj_player_Collision_12")?;
+/// if let Some(owner) = owners.owner(code_ref) {
+///     println!("{}", owner.describe(&data)); // obj_player: Collision with obj_enemy
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn build_owner_index(data: &GMData) -> OwnerIndex {
+    let codes: &[GMCode] = data.codes.elements();
+    let mut index = OwnerIndex {
+        owners: vec![None; codes.len()],
+        inherited: vec![false; codes.len()],
+    };
+
+    for (i, object) in data.game_objects.elements().iter().enumerate() {
+        for (kind, events) in EventType::ALL.iter().zip(&object.events) {
+            for event in events {
+                let owner = CodeOwner::ObjectEvent {
+                    object: GMRef::from(i),
+                    event: Event {
+                        kind: *kind,
+                        subtype: event.subtype,
+                    },
+                };
+                for action in &event.actions {
+                    index.set(action.code, owner);
+                }
+            }
+        }
+    }
+    for (i, room) in data.rooms.elements().iter().enumerate() {
+        let room_ref = GMRef::from(i);
+        index.set(room.creation_code, CodeOwner::Room { room: room_ref });
+        for (instance, object) in room.game_objects.iter().enumerate() {
+            for (code, pre_create) in [
+                (object.creation_code, false),
+                (object.pre_create_code, true),
+            ] {
+                let owner = CodeOwner::RoomInstance {
+                    room: room_ref,
+                    instance,
+                    pre_create,
+                };
+                index.set(code, owner);
+            }
+        }
+    }
+    for (i, timeline) in data.timelines.elements().iter().enumerate() {
+        for moment in &timeline.moments {
+            let owner = CodeOwner::TimelineMoment {
+                timeline: GMRef::from(i),
+                step: moment.step,
+            };
+            for action in &moment.actions {
+                index.set(action.code, owner);
+            }
+        }
+    }
+    for (i, script) in data.scripts.elements().iter().enumerate() {
+        let owner = CodeOwner::Script {
+            script: GMRef::from(i),
+        };
+        index.set(script.code, owner);
+    }
+
+    for (i, code) in codes.iter().enumerate() {
+        let Some(owner) = index.owners[i].filter(|_| code.is_root()) else {
+            continue;
+        };
+        for child in GMCode::find_children(GMRef::from(i), data) {
+            let child = u32::from(child) as usize;
+            if index.owners.get(child) == Some(&None) {
+                index.owners[child] = Some(owner);
+                index.inherited[child] = true;
+            }
+        }
+    }
+    index
+}
+
+impl OwnerIndex {
+    /// Code entries referenced by multiple owners (which GameMaker does not do)
+    /// keep the first one found.
+    fn set(&mut self, code: Option<GMRef<GMCode>>, owner: CodeOwner) {
+        let Some(slot) = code.and_then(|code| self.owners.get_mut(u32::from(code) as usize)) else {
+            return;
+        };
+        if slot.is_none() {
+            *slot = Some(owner);
+        }
+    }
+
+    /// The owner of the given code entry, or its root entry for child entries.
+    /// `None` for code entries nothing refers to.
+    #[must_use]
+    pub fn owner(&self, code: GMRef<GMCode>) -> Option<&CodeOwner> {
+        self.owners.get(u32::from(code) as usize)?.as_ref()
+    }
+
+    /// Whether the given code entry is attached to its owner itself,
+    /// instead of being a child entry of the owner's code.
+    #[must_use]
+    pub fn is_direct(&self, code: GMRef<GMCode>) -> bool {
+        let index = u32::from(code) as usize;
+        self.owner(code).is_some() && !self.inherited.get(index).copied().unwrap_or(false)
+    }
+
+    /// A human-readable description of the owner of the given code entry
+    /// (see [`CodeOwner::describe`]), if it has one.
+    #[must_use]
+    pub fn describe(&self, code: GMRef<GMCode>, data: &GMData) -> Option<String> {
+        self.owner(code).map(|owner| owner.describe(data))
+    }
+
+    /// The event code entries of the given object, sorted by event type and subtype.
+    #[must_use]
+    pub fn object_events(&self, object: GMRef<GMGameObject>) -> Vec<(Event, GMRef<GMCode>)> {
+        let mut events: Vec<(Event, GMRef<GMCode>)> = self
+            .direct_owners()
+            .filter_map(|(code, owner)| match *owner {
+                CodeOwner::ObjectEvent { object: o, event } if o == object => Some((event, code)),
+                _ => None,
+            })
+            .collect();
+        events.sort_by_key(|&(event, code)| (event, u32::from(code)));
+        events
+    }
+
+    /// All code entries attached to an owner directly, with their owner.
+    pub fn direct_owners(&self) -> impl Iterator<Item = (GMRef<GMCode>, &CodeOwner)> {
+        self.owners
+            .iter()
+            .zip(&self.inherited)
+            .enumerate()
+            .filter_map(|(i, (owner, inherited))| {
+                let owner = owner.as_ref().filter(|_| !inherited)?;
+                Some((GMRef::from(i), owner))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libgm::prelude::*;
+
+    use super::{Event, EventType};
+
+    fn describe(kind: EventType, subtype: u32) -> String {
+        Event { kind, subtype }.describe(&GMData::default())
+    }
+
+    #[test]
+    fn event_names() {
+        assert_eq!(describe(EventType::Step, 1), "Begin Step");
+        assert_eq!(describe(EventType::Alarm, 3), "Alarm 3");
+        assert_eq!(describe(EventType::Other, 12), "User Event 2");
+        assert_eq!(describe(EventType::Other, 4), "Room Start");
+        assert_eq!(describe(EventType::KeyPress, 32), "Key Press Space");
+        assert_eq!(describe(EventType::Keyboard, 65), "Keyboard A");
+        assert_eq!(describe(EventType::KeyRelease, 113), "Key Release F2");
+        assert_eq!(describe(EventType::Draw, 64), "Draw GUI");
+        assert_eq!(describe(EventType::Draw, 99), "Draw 99");
+        assert_eq!(describe(EventType::Collision, 5), "Collision with object 5");
+    }
+
+    #[test]
+    fn event_types_by_index() {
+        assert_eq!(EventType::from_index(4), Some(EventType::Collision));
+        assert_eq!(EventType::from_index(14), Some(EventType::PreCreate));
+        assert_eq!(EventType::from_index(15), None);
+        assert_eq!(EventType::KeyPress.code_name(), "KeyPress");
+    }
+}
@@ -0,0 +1,432 @@
+use std::ops::Range;
+
+use libgm::prelude::*;
+
+use crate::{GameContext, ImportReport, extract::find_function, import::import_sources};
+
+/// Minimum similarity (0 to 1) of a context line to the line it matches.
+const MATCH_THRESHOLD: f64 = 0.8;
+
+/// Indentation added to lines appended to a function body,
+/// on top of the indentation of its closing brace.
+const INDENT: &str = "    ";
+
+/// What a [`Hunk`] does with its snippet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    /// Replaces the whole entry (or creates it).
+    Replace,
+    /// Inserts the snippet before the lines matching the context.
+    InsertBefore { context: Vec<String> },
+    /// Inserts the snippet after the lines matching the context.
+    InsertAfter { context: Vec<String> },
+    /// Inserts the snippet at the end of a function's body.
+    AppendToFunction { function: String },
+    /// Appends the snippet (usually a function declaration) to the entry (or creates it).
+    AddFunction,
+}
+
+/// A single change to a root code entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// Name of the root code entry.
+    pub entry: String,
+    pub operation: Operation,
+    /// The GML snippet, without trailing empty lines.
+    pub snippet: String,
+    /// Line number of the hunk's directive in the patch file, starting at 1.
+    pub line: usize,
+}
+
+/// A parsed patch file.
+/// Patches describe code changes as GML snippets, so mods can be distributed
+/// without the game's data file.
+///
+/// A patch is a text file of hunks. Each hunk starts with a directive line naming the root
+/// code entry it changes, followed by the GML snippet (up to the next directive):
+///
+/// ```text
+/// Anything before the first directive is ignored and can describe the patch.
+///
+/// @@ insert-after gml_Object_obj_player_Step_0
+/// @@ match hp -= 1;
+/// if (hp <= 0)
+///     instance_destroy();
+///
+/// @@ append-function gml_GlobalScript_scr_damage scr_damage
+/// show_debug_message("damage dealt");
+///
+/// @@ add-function gml_GlobalScript_scr_damage
+/// function scr_heal(amount)
+/// {
+///     hp += amount;
+/// }
+/// ```
+///
+/// Directives are `replace <entry>`, `insert-before <entry>` and `insert-after <entry>`
+/// (followed by one or more `@@ match <line>` context lines), `append-function <entry> <function>`
+/// and `add-function <entry>`. Context lines are matched fuzzily, ignoring whitespace and
+/// tolerating small differences, so patches keep working after minor game updates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Patch {
+    /// Hunks in the order they are applied.
+    pub hunks: Vec<Hunk>,
+}
+
+impl Patch {
+    /// Parses a patch file; see the [module documentation](self) for the format.
+    ///
+    /// # Errors
+    /// This function fails on unknown directives, missing arguments,
+    /// and `insert-before`/`insert-after` hunks without context.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut hunks: Vec<Hunk> = Vec::new();
+        let mut snippet_lines: Vec<&str> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let Some(directive) = line.strip_prefix("@@ ") else {
+                if !hunks.is_empty() {
+                    snippet_lines.push(line);
+                }
+                continue;
+            };
+            let error =
+                |message: &str| libgm::Error::new(format!("patch line {}: {message}", i + 1));
+
+            let mut words = directive.split_whitespace();
+            let keyword = words.next().unwrap_or_default();
+            if keyword == "match" {
+                let context_line = directive
+                    .trim_start()
+                    .strip_prefix("match")
+                    .unwrap_or_default();
+                let context = match hunks.last_mut().map(|hunk| &mut hunk.operation) {
+                    Some(
+                        Operation::InsertBefore { context } | Operation::InsertAfter { context },
+                    ) if snippet_lines.iter().all(|line| line.trim().is_empty()) => context,
+                    _ => {
+                        return Err(error(
+                            "`match` must directly follow `insert-before` or `insert-after`",
+                        ));
+                    }
+                };
+                context.push(context_line.trim().to_owned());
+                continue;
+            }
+
+            finish_hunk(&mut hunks, &mut snippet_lines)?;
+            let entry = words
+                .next()
+                .ok_or_else(|| error("missing code entry name"))?;
+            let operation = match keyword {
+                "replace" => Operation::Replace,
+                "insert-before" => Operation::InsertBefore {
+                    context: Vec::new(),
+                },
+                "insert-after" => Operation::InsertAfter {
+                    context: Vec::new(),
+                },
+                "append-function" => Operation::AppendToFunction {
+                    function: words
+                        .next()
+                        .ok_or_else(|| error("missing function name"))?
+                        .to_owned(),
+                },
+                "add-function" => Operation::AddFunction,
+                other => return Err(error(&format!("unknown directive {other:?}"))),
+            };
+            if words.next().is_some() {
+                return Err(error("too many arguments"));
+            }
+            hunks.push(Hunk {
+                entry: entry.to_owned(),
+                operation,
+                snippet: String::new(),
+                line: i + 1,
+            });
+        }
+        finish_hunk(&mut hunks, &mut snippet_lines)?;
+        Ok(Self { hunks })
+    }
+}
+
+/// Moves the collected snippet lines into the last hunk and validates it.
+fn finish_hunk(hunks: &mut [Hunk], snippet_lines: &mut Vec<&str>) -> Result<()> {
+    let Some(hunk) = hunks.last_mut() else {
+        return Ok(());
+    };
+    while snippet_lines
+        .last()
+        .is_some_and(|line| line.trim().is_empty())
+    {
+        snippet_lines.pop();
+    }
+    let first = snippet_lines
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .unwrap_or(snippet_lines.len());
+    hunk.snippet = snippet_lines[first..].join("\n");
+    snippet_lines.clear();
+
+    if let Operation::InsertBefore { context } | Operation::InsertAfter { context } =
+        &hunk.operation
+        && context.is_empty()
+    {
+        return Err(libgm::Error::new(format!(
+            "patch line {}: missing `@@ match` context line",
+            hunk.line
+        )));
+    }
+    Ok(())
+}
+
+/// Applies a patch: decompiles every entry it changes, applies its hunks in order,
+/// then compiles and links the results like [`crate::import_directory`].
+///
+/// The data is only modified if every patched entry compiles;
+/// otherwise the compiler's messages are returned in [`ImportReport::errors`].
+///
+/// # Errors
+/// This function fails if a patched entry cannot be decompiled, if a hunk's context
+/// or function cannot be found, or if linking fails (see [`crate::import_directory`]).
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> libgm::Result<()> {
+/// let mut data = libgm::parse_file("./data.win")?;
+/// let patch = underanalyzer::Patch::parse(&std::fs::read_to_string("mod.patch").unwrap())?;
+/// let report = underanalyzer::apply_patch(&mut data, &patch)?;
+/// assert!(report.errors.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+pub fn apply_patch(data: &mut GMData, patch: &Patch) -> Result<ImportReport> {
+    let mut sources: Vec<(String, String)> = Vec::new();
+    {
+        let ctx = GameContext::new(data)?;
+        for hunk in &patch.hunks {
+            let index = match sources.iter().position(|(name, _)| *name == hunk.entry) {
+                Some(index) => index,
+                None => {
+                    let source = current_source(&ctx, data, hunk)?;
+                    sources.push((hunk.entry.clone(), source));
+                    sources.len() - 1
+                }
+            };
+            let patched = apply_hunk(&sources[index].1, hunk).map_err(|message| {
+                libgm::Error::new(format!(
+                    "patch line {}: {message} in {:?}",
+                    hunk.line, hunk.entry
+                ))
+            })?;
+            sources[index].1 = patched;
+        }
+    }
+    import_sources(data, sources)
+}
+
+/// The decompiled source of the hunk's entry, or nothing if it creates the entry.
+fn current_source(ctx: &GameContext, data: &GMData, hunk: &Hunk) -> Result<String> {
+    let code_ref = data
+        .codes
+        .elements()
+        .iter()
+        .position(|code| code.name == hunk.entry)
+        .map(GMRef::from);
+    match code_ref {
+        Some(code_ref) => ctx.decompile(code_ref, data).map_err(|e| {
+            e.push_context(&format!(
+                "patch line {}: decompiling {:?}",
+                hunk.line, hunk.entry
+            ))
+        }),
+        None if matches!(hunk.operation, Operation::Replace | Operation::AddFunction) => {
+            Ok(String::new())
+        }
+        None => Err(libgm::Error::new(format!(
+            "patch line {}: code entry {:?} does not exist",
+            hunk.line, hunk.entry
+        ))),
+    }
+}
+
+/// Applies a single hunk to GML source.
+fn apply_hunk(source: &str, hunk: &Hunk) -> Result<String, String> {
+    let lines: Vec<&str> = source.lines().collect();
+    let snippet = hunk.snippet.as_str();
+    let (at, indent): (usize, String) = match &hunk.operation {
+        Operation::Replace => return Ok(format!("{snippet}\n")),
+        Operation::AddFunction if source.trim().is_empty() => return Ok(format!("{snippet}\n")),
+        Operation::AddFunction => return Ok(format!("{}\n\n{snippet}\n", source.trim_end())),
+        Operation::InsertBefore { context } => {
+            let range = find_context(&lines, context)?;
+            (range.start, indentation(lines[range.start]).to_owned())
+        }
+        Operation::InsertAfter { context } => {
+            let range = find_context(&lines, context)?;
+            (range.end, indentation(lines[range.end - 1]).to_owned())
+        }
+        Operation::AppendToFunction { function } => {
+            let range = find_function(source, function)
+                .ok_or_else(|| format!("function {function:?} not found"))?;
+            let closing = range.end - 1;
+            (closing, format!("{}{INDENT}", indentation(lines[closing])))
+        }
+    };
+
+    let mut output = String::new();
+    for line in &lines[..at] {
+        output += line;
+        output.push('\n');
+    }
+    for line in snippet.lines() {
+        if !line.trim().is_empty() {
+            output += &indent;
+            output += line;
+        }
+        output.push('\n');
+    }
+    for line in &lines[at..] {
+        output += line;
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+fn indentation(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Finds the lines best matching the context lines.
+/// Fails if no lines are similar enough, or if several are equally similar.
+fn find_context(lines: &[&str], context: &[String]) -> Result<Range<usize>, String> {
+    let mut best: Option<(f64, usize)> = None;
+    let mut ambiguous = false;
+    for start in 0..=lines.len().saturating_sub(context.len()) {
+        let window = &lines[start..(start + context.len()).min(lines.len())];
+        if window.len() < context.len() {
+            break;
+        }
+        let score: f64 = window
+            .iter()
+            .zip(context)
+            .map(|(line, context)| similarity(line, context))
+            .fold(1.0, f64::min);
+        if score < MATCH_THRESHOLD {
+            continue;
+        }
+        match best {
+            Some((best_score, _)) if score < best_score => {}
+            Some((best_score, _)) if score == best_score => ambiguous = true,
+            _ => {
+                best = Some((score, start));
+                ambiguous = false;
+            }
+        }
+    }
+    match best {
+        Some(_) if ambiguous => Err(format!("context {:?} matches several places", context[0])),
+        Some((_, start)) => Ok(start..start + context.len()),
+        None => Err(format!("context {:?} not found", context[0])),
+    }
+}
+
+/// How similar two lines of code are, from 0 to 1.
+/// Whitespace and trailing semicolons are ignored.
+fn similarity(a: &str, b: &str) -> f64 {
+    let simplify = |line: &str| -> Vec<char> {
+        let line = line.trim().trim_end_matches(';');
+        line.chars().filter(|c| !c.is_whitespace()).collect()
+    };
+    let (a, b) = (simplify(a), simplify(b));
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(&a, &b) as f64 / longest as f64
+}
+
+/// Levenshtein distance between two character sequences.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Hunk, Operation, Patch, apply_hunk};
+
+    const SOURCE: &str = "if (hp > 0)\n{\n    hp -= 1;\n    x += speed * 2;\n}\n\nfunction scr_a()\n{\n    return 1;\n}\n";
+
+    fn hunk(operation: Operation, snippet: &str) -> Hunk {
+        Hunk {
+            entry: "gml_Object_o_Step_0".to_owned(),
+            operation,
+            snippet: snippet.to_owned(),
+            line: 1,
+        }
+    }
+
+    #[test]
+    fn parses_hunks() {
+        let text = "My mod\n\n@@ insert-after gml_Object_o_Step_0\n@@ match hp -= 1;\nfoo();\n\n@@ add-function gml_GlobalScript_s\nfunction f() {}\n";
+        let patch = Patch::parse(text).unwrap();
+        assert_eq!(patch.hunks.len(), 2);
+        assert_eq!(
+            patch.hunks[0].operation,
+            Operation::InsertAfter {
+                context: vec!["hp -= 1;".to_owned()]
+            }
+        );
+        assert_eq!(patch.hunks[0].snippet, "foo();");
+        assert_eq!(patch.hunks[0].line, 3);
+        assert_eq!(patch.hunks[1].snippet, "function f() {}");
+
+        assert!(Patch::parse("@@ insert-before gml_a\nfoo();\n").is_err());
+        assert!(Patch::parse("@@ replace gml_a\nfoo();\n@@ match foo();\n").is_err());
+        assert!(Patch::parse("@@ delete gml_a\n").is_err());
+    }
+
+    #[test]
+    fn inserts_with_fuzzy_context() {
+        let context = vec!["hp-=1".to_owned(), "x += speed*3;".to_owned()];
+        let patched =
+            apply_hunk(SOURCE, &hunk(Operation::InsertAfter { context }, "foo();")).unwrap();
+        assert!(patched.contains("    x += speed * 2;\n    foo();\n}"));
+
+        let context = vec!["hp -= 1;".to_owned()];
+        let patched = apply_hunk(
+            SOURCE,
+            &hunk(Operation::InsertBefore { context }, "a();\nb();"),
+        )
+        .unwrap();
+        assert!(patched.contains("{\n    a();\n    b();\n    hp -= 1;"));
+
+        let context = vec!["instance_destroy();".to_owned()];
+        assert!(apply_hunk(SOURCE, &hunk(Operation::InsertAfter { context }, "")).is_err());
+        let context = vec!["}".to_owned()];
+        assert!(apply_hunk(SOURCE, &hunk(Operation::InsertAfter { context }, "")).is_err());
+    }
+
+    #[test]
+    fn appends_to_functions() {
+        let function = "scr_a".to_owned();
+        let patched = apply_hunk(
+            SOURCE,
+            &hunk(Operation::AppendToFunction { function }, "foo();"),
+        )
+        .unwrap();
+        assert!(patched.ends_with("    return 1;\n    foo();\n}\n"));
+
+        let patched = apply_hunk(SOURCE, &hunk(Operation::AddFunction, "function b() {}")).unwrap();
+        assert!(patched.ends_with("}\n\nfunction b() {}\n"));
+    }
+}
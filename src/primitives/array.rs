@@ -6,6 +6,11 @@ pub struct RawArray<T> {
 }
 
 impl<T> RawArray<T> {
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self::from_vec(Vec::new())
+    }
+
     #[must_use]
     pub const fn from_vec(vector: Vec<T>) -> Self {
         const { assert!(size_of::<T>() != 0, "ZSTs are not supported") }
@@ -19,6 +24,20 @@ impl<T> RawArray<T> {
 
         Self { ptr, len, cap }
     }
+
+    #[must_use]
+    pub const fn as_slice(&self) -> &[T] {
+        // SAFETY: This was constructed from a leaked `Vec<T>` in `from_vec`.
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> {
+        let this = std::mem::ManuallyDrop::new(self);
+        // SAFETY: This was constructed from a leaked `Vec<T>` in `from_vec`,
+        // and `ManuallyDrop` keeps `Drop` from freeing it a second time.
+        unsafe { Vec::from_raw_parts(this.ptr.cast_mut(), this.len, this.cap) }
+    }
 }
 
 impl<T> Drop for RawArray<T> {
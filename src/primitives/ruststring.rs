@@ -20,6 +20,12 @@ impl<'a> RustStr<'a> {
             _marker: PhantomData,
         }
     }
+
+    #[must_use]
+    pub const fn as_str(&self) -> &'a str {
+        // SAFETY: This was constructed from a `&'a str` in `from_str`.
+        unsafe { std::str::from_utf8_unchecked(std::slice::from_raw_parts(self.ptr, self.len)) }
+    }
 }
 
 unsafe impl Send for RustStr<'_> {}
@@ -0,0 +1,726 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use libgm::{gml::GMCode, prelude::*};
+use serde_json::{Value, json};
+
+use crate::{
+    Decompilation, GameContext, gamemaker::AssetKind, owner::EventType, primitives::RustStr,
+};
+
+/// Version of the resource format written into `.yy` files (GameMaker 2.3).
+const RESOURCE_VERSION: &str = "1.0";
+
+/// Reference to a resource (or folder) in a `.yy` file.
+fn resource_ref(dir: &str, name: &str) -> Value {
+    json!({ "name": name, "path": format!("{dir}/{name}/{name}.yy") })
+}
+
+fn folder_ref(folder: &str) -> Value {
+    json!({ "name": folder, "path": format!("folders/{folder}.yy") })
+}
+
+/// Adds the fields every resource has to a `.yy` object.
+fn resource(resource_type: &str, name: &str, folder: &str, mut fields: Value) -> Value {
+    if let Value::Object(fields) = &mut fields {
+        fields.insert("parent".to_owned(), folder_ref(folder));
+        fields.insert("resourceVersion".to_owned(), json!(RESOURCE_VERSION));
+        fields.insert("name".to_owned(), json!(name));
+        fields.insert("tags".to_owned(), json!([]));
+        fields.insert("resourceType".to_owned(), json!(resource_type));
+    }
+    fields
+}
+
+/// An empty resource standing in for an asset that is not exported.
+struct Placeholder {
+    resource_type: &'static str,
+    dir: &'static str,
+    folder: &'static str,
+    fields: Value,
+    files: Vec<(String, String)>,
+}
+
+/// The placeholder for an asset of the given kind,
+/// or [`None`] for kinds that are exported with their contents.
+fn placeholder(kind: AssetKind, name: &str) -> Option<Placeholder> {
+    let texture_group = json!({ "name": "Default", "path": "texturegroups/Default" });
+    let (resource_type, dir, folder, fields) = match kind {
+        AssetKind::Object
+        | AssetKind::Room
+        | AssetKind::Script
+        | AssetKind::Timeline
+        | AssetKind::RoomInstance => return None,
+        AssetKind::Sprite | AssetKind::Background => {
+            let folder = if kind == AssetKind::Sprite {
+                "Sprites"
+            } else {
+                "Backgrounds"
+            };
+            let fields = json!({
+                "bboxMode": 0,
+                "collisionKind": 1,
+                "type": 0,
+                "origin": 0,
+                "preMultiplyAlpha": false,
+                "edgeFiltering": false,
+                "collisionTolerance": 0,
+                "swfPrecision": 2.525,
+                "bbox_left": 0,
+                "bbox_right": 0,
+                "bbox_top": 0,
+                "bbox_bottom": 0,
+                "HTile": false,
+                "VTile": false,
+                "For3D": false,
+                "width": 1,
+                "height": 1,
+                "textureGroupId": texture_group,
+                "swatchColours": null,
+                "gridX": 0,
+                "gridY": 0,
+                "frames": [],
+                "sequence": null,
+                "layers": [],
+                "nineSlice": null,
+            });
+            ("GMSprite", "sprites", folder, fields)
+        }
+        AssetKind::Sound => {
+            let fields = json!({
+                "compression": 0,
+                "volume": 1.0,
+                "preload": false,
+                "bitRate": 128,
+                "sampleRate": 44100,
+                "type": 0,
+                "bitDepth": 1,
+                "audioGroupId": {
+                    "name": "audiogroup_default",
+                    "path": "audiogroups/audiogroup_default",
+                },
+                "soundFile": "",
+                "duration": 0.0,
+            });
+            ("GMSound", "sounds", "Sounds", fields)
+        }
+        AssetKind::Path => {
+            let fields = json!({ "kind": 0, "closed": false, "precision": 4, "points": [] });
+            ("GMPath", "paths", "Paths", fields)
+        }
+        AssetKind::Font => {
+            let fields = json!({
+                "fontName": "Arial",
+                "styleName": "Regular",
+                "size": 12.0,
+                "bold": false,
+                "italic": false,
+                "charset": 0,
+                "AntiAlias": 1,
+                "first": 0,
+                "last": 0,
+                "sampleText": "",
+                "includeTTF": false,
+                "TTFName": "",
+                "textureGroupId": texture_group,
+                "ascenderOffset": 0,
+                "glyphs": {},
+                "kerningPairs": [],
+                "ranges": [{ "lower": 32, "upper": 127 }],
+                "regenerateBitmap": false,
+                "canGenerateBitmap": true,
+                "maintainGms1Font": false,
+            });
+            ("GMFont", "fonts", "Fonts", fields)
+        }
+        AssetKind::Shader => {
+            let files = vec![
+                (
+                    format!("{name}.vsh"),
+                    "attribute vec3 in_Position;\n\nvoid main()\n{\n    \
+                     gl_Position = gm_Matrices[MATRIX_WORLD_VIEW_PROJECTION] * \
+                     vec4(in_Position, 1.0);\n}\n"
+                        .to_owned(),
+                ),
+                (
+                    format!("{name}.fsh"),
+                    "void main()\n{\n    gl_FragColor = vec4(1.0);\n}\n".to_owned(),
+                ),
+            ];
+            return Some(Placeholder {
+                resource_type: "GMShader",
+                dir: "shaders",
+                folder: "Shaders",
+                fields: json!({ "type": 1 }),
+                files,
+            });
+        }
+        AssetKind::Sequence => {
+            let keyframes = |kind: &str| {
+                json!({
+                    "Keyframes": [],
+                    "resourceVersion": RESOURCE_VERSION,
+                    "resourceType": format!("KeyframeStore<{kind}>"),
+                })
+            };
+            let fields = json!({
+                "timeUnits": 1,
+                "playback": 1,
+                "playbackSpeed": 30.0,
+                "playbackSpeedType": 0,
+                "autoRecord": true,
+                "volume": 1.0,
+                "length": 60.0,
+                "events": keyframes("MessageEventKeyframe"),
+                "moments": keyframes("MomentsEventKeyframe"),
+                "tracks": [],
+                "visibleRange": null,
+                "lockOrigin": false,
+                "xorigin": 0,
+                "yorigin": 0,
+                "eventToFunction": {},
+                "eventStubScript": null,
+            });
+            ("GMSequence", "sequences", "Sequences", fields)
+        }
+        AssetKind::AnimationCurve => {
+            let fields = json!({ "function": 0, "channels": [] });
+            ("GMAnimCurve", "animcurves", "Animation Curves", fields)
+        }
+        AssetKind::ParticleSystem => {
+            let fields = json!({ "xorigin": 0, "yorigin": 0, "drawOrder": 0, "emitters": [] });
+            ("GMParticleSystem", "particles", "Particle Systems", fields)
+        }
+    };
+    Some(Placeholder {
+        resource_type,
+        dir,
+        folder,
+        fields,
+        files: Vec::new(),
+    })
+}
+
+struct Exporter<'c, 'a> {
+    ctx: &'c GameContext<'a>,
+    data: &'a GMData,
+    dir: PathBuf,
+    /// Entries of the `.yyp` resource list.
+    resources: Vec<Value>,
+    /// Folders used by the resources, in order of first use.
+    folders: Vec<&'static str>,
+    failed: Vec<GMRef<GMCode>>,
+}
+
+impl<'a> Exporter<'_, 'a> {
+    fn write(&self, path: &Path, contents: &str) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                libgm::Error::new(format!("creating directory {}: {e}", parent.display()))
+            })?;
+        }
+        fs::write(path, contents)
+            .map_err(|e| libgm::Error::new(format!("writing {}: {e}", path.display())))
+    }
+
+    /// Writes the `.yy` file and additional files of a resource and adds it to the project.
+    fn add_resource(
+        &mut self,
+        dir: &'static str,
+        folder: &'static str,
+        yy: &Value,
+        files: &[(String, String)],
+    ) -> Result<()> {
+        let name: &str = yy["name"].as_str().unwrap_or_default();
+        let resource_dir: PathBuf = self.dir.join(dir).join(name);
+        let text = serde_json::to_string_pretty(yy)
+            .map_err(|e| libgm::Error::new(format!("serializing {name}.yy: {e}")))?;
+        self.write(&resource_dir.join(format!("{name}.yy")), &text)?;
+        for (file_name, contents) in files {
+            self.write(&resource_dir.join(file_name), contents)?;
+        }
+
+        let order = self.resources.len();
+        self.resources
+            .push(json!({ "id": resource_ref(dir, name), "order": order }));
+        if !self.folders.contains(&folder) {
+            self.folders.push(folder);
+        }
+        Ok(())
+    }
+
+    /// Decompiled GML of a code entry, or the disassembly as comments if that fails.
+    fn gml(&mut self, code_ref: GMRef<GMCode>) -> String {
+        match self.ctx.decompile_or_disassemble(code_ref, self.data) {
+            Decompilation::Decompiled(source) => source,
+            Decompilation::Disassembled { output, .. } | Decompilation::Partial { output, .. } => {
+                self.failed.push(code_ref);
+                output
+            }
+        }
+    }
+
+    fn code_name(&self, code_ref: GMRef<GMCode>) -> &'a str {
+        self.data
+            .codes
+            .by_ref(code_ref)
+            .map_or("", |code| &code.name)
+    }
+
+    fn export_scripts(&mut self) -> Result<()> {
+        let data = self.data;
+        let using_gmlv2: bool = self.ctx.capabilities().using_gmlv2;
+        for script in data.scripts.elements() {
+            let name: &str = script.name();
+            // GMLv2 functions have script entries too, which point at child entries
+            // declared in a global script; those are exported with their global script.
+            let is_root = script
+                .code
+                .and_then(|code| data.codes.by_ref(code).ok())
+                .is_none_or(GMCode::is_root);
+            if !is_root {
+                continue;
+            }
+            let source: String = match script.code {
+                // GMS1 scripts are function bodies; since GameMaker 2.3, scripts declare functions.
+                Some(code) if !using_gmlv2 => {
+                    let body = self.gml(code);
+                    let body: String = body.lines().map(|line| format!("    {line}\n")).collect();
+                    format!("function {name}()\n{{\n{body}}}\n")
+                }
+                Some(code) => self.gml(code),
+                None => String::new(),
+            };
+            let yy = resource(
+                "GMScript",
+                name,
+                "Scripts",
+                json!({ "isDnD": false, "isCompatibility": false }),
+            );
+            self.add_resource(
+                "scripts",
+                "Scripts",
+                &yy,
+                &[(format!("{name}.gml"), source)],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn export_objects(&mut self) -> Result<()> {
+        let data = self.data;
+        for object in data.game_objects.elements() {
+            let name: &str = object.name();
+            let sprite = |sprite: Option<GMRef<GMSprite>>| -> Value {
+                sprite
+                    .and_then(|sprite| data.sprites.by_ref(sprite).ok())
+                    .map_or(Value::Null, |sprite| resource_ref("sprites", sprite.name()))
+            };
+            let parent: Value = object
+                .parent
+                .and_then(|parent| data.game_objects.by_ref(parent).ok())
+                .map_or(Value::Null, |parent| resource_ref("objects", parent.name()));
+
+            let mut events: Vec<Value> = Vec::new();
+            let mut files: Vec<(String, String)> = Vec::new();
+            for (kind, event_list) in EventType::ALL.into_iter().zip(&object.events) {
+                for event in event_list {
+                    let Some(code) = event.actions.iter().find_map(|action| action.code) else {
+                        continue;
+                    };
+                    // Collision events are named after (and reference) the other object
+                    let (file_name, event_num, collision_object) = match kind {
+                        EventType::Collision => {
+                            let other = data
+                                .game_objects
+                                .elements()
+                                .get(event.subtype as usize)
+                                .map_or("", |other| other.name());
+                            (
+                                format!("Collision_{other}.gml"),
+                                0,
+                                resource_ref("objects", other),
+                            )
+                        }
+                        _ => (
+                            format!("{}_{}.gml", kind.code_name(), event.subtype),
+                            event.subtype,
+                            Value::Null,
+                        ),
+                    };
+                    events.push(json!({
+                        "isDnD": false,
+                        "eventNum": event_num,
+                        "eventType": kind as u32,
+                        "collisionObjectId": collision_object,
+                        "parent": resource_ref("objects", name),
+                        "resourceVersion": RESOURCE_VERSION,
+                        "name": "",
+                        "tags": [],
+                        "resourceType": "GMEvent",
+                    }));
+                    files.push((file_name, self.gml(code)));
+                }
+            }
+
+            let yy = resource(
+                "GMObject",
+                name,
+                "Objects",
+                json!({
+                    "spriteId": sprite(object.sprite),
+                    "solid": object.solid,
+                    "visible": object.visible,
+                    "spriteMaskId": sprite(object.texture_mask),
+                    "persistent": object.persistent,
+                    "parentObjectId": parent,
+                    "physicsObject": false,
+                    "eventList": events,
+                    "properties": [],
+                    "overriddenProperties": [],
+                }),
+            );
+            self.add_resource("objects", "Objects", &yy, &files)?;
+        }
+        Ok(())
+    }
+
+    fn export_rooms(&mut self) -> Result<()> {
+        let data = self.data;
+        for room in data.rooms.elements() {
+            let name: &str = room.name();
+            let mut files: Vec<(String, String)> = Vec::new();
+            let creation_code_file: String = match room.creation_code {
+                Some(code) => {
+                    files.push(("RoomCreationCode.gml".to_owned(), self.gml(code)));
+                    format!("rooms/{name}/RoomCreationCode.gml")
+                }
+                None => String::new(),
+            };
+
+            let mut instances: Vec<Value> = Vec::new();
+            for instance in &room.game_objects {
+                let Ok(object) = data.game_objects.by_ref(instance.object_definition) else {
+                    continue;
+                };
+                // Named like Underanalyzer prints room instance references
+                let instance_name = format!("inst_{}", instance.instance_id);
+                if let Some(code) = instance.creation_code {
+                    let file_name = format!("InstanceCreationCode_{instance_name}.gml");
+                    files.push((file_name, self.gml(code)));
+                }
+                instances.push(json!({
+                    "properties": [],
+                    "isDnd": false,
+                    "objectId": resource_ref("objects", object.name()),
+                    "inheritCode": false,
+                    "hasCreationCode": instance.creation_code.is_some(),
+                    "colour": 4_294_967_295_u32,
+                    "rotation": 0.0,
+                    "scaleX": 1.0,
+                    "scaleY": 1.0,
+                    "imageIndex": 0,
+                    "imageSpeed": 1.0,
+                    "inheritedItemId": null,
+                    "frozen": false,
+                    "ignore": false,
+                    "inheritItemSettings": false,
+                    "x": instance.x,
+                    "y": instance.y,
+                    "resourceVersion": RESOURCE_VERSION,
+                    "name": instance_name,
+                    "tags": [],
+                    "resourceType": "GMRInstance",
+                }));
+            }
+            let creation_order: Vec<Value> = instances
+                .iter()
+                .map(|instance| {
+                    json!({ "name": instance["name"], "path": format!("rooms/{name}/{name}.yy") })
+                })
+                .collect();
+            let layer = json!({
+                "instances": instances,
+                "visible": true,
+                "depth": 0,
+                "userdefinedDepth": false,
+                "inheritLayerDepth": false,
+                "inheritLayerSettings": false,
+                "gridX": 32,
+                "gridY": 32,
+                "layers": [],
+                "hierarchyFrozen": false,
+                "resourceVersion": RESOURCE_VERSION,
+                "name": "Instances",
+                "tags": [],
+                "resourceType": "GMRInstanceLayer",
+            });
+
+            let yy = resource(
+                "GMRoom",
+                name,
+                "Rooms",
+                json!({
+                    "isDnd": false,
+                    "volume": 1.0,
+                    "parentRoom": null,
+                    "views": [],
+                    "layers": [layer],
+                    "inheritLayers": false,
+                    "creationCodeFile": creation_code_file,
+                    "inheritCode": false,
+                    "instanceCreationOrder": creation_order,
+                    "inheritCreationOrder": false,
+                    "sequenceId": null,
+                    "roomSettings": {
+                        "inheritRoomSettings": false,
+                        "Width": room.width,
+                        "Height": room.height,
+                        "persistent": false,
+                    },
+                    "viewSettings": {
+                        "inheritViewSettings": false,
+                        "enableViews": false,
+                        "clearViewBackground": false,
+                        "clearDisplayBuffer": true,
+                    },
+                    "physicsSettings": {
+                        "inheritPhysicsSettings": false,
+                        "PhysicsWorld": false,
+                        "PhysicsWorldGravityX": 0.0,
+                        "PhysicsWorldGravityY": 10.0,
+                        "PhysicsWorldPixToMetres": 0.1,
+                    },
+                }),
+            );
+            self.add_resource("rooms", "Rooms", &yy, &files)?;
+        }
+        Ok(())
+    }
+
+    fn export_timelines(&mut self) -> Result<()> {
+        let data = self.data;
+        for timeline in data.timelines.elements() {
+            let name: &str = timeline.name();
+            let mut moments: Vec<Value> = Vec::new();
+            let mut files: Vec<(String, String)> = Vec::new();
+            for moment in &timeline.moments {
+                let Some(code) = moment.actions.iter().find_map(|action| action.code) else {
+                    continue;
+                };
+                files.push((format!("moment_{}.gml", moment.step), self.gml(code)));
+                moments.push(json!({
+                    "moment": moment.step,
+                    "evnt": {
+                        "isDnD": false,
+                        "eventNum": moment.step,
+                        "eventType": 0,
+                        "collisionObjectId": null,
+                        "resourceVersion": RESOURCE_VERSION,
+                        "name": "",
+                        "tags": [],
+                        "resourceType": "GMEvent",
+                    },
+                    "resourceVersion": RESOURCE_VERSION,
+                    "name": "",
+                    "tags": [],
+                    "resourceType": "GMMoment",
+                }));
+            }
+            let yy = resource(
+                "GMTimeline",
+                name,
+                "Timelines",
+                json!({ "momentList": moments }),
+            );
+            self.add_resource("timelines", "Timelines", &yy, &files)?;
+        }
+        Ok(())
+    }
+
+    /// Writes empty resources for every kind of asset code can reference that is not
+    /// exported otherwise, so that references to them resolve.
+    fn export_placeholders(&mut self) -> Result<()> {
+        let (ctx, data) = (self.ctx, self.data);
+        let sprite_names: Vec<&str> = data.sprites.elements().iter().map(|s| s.name()).collect();
+        for (kind, names) in ctx.asset_tables() {
+            for name in names.as_slice().iter().map(RustStr::as_str) {
+                // GameMaker 2 turns backgrounds into sprites, unless the name is taken.
+                if kind == AssetKind::Background && sprite_names.contains(&name) {
+                    continue;
+                }
+                let Some(placeholder) = placeholder(kind, name) else {
+                    continue;
+                };
+                let yy = resource(
+                    placeholder.resource_type,
+                    name,
+                    placeholder.folder,
+                    placeholder.fields,
+                );
+                self.add_resource(placeholder.dir, placeholder.folder, &yy, &placeholder.files)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_project(&self, name: &str) -> Result<()> {
+        let data = self.data;
+        let mut room_order: Vec<&GMRoom> = data
+            .general_info
+            .room_order
+            .iter()
+            .filter_map(|&room| data.rooms.by_ref(room).ok())
+            .collect();
+        if room_order.is_empty() {
+            room_order = data.rooms.elements().iter().collect();
+        }
+        let room_order: Vec<Value> = room_order
+            .into_iter()
+            .map(|room| json!({ "roomId": resource_ref("rooms", room.name()) }))
+            .collect();
+
+        let mut folders: Vec<Value> = Vec::new();
+        for (order, folder) in self.folders.iter().enumerate() {
+            let path = format!("folders/{folder}.yy");
+            folders.push(json!({
+                "folderPath": path,
+                "order": order,
+                "resourceVersion": RESOURCE_VERSION,
+                "name": folder,
+                "tags": [],
+                "resourceType": "GMFolder",
+            }));
+        }
+
+        let yyp = json!({
+            "resources": self.resources,
+            "Options": [],
+            "isDnDProject": false,
+            "isEcma": false,
+            "tutorialPath": "",
+            "configs": { "name": "Default", "children": [] },
+            "RoomOrder": room_order,
+            "Folders": folders,
+            "AudioGroups": [{
+                "targets": -1,
+                "resourceVersion": RESOURCE_VERSION,
+                "name": "audiogroup_default",
+                "resourceType": "GMAudioGroup",
+            }],
+            "TextureGroups": [{
+                "isScaled": true,
+                "autocrop": true,
+                "border": 2,
+                "mipsToGenerate": 0,
+                "groupParent": null,
+                "targets": -1,
+                "resourceVersion": RESOURCE_VERSION,
+                "name": "Default",
+                "resourceType": "GMTextureGroup",
+            }],
+            "IncludedFiles": [],
+            "MetaData": { "IDEVersion": "2.3.0.529" },
+            "resourceVersion": "1.4",
+            "name": name,
+            "tags": [],
+            "resourceType": "GMProject",
+        });
+        let text = serde_json::to_string_pretty(&yyp)
+            .map_err(|e| libgm::Error::new(format!("serializing {name}.yyp: {e}")))?;
+        self.write(&self.dir.join(format!("{name}.yyp")), &text)
+    }
+}
+
+impl GameContext<'_> {
+    /// Exports the game as a GameMaker 2.3+ project into the given directory,
+    /// which is created if needed. Existing files are overwritten.
+    ///
+    /// Scripts, objects (with one `.gml` file per event, e.g. `Step_1.gml` or
+    /// `Collision_obj_enemy.gml`), rooms (with their instances, creation code and
+    /// instance creation code) and timelines are exported with their decompiled GML.
+    /// Objects reference their sprite, mask and parent, and rooms their instances' objects, by name.
+    /// All other assets code can reference (sprites, sounds, backgrounds, paths, fonts, shaders,
+    /// sequences, animation curves and particle systems) are exported as empty placeholders
+    /// so that these references resolve. Since data files do not contain the IDE's folder structure,
+    /// resources are grouped into one folder per resource type.
+    ///
+    /// Scripts of games before GMLv2 are wrapped into a function with the script's name,
+    /// like the IDE does when importing old projects. Script entries of GMLv2 functions
+    /// are not exported separately; the functions are part of their global script.
+    /// Code entries that fail to decompile are exported with (part of) their disassembly
+    /// as comments (see [`GameContext::decompile_or_disassemble`]) and returned.
+    ///
+    /// # Errors
+    /// This function fails if a file cannot be written.
+    pub fn export_project(
+        &self,
+        gm_data: &GMData,
+        dir: impl AsRef<Path>,
+    ) -> Result<Vec<GMRef<GMCode>>> {
+        let dir: &Path = dir.as_ref();
+        let name: String = dir.file_name().map_or_else(
+            || "project".to_owned(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let mut exporter = Exporter {
+            ctx: self,
+            data: gm_data,
+            dir: dir.to_owned(),
+            resources: Vec::new(),
+            folders: Vec::new(),
+            failed: Vec::new(),
+        };
+        exporter.export_scripts()?;
+        exporter.export_objects()?;
+        exporter.export_rooms()?;
+        exporter.export_timelines()?;
+        exporter.export_placeholders()?;
+        exporter.write_project(&name)?;
+        Ok(exporter.failed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{placeholder, resource, resource_ref};
+    use crate::gamemaker::AssetKind;
+
+    #[test]
+    fn resource_fields() {
+        let yy = resource("GMScript", "scr_a", "Scripts", json!({ "isDnD": false }));
+        let expected = json!({
+            "isDnD": false,
+            "parent": { "name": "Scripts", "path": "folders/Scripts.yy" },
+            "resourceVersion": "1.0",
+            "name": "scr_a",
+            "tags": [],
+            "resourceType": "GMScript",
+        });
+        assert_eq!(yy, expected);
+        assert_eq!(
+            resource_ref("objects", "obj_a"),
+            json!({ "name": "obj_a", "path": "objects/obj_a/obj_a.yy" })
+        );
+    }
+
+    #[test]
+    fn placeholders_for_assets_without_contents() {
+        assert!(placeholder(AssetKind::Object, "obj_a").is_none());
+        assert!(placeholder(AssetKind::Script, "scr_a").is_none());
+
+        let background = placeholder(AssetKind::Background, "bg_a").unwrap();
+        assert_eq!(
+            (background.resource_type, background.dir),
+            ("GMSprite", "sprites")
+        );
+
+        let shader = placeholder(AssetKind::Shader, "sh_a").unwrap();
+        let files: Vec<&str> = shader.files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(files, ["sh_a.vsh", "sh_a.fsh"]);
+    }
+}
@@ -0,0 +1,444 @@
+use std::{
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock, PoisonError, mpsc},
+    thread,
+};
+
+use libgm::{gml::GMCode, prelude::*};
+use serde_json::{Value, json};
+
+use crate::{
+    GameContext, OutputFormat,
+    owner::{OwnerIndex, build_owner_index},
+    xref::{self, XrefIndex},
+};
+
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+/// Implementation defined error code, used when the requested operation itself fails.
+const SERVER_ERROR: i32 = -32000;
+
+/// Maximum number of matches returned by `search`, unless the request specifies a `limit`.
+const DEFAULT_SEARCH_LIMIT: usize = 1000;
+
+/// Serves JSON-RPC 2.0 requests for a data file until `input` ends.
+///
+/// Every line of `input` is one request and every line written to `output` is one response.
+/// Requests are handled concurrently by one worker thread per CPU core (further requests wait
+/// in a queue), so responses may arrive in a different order
+/// (match them by their `id`). Requests without an `id` are notifications and get no response.
+///
+/// Methods (parameters are passed by name):
+/// * `decompile {code, format?}`: decompiles the code entry with this name.
+///   `format` is one of `plain` (default), `ansi`, `html` or `css_colors`.
+///   Returns `{source}`.
+/// * `search {query, case_sensitive?, limit?}`: searches the decompiled GML of all root entries.
+///   Returns `{matches: [{code, line, text}], truncated}`, with 1-based line numbers.
+///   Decompiled entries are cached until the next `reload`.
+/// * `list_codes {filter?, roots_only?}`: returns `[{name, root, owner}]` for all code entries
+///   whose name contains `filter`. `owner` describes what the entry belongs to
+///   (e.g. `obj_player: Collision with obj_enemy`, see [`build_owner_index`]) or is `null`.
+/// * `xrefs {name}`: returns `[{code, address, kind}]` for every use of a variable, function
+///   or asset with this name (see [`xref::build`]). `kind` is `read`, `write`, `call`
+///   or `reference`. The index is built on first use and kept until the next `reload`.
+/// * `compile {source}`: compiles GML against this game without changing anything.
+///   Returns `{errors}`, which is empty if the source compiles.
+/// * `reload {path?}`: parses the data file again (or another one, if `path` is given)
+///   and rebuilds the [`GameContext`]. Waits for running requests to finish first.
+///   Returns `{codes}`, the number of code entries. If parsing fails, the old data is kept.
+///
+/// # Errors
+/// This function fails if the data file cannot be loaded initially,
+/// if the [`GameContext`] cannot be built or if reading from `input` fails.
+pub fn serve(
+    data_path: impl AsRef<Path>,
+    input: impl BufRead,
+    output: impl Write + Send,
+) -> Result<()> {
+    let output = Mutex::new(output);
+    let mut path: PathBuf = data_path.as_ref().to_owned();
+    let mut data: GMData = libgm::parse_file(&path)?;
+    let mut lines = input.lines();
+
+    loop {
+        let reload: Option<Request> = {
+            let ctx = GameContext::new(&data)?;
+            let session = Session::new(&data, &ctx, &output);
+            session.run(&mut lines)?
+        };
+        let Some(request) = reload else {
+            return Ok(());
+        };
+
+        let result = reload_path(&request.params, &path).and_then(|new_path| {
+            let new_data = libgm::parse_file(&new_path)?;
+            data = new_data;
+            path = new_path;
+            Ok(json!({ "codes": data.codes.len() }))
+        });
+        respond(&output, request.id, result);
+    }
+}
+
+#[derive(Debug)]
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+impl RpcError {
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<libgm::Error> for RpcError {
+    fn from(error: libgm::Error) -> Self {
+        Self {
+            code: SERVER_ERROR,
+            message: error.chain_pretty(),
+        }
+    }
+}
+
+type RpcResult = std::result::Result<Value, RpcError>;
+
+#[derive(Debug)]
+struct Request {
+    /// `None` for notifications, which are not answered.
+    id: Option<Value>,
+    method: String,
+    params: Value,
+}
+
+fn parse_request(line: &str) -> std::result::Result<Request, RpcError> {
+    let value: Value = serde_json::from_str(line).map_err(|e| RpcError {
+        code: PARSE_ERROR,
+        message: format!("Invalid JSON: {e}"),
+    })?;
+    let Some(method) = value.get("method").and_then(Value::as_str) else {
+        return Err(RpcError {
+            code: INVALID_REQUEST,
+            message: "Request has no method".to_owned(),
+        });
+    };
+    Ok(Request {
+        id: value.get("id").cloned(),
+        method: method.to_owned(),
+        params: value.get("params").cloned().unwrap_or(Value::Null),
+    })
+}
+
+/// Writes the response for a request. Notifications (without `id`) are not answered.
+fn respond(output: &Mutex<impl Write>, id: Option<Value>, result: RpcResult) {
+    let Some(id) = id else {
+        return;
+    };
+    let response = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code, "message": error.message },
+        }),
+    };
+
+    // If writing fails, the client is gone; the server stops once its input ends.
+    let mut output = output.lock().unwrap_or_else(PoisonError::into_inner);
+    let _ = writeln!(output, "{response}").and_then(|()| output.flush());
+}
+
+fn reload_path(params: &Value, current: &Path) -> std::result::Result<PathBuf, RpcError> {
+    Ok(optional_str_param(params, "path")?.map_or_else(|| current.to_owned(), PathBuf::from))
+}
+
+fn str_param<'p>(params: &'p Value, name: &str) -> std::result::Result<&'p str, RpcError> {
+    optional_str_param(params, name)?
+        .ok_or_else(|| RpcError::invalid_params(format!("Missing parameter {name:?}")))
+}
+
+fn optional_str_param<'p>(
+    params: &'p Value,
+    name: &str,
+) -> std::result::Result<Option<&'p str>, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(string)) => Ok(Some(string)),
+        Some(_) => Err(RpcError::invalid_params(format!(
+            "Parameter {name:?} must be a string"
+        ))),
+    }
+}
+
+fn bool_param(params: &Value, name: &str, default: bool) -> std::result::Result<bool, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(default),
+        Some(Value::Bool(value)) => Ok(*value),
+        Some(_) => Err(RpcError::invalid_params(format!(
+            "Parameter {name:?} must be a boolean"
+        ))),
+    }
+}
+
+fn parse_format(name: &str) -> std::result::Result<OutputFormat, RpcError> {
+    match name {
+        "plain" => Ok(OutputFormat::Plain),
+        "ansi" => Ok(OutputFormat::Ansi),
+        "html" => Ok(OutputFormat::Html),
+        "css_colors" => Ok(OutputFormat::CssColors),
+        _ => Err(RpcError::invalid_params(format!(
+            "Unknown format {name:?}; expected plain, ansi, html or css_colors"
+        ))),
+    }
+}
+
+/// Everything requests need while the data file stays loaded.
+struct Session<'s, 'a, W> {
+    data: &'a GMData,
+    ctx: &'s GameContext<'a>,
+    output: &'s Mutex<W>,
+    /// Decompiled source (or error message) of each root entry, indexed like `data.codes`.
+    sources: Vec<OnceLock<std::result::Result<String, String>>>,
+    xrefs: OnceLock<XrefIndex>,
+    owners: OnceLock<OwnerIndex>,
+}
+
+impl<'s, 'a, W: Write + Send> Session<'s, 'a, W> {
+    fn new(data: &'a GMData, ctx: &'s GameContext<'a>, output: &'s Mutex<W>) -> Self {
+        let sources = (0..data.codes.len()).map(|_| OnceLock::new()).collect();
+        Self {
+            data,
+            ctx,
+            output,
+            sources,
+            xrefs: OnceLock::new(),
+            owners: OnceLock::new(),
+        }
+    }
+
+    /// Handles requests until the input ends (returns `None`) or a reload is requested.
+    /// All queued and running requests are finished before returning.
+    fn run(
+        &self,
+        lines: &mut impl Iterator<Item = std::io::Result<String>>,
+    ) -> Result<Option<Request>> {
+        let workers: usize = thread::available_parallelism().map_or(1, usize::from);
+        let (sender, receiver) = mpsc::channel::<Request>();
+        let receiver = Mutex::new(receiver);
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    loop {
+                        // The lock is only held while waiting for the next request
+                        let next = receiver
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .recv();
+                        // Fails once the sender is dropped and the queue is empty
+                        let Ok(request) = next else { break };
+                        let result = self.handle(&request.method, &request.params);
+                        respond(self.output, request.id, result);
+                    }
+                });
+            }
+            // Moved into this closure, so it is dropped when returning, before the workers
+            // are joined. They finish the queued requests and stop.
+            let sender = sender;
+            for line in lines {
+                let line = line.map_err(|e| libgm::Error::new(format!("reading request: {e}")))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match parse_request(&line) {
+                    Ok(request) if request.method == "reload" => return Ok(Some(request)),
+                    Ok(request) => {
+                        // Cannot fail: the workers keep the receiver until the sender is dropped
+                        let _ = sender.send(request);
+                    }
+                    Err(error) => respond(self.output, Some(Value::Null), Err(error)),
+                }
+            }
+            Ok(None)
+        })
+    }
+
+    fn handle(&self, method: &str, params: &Value) -> RpcResult {
+        match method {
+            "decompile" => self.decompile(params),
+            "search" => self.search(params),
+            "list_codes" => self.list_codes(params),
+            "xrefs" => self.xrefs(params),
+            "compile" => self.compile(params),
+            _ => Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("Unknown method {method:?}"),
+            }),
+        }
+    }
+
+    fn decompile(&self, params: &Value) -> RpcResult {
+        let code_ref: GMRef<GMCode> = self.data.codes.ref_by_name(str_param(params, "code")?)?;
+        let format =
+            optional_str_param(params, "format")?.map_or(Ok(OutputFormat::Plain), parse_format)?;
+        let source = self
+            .ctx
+            .decompile_with_format(code_ref, self.data, format)?;
+        Ok(json!({ "source": source }))
+    }
+
+    fn list_codes(&self, params: &Value) -> RpcResult {
+        let filter: &str = optional_str_param(params, "filter")?.unwrap_or("");
+        let roots_only: bool = bool_param(params, "roots_only", false)?;
+        let owners: &OwnerIndex = self.owners.get_or_init(|| build_owner_index(self.data));
+        let codes: Vec<Value> = self
+            .data
+            .codes
+            .elements()
+            .iter()
+            .enumerate()
+            .filter(|(_, code)| code.name.contains(filter) && (!roots_only || code.is_root()))
+            .map(|(i, code)| {
+                let owner = owners.describe(GMRef::from(i), self.data);
+                json!({ "name": code.name, "root": code.is_root(), "owner": owner })
+            })
+            .collect();
+        Ok(Value::Array(codes))
+    }
+
+    fn search(&self, params: &Value) -> RpcResult {
+        let query: &str = str_param(params, "query")?;
+        let case_sensitive: bool = bool_param(params, "case_sensitive", true)?;
+        let limit: usize = match params.get("limit") {
+            None | Some(Value::Null) => DEFAULT_SEARCH_LIMIT,
+            Some(value) => value
+                .as_u64()
+                .and_then(|limit| usize::try_from(limit).ok())
+                .ok_or_else(|| RpcError::invalid_params("Parameter \"limit\" must be a count"))?,
+        };
+        let query: String = if case_sensitive {
+            query.to_owned()
+        } else {
+            query.to_lowercase()
+        };
+
+        self.decompile_all_roots();
+        let mut matches: Vec<Value> = Vec::new();
+        for (code, source) in self.data.codes.elements().iter().zip(&self.sources) {
+            // Entries which failed to decompile (or children) are not searchable
+            let Some(Ok(source)) = source.get() else {
+                continue;
+            };
+            for (number, line) in source.lines().enumerate() {
+                let found = if case_sensitive {
+                    line.contains(&query)
+                } else {
+                    line.to_lowercase().contains(&query)
+                };
+                if !found {
+                    continue;
+                }
+                if matches.len() == limit {
+                    return Ok(json!({ "matches": matches, "truncated": true }));
+                }
+                matches.push(json!({ "code": code.name, "line": number + 1, "text": line }));
+            }
+        }
+        Ok(json!({ "matches": matches, "truncated": false }))
+    }
+
+    /// Fills the source cache for all root entries, spread over all CPU cores.
+    fn decompile_all_roots(&self) {
+        let threads: usize = thread::available_parallelism().map_or(1, usize::from);
+        let codes = self.data.codes.elements();
+        thread::scope(|scope| {
+            for offset in 0..threads {
+                scope.spawn(move || {
+                    for i in (offset..codes.len()).step_by(threads) {
+                        if codes[i].is_root() {
+                            self.root_source(i);
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    fn root_source(&self, index: usize) -> &std::result::Result<String, String> {
+        self.sources[index].get_or_init(|| {
+            self.ctx
+                .decompile(GMRef::from(index), self.data)
+                .map_err(|e| e.chain_pretty())
+        })
+    }
+
+    fn xrefs(&self, params: &Value) -> RpcResult {
+        let name: &str = str_param(params, "name")?;
+        let index: &XrefIndex = self.xrefs.get_or_init(|| xref::build(self.data));
+        let references: Vec<Value> = index
+            .references_to(name)
+            .map(|xref| {
+                let code = self
+                    .data
+                    .codes
+                    .by_ref(xref.code)
+                    .map_or("", |code| &code.name);
+                json!({ "code": code, "address": xref.address, "kind": xref.kind.as_str() })
+            })
+            .collect();
+        Ok(Value::Array(references))
+    }
+
+    fn compile(&self, params: &Value) -> RpcResult {
+        let errors: Vec<String> = self.ctx.compile_errors(str_param(params, "source")?)?;
+        Ok(json!({ "errors": errors }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::{INVALID_PARAMS, INVALID_REQUEST, PARSE_ERROR, bool_param, parse_request};
+
+    #[test]
+    fn parses_requests() {
+        let request =
+            parse_request(r#"{"jsonrpc":"2.0","id":3,"method":"xrefs","params":{"name":"hp"}}"#)
+                .unwrap();
+        assert_eq!(request.id, Some(json!(3)));
+        assert_eq!(request.method, "xrefs");
+        assert_eq!(request.params, json!({ "name": "hp" }));
+
+        let notification = parse_request(r#"{"jsonrpc":"2.0","method":"reload"}"#).unwrap();
+        assert_eq!(notification.id, None);
+        assert_eq!(notification.params, Value::Null);
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        assert_eq!(parse_request("{").unwrap_err().code, PARSE_ERROR);
+        assert_eq!(
+            parse_request(r#"{"id":1}"#).unwrap_err().code,
+            INVALID_REQUEST
+        );
+    }
+
+    #[test]
+    fn reads_bool_params() {
+        let params = json!({ "roots_only": true, "case_sensitive": "no" });
+        assert!(bool_param(&params, "roots_only", false).unwrap());
+        assert!(bool_param(&params, "missing", true).unwrap());
+        assert_eq!(
+            bool_param(&params, "case_sensitive", true)
+                .unwrap_err()
+                .code,
+            INVALID_PARAMS
+        );
+    }
+}
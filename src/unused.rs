@@ -0,0 +1,373 @@
+use std::{
+    collections::{HashMap, HashSet},
+    thread,
+};
+
+use libgm::{
+    gamemaker::elements::room::{GMRoomLayerData, GMRoomTile, GMRoomTileTexture},
+    gml::GMCode,
+    prelude::*,
+};
+use serde_json::{Value, json};
+
+use crate::{
+    GameContext,
+    callgraph::build_call_graph,
+    gamemaker::{AssetKind, child_function_name},
+    highlight::tokenize,
+    owner::{OwnerIndex, build_owner_index},
+    primitives::RustStr,
+    xref::{self, XrefIndex},
+};
+
+/// Everything [`find_unused_assets`] found to be unused.
+/// Assets are sorted by kind and name, code entries by index.
+#[derive(Debug, Clone, Default)]
+pub struct UnusedReport {
+    /// Assets that are neither referenced by code nor by objects and rooms.
+    pub assets: Vec<(AssetKind, String)>,
+    /// Scripts (GMS1) and GMLv2 functions that are never called or referenced.
+    pub functions: Vec<GMRef<GMCode>>,
+    /// Root code entries that are not attached to any object event, room,
+    /// room instance, timeline moment or script.
+    pub detached_code: Vec<GMRef<GMCode>>,
+    /// Root code entries that failed to decompile.
+    /// Assets they use are only found if referenced with `pushref` (GameMaker 2023.8+),
+    /// so some of the reported assets may be used by these entries.
+    pub undecompiled: Vec<GMRef<GMCode>>,
+}
+
+/// Decompiles all code to find the assets, scripts and functions nothing refers to,
+/// and the code entries nothing owns.
+///
+/// An asset counts as used if its name appears in decompiled code (outside of strings
+/// and comments), if a `pushref` instruction references it, if an object uses it
+/// as sprite, mask or parent, if a room contains an instance of it, lists it as background,
+/// uses it for tiles or places it on a layer (tile sets, background sprites, asset layer
+/// sprites and sequences), or if it is part of the room order.
+/// Assets only looked up by name at runtime (e.g. `asset_get_index("spr_player")`)
+/// are reported as unused.
+///
+/// A script or function counts as used if it is called (see [`build_call_graph`]),
+/// pushed as a value, or its name appears in decompiled code other than its declaration.
+///
+/// Root entries are decompiled in parallel, which may take a while for big games.
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> libgm::Result<()> {
+/// let data = libgm::parse_file("./data.win")?;
+/// let ctx = underanalyzer::GameContext::new(&data)?;
+/// let report = underanalyzer::find_unused_assets(&ctx, &data);
+/// for (kind, name) in &report.assets {
+///     println!("unused {}: {name}", kind.as_str());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn find_unused_assets(ctx: &GameContext, data: &GMData) -> UnusedReport {
+    let codes: &[GMCode] = data.codes.elements();
+    let sources: Vec<Option<String>> = decompile_roots(ctx, data);
+    let index: XrefIndex = xref::build(data);
+    let graph = build_call_graph(data);
+
+    let mut used: HashSet<&str> = sources
+        .iter()
+        .flatten()
+        .flat_map(|source| referenced_identifiers(source))
+        .collect();
+    used.extend(index.assets().map(|(_, name, _)| name));
+    asset_references(data, &mut used);
+
+    let mut report = UnusedReport::default();
+    for (kind, names) in ctx.asset_tables() {
+        let unused = names
+            .as_slice()
+            .iter()
+            .map(RustStr::as_str)
+            .filter(|name| !used.contains(name));
+        report
+            .assets
+            .extend(unused.map(|name| (kind, name.to_owned())));
+    }
+    report.assets.sort();
+
+    let called: HashSet<u32> = graph
+        .calls()
+        .iter()
+        .map(|call| u32::from(call.callee))
+        .collect();
+    let parents: HashMap<usize, usize> = parent_indices(data);
+    for (i, code) in codes.iter().enumerate() {
+        let name: &str = match parents.get(&i) {
+            Some(&parent) => child_function_name(&code.name, &codes[parent].name),
+            None => match code.name.strip_prefix("gml_Script_") {
+                Some(script) => script,
+                None => continue,
+            },
+        };
+        let referenced = called.contains(&(i as u32))
+            || !index.function(&code.name).is_empty()
+            || !index.function(name).is_empty()
+            || used.contains(name);
+        if !referenced {
+            report.functions.push(GMRef::from(i));
+        }
+    }
+
+    let owners: OwnerIndex = build_owner_index(data);
+    for (i, code) in codes.iter().enumerate() {
+        if code.is_root() && owners.owner(GMRef::from(i)).is_none() {
+            report.detached_code.push(GMRef::from(i));
+        }
+    }
+    for (i, source) in sources.iter().enumerate() {
+        if codes[i].is_root() && source.is_none() {
+            report.undecompiled.push(GMRef::from(i));
+        }
+    }
+    report
+}
+
+/// Decompiles every root entry, spread over all CPU cores.
+/// Child entries and failed decompilations are `None`.
+fn decompile_roots(ctx: &GameContext, data: &GMData) -> Vec<Option<String>> {
+    let codes: &[GMCode] = data.codes.elements();
+    let threads: usize = thread::available_parallelism().map_or(1, usize::from);
+    let mut sources: Vec<Option<String>> = vec![None; codes.len()];
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|offset| {
+                scope.spawn(move || {
+                    (offset..codes.len())
+                        .step_by(threads)
+                        .filter(|&i| codes[i].is_root())
+                        .map(|i| (i, ctx.decompile(GMRef::from(i), data).ok()))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        for worker in workers {
+            for (i, source) in worker.join().unwrap_or_default() {
+                sources[i] = source;
+            }
+        }
+    });
+    sources
+}
+
+/// Identifiers in GML source, except names of declared functions.
+fn referenced_identifiers(source: &str) -> impl Iterator<Item = &str> {
+    let mut previous: &str = "";
+    tokenize(source).filter_map(move |(token, class)| {
+        if token.trim().is_empty() {
+            return None;
+        }
+        let declaration = previous == "function";
+        previous = token;
+        let identifier =
+            class.is_none() && token.starts_with(|c: char| c.is_alphabetic() || c == '_');
+        (identifier && !declaration).then_some(token)
+    })
+}
+
+/// Adds the names of assets referenced by objects and rooms.
+fn asset_references<'a>(data: &'a GMData, used: &mut HashSet<&'a str>) {
+    for object in data.game_objects.elements() {
+        let sprites = [object.sprite, object.texture_mask].into_iter().flatten();
+        used.extend(sprites.filter_map(|sprite| Some(data.sprites.by_ref(sprite).ok()?.name())));
+        if let Some(parent) = object.parent
+            && let Ok(parent) = data.game_objects.by_ref(parent)
+        {
+            used.insert(parent.name());
+        }
+    }
+    for room in data.rooms.elements() {
+        for instance in &room.game_objects {
+            if let Ok(object) = data.game_objects.by_ref(instance.object_definition) {
+                used.insert(object.name());
+            }
+        }
+        for background in &room.backgrounds {
+            if let Some(definition) = background.background_definition
+                && let Ok(definition) = data.backgrounds.by_ref(definition)
+            {
+                used.insert(definition.name());
+            }
+        }
+        for tile in &room.tiles {
+            tile_asset(data, tile, used);
+        }
+        for layer in &room.layers {
+            layer_assets(data, &layer.data, used);
+        }
+    }
+    for &room in &data.general_info.room_order {
+        if let Ok(room) = data.rooms.by_ref(room) {
+            used.insert(room.name());
+        }
+    }
+}
+
+/// Adds the names of assets placed on a room layer: the tile set of a tile layer,
+/// the sprite of a background layer, and the tiles, sprites and sequences of an asset layer.
+fn layer_assets<'a>(data: &'a GMData, layer: &'a GMRoomLayerData, used: &mut HashSet<&'a str>) {
+    match layer {
+        GMRoomLayerData::Tiles(tiles) => {
+            if let Some(background) = tiles.background
+                && let Ok(background) = data.backgrounds.by_ref(background)
+            {
+                used.insert(background.name());
+            }
+        }
+        GMRoomLayerData::Background(background) => {
+            if let Some(sprite) = background.sprite
+                && let Ok(sprite) = data.sprites.by_ref(sprite)
+            {
+                used.insert(sprite.name());
+            }
+        }
+        GMRoomLayerData::Assets(assets) => {
+            for tile in &assets.legacy_tiles {
+                tile_asset(data, tile, used);
+            }
+            for instance in &assets.sprites {
+                if let Ok(sprite) = data.sprites.by_ref(instance.sprite) {
+                    used.insert(sprite.name());
+                }
+            }
+            for instance in &assets.sequences {
+                if let Ok(sequence) = data.sequences.by_ref(instance.sequence) {
+                    used.insert(sequence.name());
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Adds the name of the background (GMS1) or sprite a tile is cut from.
+fn tile_asset<'a>(data: &'a GMData, tile: &'a GMRoomTile, used: &mut HashSet<&'a str>) {
+    let name = match tile.texture {
+        GMRoomTileTexture::Background(background) => {
+            data.backgrounds.by_ref(background).map(|b| b.name())
+        }
+        GMRoomTileTexture::Sprite(sprite) => data.sprites.by_ref(sprite).map(|s| s.name()),
+    };
+    if let Ok(name) = name {
+        used.insert(name);
+    }
+}
+
+/// The root entry index of every child entry, by child entry index.
+fn parent_indices(data: &GMData) -> HashMap<usize, usize> {
+    let codes: &[GMCode] = data.codes.elements();
+    let mut parents: HashMap<usize, usize> = HashMap::new();
+    for (i, code) in codes.iter().enumerate() {
+        if code.is_root() {
+            for child in GMCode::find_children(GMRef::from(i), data) {
+                parents.insert(u32::from(child) as usize, i);
+            }
+        }
+    }
+    parents
+}
+
+impl UnusedReport {
+    /// Formats the report as a plain text listing, one section per category.
+    #[must_use]
+    pub fn to_text(&self, data: &GMData) -> String {
+        let mut output = String::from("Unused assets:\n");
+        for (kind, name) in &self.assets {
+            output += &format!("    {} {name}\n", kind.as_str());
+        }
+        let sections = [
+            ("Uncalled scripts and functions", &self.functions),
+            ("Code entries without owner", &self.detached_code),
+            (
+                "Code entries that failed to decompile (their asset uses may be missing)",
+                &self.undecompiled,
+            ),
+        ];
+        for (title, codes) in sections {
+            output += &format!("\n{title}:\n");
+            for &code in codes {
+                output += &format!("    {}\n", code_name(data, code));
+            }
+        }
+        output
+    }
+
+    /// Exports the report as JSON, with code entries referred to by name:
+    ///
+    /// ```json
+    /// {
+    ///   "assets": { "sprite": ["spr_unused"] },
+    ///   "functions": ["gml_Script_scr_old"],
+    ///   "detached_code": ["gml_Object_obj_removed_Create_0"],
+    ///   "undecompiled": []
+    /// }
+    /// ```
+    #[must_use]
+    pub fn to_json(&self, data: &GMData) -> Value {
+        let mut assets = serde_json::Map::new();
+        for (kind, name) in &self.assets {
+            let names = assets
+                .entry(kind.as_str())
+                .or_insert_with(|| Value::Array(Vec::new()));
+            if let Value::Array(names) = names {
+                names.push(Value::from(name.as_str()));
+            }
+        }
+        let names = |codes: &[GMRef<GMCode>]| -> Vec<&str> {
+            codes.iter().map(|&code| code_name(data, code)).collect()
+        };
+        json!({
+            "assets": assets,
+            "functions": names(&self.functions),
+            "detached_code": names(&self.detached_code),
+            "undecompiled": names(&self.undecompiled),
+        })
+    }
+}
+
+fn code_name(data: &GMData, code: GMRef<GMCode>) -> &str {
+    data.codes.by_ref(code).map_or("", |code| &code.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use libgm::prelude::*;
+    use serde_json::json;
+
+    use super::{UnusedReport, referenced_identifiers};
+    use crate::gamemaker::AssetKind;
+
+    #[test]
+    fn skips_declarations() {
+        let source = "function scr_a(arg)\n{\n    return scr_b(\"scr_c\", spr_x); // scr_d\n}";
+        let identifiers: Vec<&str> = referenced_identifiers(source).collect();
+        assert_eq!(identifiers, ["function", "arg", "return", "scr_b", "spr_x"]);
+    }
+
+    #[test]
+    fn exports_json() {
+        let data: GMData = GMData::default();
+        let report = UnusedReport {
+            assets: vec![
+                (AssetKind::Sprite, "spr_a".to_owned()),
+                (AssetKind::Sprite, "spr_b".to_owned()),
+                (AssetKind::Sound, "snd_a".to_owned()),
+            ],
+            ..UnusedReport::default()
+        };
+        let expected = json!({
+            "assets": { "sprite": ["spr_a", "spr_b"], "sound": ["snd_a"] },
+            "functions": [],
+            "detached_code": [],
+            "undecompiled": [],
+        });
+        assert_eq!(report.to_json(&data), expected);
+    }
+}
@@ -0,0 +1,365 @@
+//! Cross references between code and the variables, functions and assets it uses.
+
+use std::collections::BTreeMap;
+
+use libgm::{
+    gml::{GMCode, instruction::Instruction as LibGMInstruction},
+    prelude::*,
+};
+use serde_json::{Value, json};
+
+use crate::gamemaker::{AssetKind, Code};
+
+/// How an instruction uses what it references.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessKind {
+    /// A variable is pushed onto the stack.
+    Read,
+    /// A variable is assigned (popped into).
+    Write,
+    /// A function or script is called.
+    Call,
+    /// A function or asset is pushed as a value, e.g. `method(self, fn)` or `spr_player`.
+    Reference,
+}
+
+impl AccessKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::Call => "call",
+            Self::Reference => "reference",
+        }
+    }
+}
+
+/// A single instruction referencing a variable, function or asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Xref {
+    /// The root code entry containing the instruction.
+    /// GMLv2 functions are part of their root entry's bytecode.
+    pub code: GMRef<GMCode>,
+    /// Byte offset of the instruction from the start of the root entry.
+    pub address: u32,
+    pub kind: AccessKind,
+}
+
+/// Every use of every variable, function and asset in the code of a data file.
+/// Build it using [`build`].
+///
+/// Names are the names of the data file's variables, functions and assets.
+/// Variables with the same name in different scopes (e.g. `self.hp` and `global.hp`) are merged.
+#[derive(Debug, Clone, Default)]
+pub struct XrefIndex {
+    variables: BTreeMap<String, Vec<Xref>>,
+    functions: BTreeMap<String, Vec<Xref>>,
+    assets: BTreeMap<(AssetKind, String), Vec<Xref>>,
+}
+
+/// Scans the instructions of every root code entry once and records
+/// which variables, functions and assets they use.
+///
+/// Asset references are only recognized if the game uses `pushref` instructions
+/// (GameMaker 2023.8 and later); before that, assets are plain integers.
+/// Code entries that cannot be converted (e.g. because of broken references) are skipped.
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> libgm::Result<()> {
+/// let data = libgm::parse_file("./data.win")?;
+/// let index = underanalyzer::xref::build(&data);
+/// for write in index.writes("hp") {
+///     let code = data.codes.by_ref(write.code)?;
+///     println!("{} writes hp at {}", code.name, write.address);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn build(data: &GMData) -> XrefIndex {
+    let mut index = XrefIndex::default();
+    for (i, gm_code) in data.codes.elements().iter().enumerate() {
+        if !gm_code.is_root() {
+            continue;
+        }
+        let code_ref = GMRef::from(i);
+        let Ok(code) = Code::try_from_libgm(code_ref, data) else {
+            continue;
+        };
+
+        for (gm_instr, (address, instr)) in gm_code
+            .instructions
+            .iter()
+            .zip(code.addressed_instructions())
+        {
+            let xref = |kind| Xref {
+                code: code_ref,
+                address,
+                kind,
+            };
+            if let Some(name) = instr.variable_name() {
+                let kind = if instr.is_pop() {
+                    AccessKind::Write
+                } else {
+                    AccessKind::Read
+                };
+                index.record_variable(name, xref(kind));
+            }
+            if let Some(name) = instr.function_name() {
+                let kind = if instr.is_call() {
+                    AccessKind::Call
+                } else {
+                    AccessKind::Reference
+                };
+                index.record_function(name, xref(kind));
+            }
+            if let LibGMInstruction::PushReference { asset_reference } = gm_instr
+                && let Some((kind, name)) = asset_name(data, asset_reference.build() as i32)
+            {
+                index.record_asset(kind, name, xref(AccessKind::Reference));
+            }
+        }
+    }
+    index
+}
+
+/// Resolves an encoded asset reference (type in the upper 8 bits, index in the lower 24 bits).
+pub(crate) fn asset_name(data: &GMData, reference: i32) -> Option<(AssetKind, String)> {
+    let kind = AssetKind::from_reference_type(u8::try_from(reference >> 24).ok()?)?;
+    let index = usize::try_from(reference & 0xFF_FF_FF).ok()?;
+    let name: &str = match kind {
+        AssetKind::Object => name_at(&data.game_objects, index)?,
+        AssetKind::Sprite => name_at(&data.sprites, index)?,
+        AssetKind::Sound => name_at(&data.sounds, index)?,
+        AssetKind::Room => name_at(&data.rooms, index)?,
+        AssetKind::Background => name_at(&data.backgrounds, index)?,
+        AssetKind::Path => name_at(&data.paths, index)?,
+        AssetKind::Script => name_at(&data.scripts, index)?,
+        AssetKind::Font => name_at(&data.fonts, index)?,
+        AssetKind::Timeline => name_at(&data.timelines, index)?,
+        AssetKind::Shader => name_at(&data.shaders, index)?,
+        AssetKind::Sequence => name_at(&data.sequences, index)?,
+        AssetKind::AnimationCurve => name_at(&data.animation_curves, index)?,
+        AssetKind::ParticleSystem => name_at(&data.particle_systems, index)?,
+        // Named like Underanalyzer prints them
+        AssetKind::RoomInstance => return Some((kind, format!("inst_{index}"))),
+    };
+    Some((kind, name.to_owned()))
+}
+
+fn name_at(chunk: &impl GMNamedListChunk, index: usize) -> Option<&str> {
+    chunk.elements().get(index).map(|element| element.name())
+}
+
+impl XrefIndex {
+    fn record_variable(&mut self, name: &str, xref: Xref) {
+        self.variables
+            .entry(name.to_owned())
+            .or_default()
+            .push(xref);
+    }
+
+    fn record_function(&mut self, name: &str, xref: Xref) {
+        self.functions
+            .entry(name.to_owned())
+            .or_default()
+            .push(xref);
+    }
+
+    fn record_asset(&mut self, kind: AssetKind, name: String, xref: Xref) {
+        self.assets.entry((kind, name)).or_default().push(xref);
+    }
+
+    /// All reads and writes of variables with this name.
+    #[must_use]
+    pub fn variable(&self, name: &str) -> &[Xref] {
+        self.variables.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// All reads of variables with this name.
+    pub fn reads(&self, name: &str) -> impl Iterator<Item = &Xref> {
+        self.variable(name)
+            .iter()
+            .filter(|xref| xref.kind == AccessKind::Read)
+    }
+
+    /// All assignments to variables with this name.
+    pub fn writes(&self, name: &str) -> impl Iterator<Item = &Xref> {
+        self.variable(name)
+            .iter()
+            .filter(|xref| xref.kind == AccessKind::Write)
+    }
+
+    /// All calls of and references to the function (or script) with this name.
+    #[must_use]
+    pub fn function(&self, name: &str) -> &[Xref] {
+        self.functions.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// All call sites of the function (or script) with this name.
+    pub fn calls(&self, name: &str) -> impl Iterator<Item = &Xref> {
+        self.function(name)
+            .iter()
+            .filter(|xref| xref.kind == AccessKind::Call)
+    }
+
+    /// All references to the asset with this kind and name.
+    #[must_use]
+    pub fn asset(&self, kind: AssetKind, name: &str) -> &[Xref] {
+        self.assets
+            .get(&(kind, name.to_owned()))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// All uses of variables, functions and assets with this name.
+    pub fn references_to<'s>(&'s self, name: &'s str) -> impl Iterator<Item = &'s Xref> {
+        let assets = self
+            .assets
+            .iter()
+            .filter(move |((_, asset), _)| asset == name)
+            .flat_map(|(_, xrefs)| xrefs);
+        self.variable(name)
+            .iter()
+            .chain(self.function(name))
+            .chain(assets)
+    }
+
+    /// All referenced variable names and their uses, sorted by name.
+    pub fn variables(&self) -> impl Iterator<Item = (&str, &[Xref])> {
+        self.variables
+            .iter()
+            .map(|(name, xrefs)| (name.as_str(), xrefs.as_slice()))
+    }
+
+    /// All referenced function names and their uses, sorted by name.
+    pub fn functions(&self) -> impl Iterator<Item = (&str, &[Xref])> {
+        self.functions
+            .iter()
+            .map(|(name, xrefs)| (name.as_str(), xrefs.as_slice()))
+    }
+
+    /// All referenced assets and their uses, sorted by kind and name.
+    pub fn assets(&self) -> impl Iterator<Item = (AssetKind, &str, &[Xref])> {
+        self.assets
+            .iter()
+            .map(|((kind, name), xrefs)| (*kind, name.as_str(), xrefs.as_slice()))
+    }
+
+    /// Exports the index as JSON, with code entries referred to by name:
+    ///
+    /// ```json
+    /// {
+    ///   "variables": { "hp": [{ "code": "gml_Object_obj_player_Step_0", "address": 8, "kind": "write" }] },
+    ///   "functions": { "scr_damage": [{ "code": "...", "address": 40, "kind": "call" }] },
+    ///   "assets": { "sprite": { "spr_player": [{ "code": "...", "address": 16, "kind": "reference" }] } }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn to_json(&self, data: &GMData) -> Value {
+        let export = |xrefs: &[Xref]| -> Value {
+            xrefs
+                .iter()
+                .map(|xref| {
+                    let code = data.codes.by_ref(xref.code).map_or("", |code| &code.name);
+                    json!({ "code": code, "address": xref.address, "kind": xref.kind.as_str() })
+                })
+                .collect()
+        };
+
+        let variables: serde_json::Map<String, Value> = self
+            .variables()
+            .map(|(name, xrefs)| (name.to_owned(), export(xrefs)))
+            .collect();
+        let functions: serde_json::Map<String, Value> = self
+            .functions()
+            .map(|(name, xrefs)| (name.to_owned(), export(xrefs)))
+            .collect();
+        let mut assets = serde_json::Map::new();
+        for (kind, name, xrefs) in self.assets() {
+            let by_name = assets
+                .entry(kind.as_str())
+                .or_insert_with(|| Value::Object(serde_json::Map::new()));
+            if let Value::Object(by_name) = by_name {
+                by_name.insert(name.to_owned(), export(xrefs));
+            }
+        }
+        json!({ "variables": variables, "functions": functions, "assets": assets })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libgm::prelude::*;
+    use serde_json::json;
+
+    use super::{AccessKind, AssetKind, Xref, XrefIndex};
+
+    fn xref(address: u32, kind: AccessKind) -> Xref {
+        Xref {
+            code: GMRef::from(0),
+            address,
+            kind,
+        }
+    }
+
+    #[test]
+    fn queries_by_kind() {
+        let mut index = XrefIndex::default();
+        index.record_variable("hp", xref(0, AccessKind::Read));
+        index.record_variable("hp", xref(8, AccessKind::Write));
+        index.record_function("scr_hurt", xref(16, AccessKind::Call));
+        index.record_function("scr_hurt", xref(24, AccessKind::Reference));
+        index.record_asset(
+            AssetKind::Sprite,
+            "hp".to_owned(),
+            xref(32, AccessKind::Reference),
+        );
+
+        assert_eq!(
+            index.reads("hp").map(|x| x.address).collect::<Vec<_>>(),
+            [0]
+        );
+        assert_eq!(
+            index.writes("hp").map(|x| x.address).collect::<Vec<_>>(),
+            [8]
+        );
+        assert_eq!(index.calls("scr_hurt").count(), 1);
+        assert_eq!(index.function("scr_hurt").len(), 2);
+        assert_eq!(index.asset(AssetKind::Sprite, "hp").len(), 1);
+        assert!(index.asset(AssetKind::Sound, "hp").is_empty());
+        assert_eq!(index.references_to("hp").count(), 3);
+        assert!(index.variable("missing").is_empty());
+    }
+
+    #[test]
+    fn decodes_asset_types() {
+        assert_eq!(AssetKind::from_reference_type(0), Some(AssetKind::Object));
+        assert_eq!(AssetKind::from_reference_type(4), Some(AssetKind::Path));
+        assert_eq!(
+            AssetKind::from_reference_type(13),
+            Some(AssetKind::Background)
+        );
+        assert_eq!(AssetKind::from_reference_type(12), None);
+    }
+
+    #[test]
+    fn exports_json() {
+        let data: GMData = GMData::default();
+        let mut index = XrefIndex::default();
+        index.record_variable("hp", xref(8, AccessKind::Write));
+        index.record_asset(
+            AssetKind::Sprite,
+            "spr_player".to_owned(),
+            xref(16, AccessKind::Reference),
+        );
+
+        let expected = json!({
+            "variables": { "hp": [{ "code": "", "address": 8, "kind": "write" }] },
+            "functions": {},
+            "assets": { "sprite": { "spr_player": [{ "code": "", "address": 16, "kind": "reference" }] } },
+        });
+        assert_eq!(index.to_json(&data), expected);
+    }
+}